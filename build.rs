@@ -0,0 +1,17 @@
+use std::process::Command;
+
+/// Passes the commit being built to the About window as `GIT_HASH`. Builds from outside a git
+/// checkout, such as a source archive, go without.
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output();
+    if let Ok(output) = output {
+        if output.status.success() {
+            let hash = String::from_utf8_lossy(&output.stdout);
+            println!("cargo:rustc-env=GIT_HASH={}", hash.trim());
+        }
+    }
+}
@@ -0,0 +1,551 @@
+use crate::{
+    analysis,
+    codes::{self, CodeInfo},
+    decoders, manifest,
+};
+use egui::{
+    plot::{HLine, Legend, Line, Plot, PlotPoints, VLine},
+    Color32, DragValue, Grid,
+};
+use hamming::{encode, Code};
+use instant::Instant;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::VecDeque, f64::consts::TAU, time::Duration};
+
+/// The size of each frame's message, in bytes.
+const FRAME_BYTES: usize = 64;
+
+/// Known bits sent bare ahead of each frame, for the receiver to measure the channel with.
+const PILOT_BITS: usize = 512;
+
+/// Frames whose pilot bits the channel's bit error rate is measured over.
+const WINDOW: usize = 32;
+
+/// How far inside a lighter scheme's limit the measured rate has to fall before switching back
+/// to it, so that the scheme doesn't flap back and forth while the rate hovers at a limit.
+const HYSTERESIS: f64 = 0.5;
+
+/// The most frames kept for the timeline. Older ones scroll off.
+const MAX_FRAMES: usize = 2000;
+
+/// The most frames sent in one go when the window falls behind, as after a stall.
+const MAX_CATCH_UP: usize = 100;
+
+/// The lowest bit error rate the timeline shows; anything lower is drawn at it.
+const MIN_PLOTTED_BER: f64 = 1e-5;
+
+/// The ways of sending a frame, from the lightest to the heaviest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Scheme {
+    Bare,
+    Eh16_11,
+    H7_4,
+    Repeat,
+}
+
+impl Scheme {
+    const ALL: [Scheme; 4] = [Scheme::Bare, Scheme::Eh16_11, Scheme::H7_4, Scheme::Repeat];
+
+    fn name(self) -> &'static str {
+        match self {
+            Scheme::Bare => "Bare",
+            Scheme::Eh16_11 => "EH16_11",
+            Scheme::H7_4 => "H7_4",
+            Scheme::Repeat => "Repeat x3",
+        }
+    }
+
+    fn code(self) -> Option<&'static (CodeInfo, Code)> {
+        match self {
+            Scheme::Eh16_11 => codes::pipeline_code(codes::EH16_11.name),
+            Scheme::H7_4 => codes::pipeline_code(codes::H7_4.name),
+            Scheme::Bare | Scheme::Repeat => None,
+        }
+    }
+
+    /// The share of the bits sent that are message.
+    fn rate(self) -> f64 {
+        match self {
+            Scheme::Bare => 1.0,
+            Scheme::Repeat => 1.0 / 3.0,
+            _ => self.code().map_or(1.0, |(info, _)| info.rate()),
+        }
+    }
+
+    /// The highest bit error rate the scheme is used at.
+    fn limit(self) -> f64 {
+        match self {
+            Scheme::Bare => 2e-4,
+            Scheme::Eh16_11 => 2e-3,
+            Scheme::H7_4 => 1e-2,
+            Scheme::Repeat => f64::INFINITY,
+        }
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Scheme::Bare => Color32::LIGHT_BLUE,
+            Scheme::Eh16_11 => Color32::GREEN,
+            Scheme::H7_4 => Color32::YELLOW,
+            Scheme::Repeat => Color32::RED,
+        }
+    }
+
+    /// The scheme to send with next, going from `self` with the channel measured at `ber`: the
+    /// lightest one whose limit is above it, though a lighter one than `self` only once the rate
+    /// is well inside its limit.
+    fn next(self, ber: f64) -> Scheme {
+        let lightest = |margin: f64| {
+            Scheme::ALL
+                .into_iter()
+                .find(|s| ber < s.limit() * margin)
+                .unwrap_or(Scheme::Repeat)
+        };
+        let needed = lightest(1.0);
+        if needed > self {
+            needed
+        } else {
+            lightest(HYSTERESIS).min(self)
+        }
+    }
+
+    /// The chance that a block, a codeword or the three copies of a bit, arrives with a nonzero
+    /// syndrome when each bit is flipped with chance `p`: that the flips aren't themselves a
+    /// codeword. `weights` is the code's weight distribution.
+    fn flagged(self, weights: &[usize], p: f64) -> f64 {
+        match self {
+            Scheme::Bare => 0.0,
+            Scheme::Repeat => 1.0 - p.powi(3) - (1.0 - p).powi(3),
+            _ => {
+                let n = weights.len() - 1;
+                let unseen: f64 = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(w, &count)| {
+                        count as f64 * p.powi(w as i32) * (1.0 - p).powi((n - w) as i32)
+                    })
+                    .sum();
+                1.0 - unseen
+            }
+        }
+    }
+
+    /// Sends `bytes` through a channel flipping each bit with chance `probability`. Returns what
+    /// arrived, and how many blocks there were and how many of them had a nonzero syndrome.
+    /// Codewords that can't be corrected are zeroed.
+    fn send(self, bytes: &[u8], seed: u64, probability: f64) -> (Vec<u8>, usize, usize) {
+        let bits = bytes.len() * 8;
+        match self {
+            Scheme::Bare => {
+                let error = manifest::error_pattern(seed, bytes.len(), probability);
+                let arrived = Iterator::zip(bytes.iter(), error.iter())
+                    .map(|(b, e)| b ^ e)
+                    .collect();
+                (arrived, 0, 0)
+            }
+            Scheme::Repeat => {
+                // Three copies back to back, with each bit taken from the majority of its copies.
+                let error = manifest::error_pattern(seed, bytes.len() * 3, probability);
+                let mut arrived = bytes.to_vec();
+                let mut flagged = 0;
+                for b in 0..bits {
+                    let flips = (0..3)
+                        .filter(|&copy| analysis::get_bit(&error, copy * bits + b))
+                        .count();
+                    flagged += usize::from(flips % 3 != 0);
+                    if flips >= 2 {
+                        analysis::set_bit(&mut arrived, b, !analysis::get_bit(bytes, b));
+                    }
+                }
+                (arrived, bits, flagged)
+            }
+            Scheme::Eh16_11 | Scheme::H7_4 => {
+                let (info, code) = self.code().unwrap();
+                let encoded = encode(bytes, code).unwrap();
+                let error = manifest::error_pattern(seed, encoded.len(), probability);
+                let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+                    .map(|(b, e)| b ^ e)
+                    .collect();
+                let blocks = analysis::codeword_count(info, &received);
+                let flagged = (0..blocks)
+                    .filter_map(|j| analysis::codeword(info, &received, j))
+                    .filter(|word| !analysis::check(info, word).is_valid())
+                    .count();
+                let mut arrived = decoders::decode_blocks(code, info, &received)
+                    .map_or_else(Vec::new, |(decoded, _)| decoded);
+                arrived.resize(bytes.len(), 0);
+                (arrived, blocks, flagged)
+            }
+        }
+    }
+}
+
+/// One frame of the stream.
+struct Frame {
+    at: usize,
+    /// The channel's bit error rate while the frame was sent.
+    channel_ber: f64,
+    /// The bit error rate the receiver measured, up to and including this frame.
+    measured: f64,
+    /// The bit error rate the receiver estimated from syndromes alone, once it had any.
+    blind: Option<f64>,
+}
+
+/// How one scheme has done over the run.
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    frames: usize,
+    /// Message bits that arrived wrong.
+    errors: usize,
+}
+
+struct Run {
+    rng: StdRng,
+    /// Frames sent, including ones scrolled off the timeline.
+    sent: usize,
+    /// When frames were last sent.
+    last: Instant,
+    frames: VecDeque<Frame>,
+    /// Pilot bits flipped in each of the last `WINDOW` frames.
+    pilot_flips: VecDeque<usize>,
+    scheme: Scheme,
+    /// The frames the scheme switched at, and what it switched to.
+    switches: VecDeque<(usize, Scheme)>,
+    /// Indexed like `Scheme::ALL`.
+    totals: [Totals; 4],
+    /// The blocks of each of the last `WINDOW` frames that had any, and how many had a nonzero
+    /// syndrome.
+    syndromes: VecDeque<(Scheme, usize, usize)>,
+    /// The weight distribution of each scheme's code, indexed like `Scheme::ALL`, for the
+    /// schemes that have one.
+    weights: Vec<Vec<usize>>,
+}
+
+impl Run {
+    fn new() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            sent: 0,
+            last: Instant::now(),
+            frames: VecDeque::new(),
+            pilot_flips: VecDeque::new(),
+            // Nothing has been measured yet, so it starts out safe.
+            scheme: Scheme::Repeat,
+            switches: VecDeque::new(),
+            totals: [Totals::default(); 4],
+            syndromes: VecDeque::new(),
+            weights: Scheme::ALL
+                .into_iter()
+                .map(|s| {
+                    s.code()
+                        .map_or_else(Vec::new, |(info, _)| analysis::weight_distribution(info))
+                })
+                .collect(),
+        }
+    }
+
+    fn measured(&self) -> f64 {
+        let flips: usize = self.pilot_flips.iter().sum();
+        flips as f64 / (self.pilot_flips.len().max(1) * PILOT_BITS) as f64
+    }
+
+    /// The bit error rate that best explains the syndromes of the last `WINDOW` frames that had
+    /// any, with no help from the pilot bits: the rate at which as many blocks as were flagged
+    /// are expected to be. `None` while no frame has had syndromes.
+    fn blind(&self) -> Option<f64> {
+        if self.syndromes.is_empty() {
+            return None;
+        }
+        let flagged: usize = self.syndromes.iter().map(|&(_, _, f)| f).sum();
+        let expected = |p: f64| -> f64 {
+            self.syndromes
+                .iter()
+                .map(|&(s, blocks, _)| blocks as f64 * s.flagged(&self.weights[s as usize], p))
+                .sum()
+        };
+        // More flags are expected the higher the rate, up to a half, so it can be bisected for.
+        let (mut low, mut high) = (0.0, 0.5);
+        for _ in 0..50 {
+            let mid = (low + high) / 2.0;
+            if expected(mid) < flagged as f64 {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Some((low + high) / 2.0)
+    }
+
+    /// Sends a frame through a channel with bit error rate `probability`: its pilot bits, which
+    /// update the measurement, and a random message with the current scheme. The measurement
+    /// then picks the scheme for the next frame, as if sent back to the sender.
+    fn frame(&mut self, probability: f64) {
+        let seed = manifest::new_seed(&mut self.rng);
+        let pilot = manifest::error_pattern(seed, PILOT_BITS / 8, probability);
+        self.pilot_flips
+            .push_back(pilot.iter().map(|b| b.count_ones() as usize).sum());
+        if self.pilot_flips.len() > WINDOW {
+            self.pilot_flips.pop_front();
+        }
+
+        let mut message = vec![0; FRAME_BYTES];
+        self.rng.fill(&mut message[..]);
+        let (arrived, blocks, flagged) =
+            self.scheme
+                .send(&message, seed.wrapping_add(1), probability);
+        if blocks > 0 {
+            self.syndromes.push_back((self.scheme, blocks, flagged));
+            if self.syndromes.len() > WINDOW {
+                self.syndromes.pop_front();
+            }
+        }
+        let totals = &mut self.totals[self.scheme as usize];
+        totals.frames += 1;
+        totals.errors += Iterator::zip(message.iter(), arrived.iter())
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum::<usize>();
+
+        let measured = self.measured();
+        self.frames.push_back(Frame {
+            at: self.sent,
+            channel_ber: probability,
+            measured,
+            blind: self.blind(),
+        });
+        self.sent += 1;
+        let next = self.scheme.next(measured);
+        if next != self.scheme {
+            self.switches.push_back((self.sent, next));
+            self.scheme = next;
+        }
+
+        let oldest = self.sent.saturating_sub(MAX_FRAMES);
+        while matches!(self.frames.front(), Some(f) if f.at < oldest) {
+            self.frames.pop_front();
+        }
+        while matches!(self.switches.front(), Some(&(at, _)) if at < oldest) {
+            self.switches.pop_front();
+        }
+    }
+}
+
+/// A channel whose bit error rate swings between quiet and noisy and back, evenly on a log
+/// scale.
+struct Swing {
+    quiet: f64,
+    noisy: f64,
+    /// Frames from one quiet spell to the next.
+    period: usize,
+}
+
+impl Swing {
+    /// The bit error rate while frame `frame` is sent.
+    fn ber(&self, frame: usize) -> f64 {
+        let phase = (frame % self.period) as f64 / self.period as f64;
+        let t = (1.0 - (phase * TAU).cos()) / 2.0;
+        (self.quiet.ln() * (1.0 - t) + self.noisy.ln() * t).exp()
+    }
+}
+
+/// A stream of frames over a channel whose noise rises and falls, with the sender switching
+/// between codes as the receiver's measurement of the channel changes: a light code while the
+/// channel is quiet, for more of the bits sent to be message, and heavier ones as it gets noisy.
+/// The switches are marked on a timeline of the channel.
+pub struct Adaptive {
+    pub open: bool,
+    channel: Swing,
+    /// Frames sent a second.
+    rate: f64,
+    run: Option<Run>,
+}
+
+impl Adaptive {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            channel: Swing {
+                quiet: 1e-5,
+                noisy: 0.03,
+                period: 400,
+            },
+            rate: 20.0,
+            run: None,
+        }
+    }
+
+    /// Sends the frames due while a run is going, whether or not the window is open, and shows
+    /// the window.
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if let Some(run) = &mut self.run {
+            let due = (run.last.elapsed().as_secs_f64() * self.rate) as usize;
+            if due > MAX_CATCH_UP {
+                run.last = Instant::now();
+            } else {
+                run.last += Duration::from_secs_f64(due as f64 / self.rate);
+            }
+            for _ in 0..due.min(MAX_CATCH_UP) {
+                run.frame(self.channel.ber(run.sent));
+            }
+            ctx.request_repaint();
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Adaptive Coding")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        fn probability(value: &mut f64) -> DragValue<'_> {
+            DragValue::new(value)
+                .clamp_range(1e-6..=0.5)
+                .speed(0.0001)
+                .max_decimals(6)
+        }
+        ui.horizontal(|ui| {
+            ui.label("Channel bit error rate from");
+            ui.add(probability(&mut self.channel.quiet));
+            ui.label("to");
+            ui.add(probability(&mut self.channel.noisy));
+            ui.label("and back every");
+            ui.add(
+                DragValue::new(&mut self.channel.period)
+                    .clamp_range(10..=10_000)
+                    .suffix(" frames"),
+            );
+        });
+        ui.horizontal(|ui| {
+            if self.run.is_some() {
+                if ui.button("Stop").clicked() {
+                    self.run = None;
+                }
+            } else if ui.button("Start").clicked() {
+                self.run = Some(Run::new());
+            }
+            ui.label("at");
+            ui.add(
+                DragValue::new(&mut self.rate)
+                    .clamp_range(1.0..=1000.0)
+                    .suffix(" frames/s"),
+            );
+        });
+        ui.small(format!(
+            "Each frame is a random {FRAME_BYTES}-byte message, after {PILOT_BITS} known pilot \
+             bits sent bare. The receiver measures the bit error rate from the pilot bits of the \
+             last {WINDOW} frames, and the sender switches to the lightest scheme whose limit is \
+             above it, or back to a lighter one once the rate is below {}% of that one's limit.",
+            HYSTERESIS * 100.0
+        ));
+        ui.separator();
+
+        let run = match &self.run {
+            Some(run) => run,
+            None => {
+                ui.label("Start the stream to watch the code follow the channel.");
+                return;
+            }
+        };
+        let sent: usize = run.totals.iter().map(|t| t.frames).sum();
+        let channel_bits: f64 = Iterator::zip(Scheme::ALL.iter(), run.totals.iter())
+            .map(|(s, t)| (t.frames * FRAME_BYTES * 8) as f64 / s.rate())
+            .sum();
+        Grid::new("adaptive_stats").show(ui, |ui| {
+            ui.label("Frames sent");
+            ui.label(sent.to_string());
+            ui.end_row();
+            ui.label("Sending with");
+            ui.colored_label(run.scheme.color(), run.scheme.name());
+            ui.end_row();
+            if let Some(frame) = run.frames.back() {
+                ui.label("Channel BER");
+                ui.label(format!("{:.2e}", frame.channel_ber));
+                ui.end_row();
+            }
+            ui.label("Measured BER");
+            ui.label(format!("{:.2e} from the pilot bits", run.measured()));
+            ui.end_row();
+            ui.label("Blind estimate");
+            ui.label(run.blind().map_or_else(
+                || "no syndromes yet".to_string(),
+                |p| format!("{p:.2e} from the syndromes alone"),
+            ));
+            ui.end_row();
+            ui.label("Throughput");
+            ui.label(format!(
+                "{:.2} message bits per bit sent",
+                (sent * FRAME_BYTES * 8) as f64 / channel_bits.max(1.0)
+            ));
+            ui.end_row();
+        });
+        ui.separator();
+        Grid::new("adaptive_schemes").striped(true).show(ui, |ui| {
+            ui.strong("Scheme");
+            ui.strong("Used up to BER");
+            ui.strong("Rate");
+            ui.strong("Frames");
+            ui.strong("Residual BER");
+            ui.end_row();
+            for (scheme, totals) in Iterator::zip(Scheme::ALL.into_iter(), run.totals.iter()) {
+                ui.colored_label(scheme.color(), scheme.name());
+                ui.label(if scheme.limit().is_finite() {
+                    format!("{:.0e}", scheme.limit())
+                } else {
+                    "any".to_string()
+                });
+                ui.label(format!("{:.2}", scheme.rate()));
+                ui.label(totals.frames.to_string());
+                ui.label(if totals.frames > 0 {
+                    let bits = totals.frames * FRAME_BYTES * 8;
+                    format!("{:.2e}", totals.errors as f64 / bits as f64)
+                } else {
+                    "-".to_string()
+                });
+                ui.end_row();
+            }
+        });
+        ui.separator();
+
+        let log = |ber: f64| ber.max(MIN_PLOTTED_BER).log10();
+        let line = |ber: fn(&Frame) -> Option<f64>| {
+            let points: Vec<[f64; 2]> = run
+                .frames
+                .iter()
+                .filter_map(|f| Some([f.at as f64, log(ber(f)?)]))
+                .collect();
+            Line::new(PlotPoints::new(points))
+        };
+        Plot::new("adaptive_timeline")
+            .height(220.0)
+            .legend(Legend::default())
+            .include_y(log(MIN_PLOTTED_BER))
+            .include_y(0.0)
+            .show(ui, |plot| {
+                plot.line(line(|f| Some(f.channel_ber)).name("Channel BER"));
+                plot.line(line(|f| Some(f.measured)).name("Measured BER"));
+                plot.line(line(|f| f.blind).name("Blind estimate"));
+                for scheme in Scheme::ALL {
+                    if scheme.limit().is_finite() {
+                        plot.hline(HLine::new(log(scheme.limit())).color(scheme.color()));
+                    }
+                }
+                for &(at, scheme) in &run.switches {
+                    plot.vline(
+                        VLine::new(at as f64)
+                            .color(scheme.color())
+                            .name(scheme.name()),
+                    );
+                }
+            });
+        ui.small(
+            "x: frame, y: log10 of the bit error rate. Vertical lines mark switches, colored by \
+             the scheme switched to, and horizontal lines each scheme's limit.",
+        );
+        ui.small(format!(
+            "The blind estimate is what a receiver can tell with no pilot bits: how many \
+             codewords, or threes of copies, of the last {WINDOW} coded frames arrived with a \
+             nonzero syndrome, and the bit error rate that would flag that many. Flips that make \
+             another codeword go unseen, and bare frames carry nothing to go on."
+        ));
+    }
+}
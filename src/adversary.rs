@@ -0,0 +1,127 @@
+use crate::{analysis, codes::CodeInfo};
+use egui::DragValue;
+
+/// The most flips tried in one codeword when searching for the most damaging pattern.
+const MAX_FLIPS_PER_CODEWORD: usize = 4;
+
+/// The data bits a decoder taking each codeword on its own gets wrong without noticing, when
+/// `error` is added to a codeword. The code is linear, so this is the same whichever codeword was
+/// sent. A codeword the decoder flags as uncorrectable counts for nothing, since it can be asked
+/// for again.
+fn silent_damage(info: &CodeInfo, error: &[bool]) -> usize {
+    let check = analysis::check(info, error);
+    let mut word = error.to_vec();
+    match (check.distance, check.flip) {
+        (0, _) => {}
+        (1, Some(i)) => word[i] = !word[i],
+        _ => return 0,
+    }
+    analysis::data_bits(info, &word)
+        .into_iter()
+        .filter(|&b| b)
+        .count()
+}
+
+/// The most damaging pattern of each number of flips in one codeword, from none up to `max`,
+/// with the damage it does.
+fn best_patterns(info: &CodeInfo, max: usize) -> Vec<(Vec<usize>, usize)> {
+    let mut best = vec![(Vec::new(), 0)];
+    for flips in 1..=max.min(info.n) {
+        let mut chosen: Vec<usize> = (0..flips).collect();
+        let mut found = (Vec::new(), 0);
+        loop {
+            let mut error = vec![false; info.n];
+            for &i in &chosen {
+                error[i] = true;
+            }
+            let damage = silent_damage(info, &error);
+            if found.0.is_empty() || damage > found.1 {
+                found = (chosen.clone(), damage);
+            }
+            // The next combination in lexicographic order, if there is one.
+            let i = match (0..flips).rev().find(|&i| chosen[i] < info.n - flips + i) {
+                Some(i) => i,
+                None => break,
+            };
+            chosen[i] += 1;
+            for j in i + 1..flips {
+                chosen[j] = chosen[j - 1] + 1;
+            }
+        }
+        best.push(found);
+    }
+    best
+}
+
+/// A channel that flips bits on purpose rather than by chance: given a budget of flips, it
+/// places them where they do the most harm, to show the worst a channel can do against the
+/// average.
+pub struct Adversary {
+    /// The most bits flipped in the whole stream.
+    pub budget: usize,
+}
+
+impl Adversary {
+    pub fn new() -> Self {
+        Self { budget: 12 }
+    }
+
+    /// The most damaging error of at most `budget` flips for a `len`-byte stream of `info`
+    /// codewords, with how many data bits it gets past the decoder. Every codeword is alike, so
+    /// the search is over the patterns of flips within one. The flips go greedily to the pattern
+    /// that does the most damage per flip, spread evenly over the stream, and any left over go
+    /// to the best pattern they can pay for.
+    pub fn pattern(&self, info: &CodeInfo, len: usize) -> (Vec<u8>, usize) {
+        let best = best_patterns(info, MAX_FLIPS_PER_CODEWORD);
+        let per_flip = |w: usize| best[w].1 as f64 / w as f64;
+        let mut error = vec![0; len];
+        let codewords = analysis::codeword_count(info, &error);
+        let w = (1..best.len()).max_by(|&a, &b| per_flip(a).total_cmp(&per_flip(b)));
+        let w = match w.filter(|&w| best[w].1 > 0) {
+            Some(w) => w,
+            // Nothing gets past the decoder, or the codeword is empty.
+            None => return (error, 0),
+        };
+        let full = (self.budget / w).min(codewords);
+        let left = self.budget - full * w;
+        let mut plan = vec![w; full];
+        if full < codewords {
+            // What is left over can't pay for another of the best pattern.
+            let spare = (1..=left.min(best.len() - 1)).max_by_key(|&f| best[f].1);
+            if let Some(f) = spare.filter(|&f| best[f].1 > 0) {
+                plan.push(f);
+            }
+        }
+        let mut damage = 0;
+        for (i, &flips) in plan.iter().enumerate() {
+            let j = i * codewords / plan.len();
+            for &bit in &best[flips].0 {
+                analysis::set_bit(&mut error, j * info.n + bit, true);
+            }
+            damage += best[flips].1;
+        }
+        (error, damage)
+    }
+
+    /// The adversary's budget on one line, with a button to attack with it. Returns whether it
+    /// was pressed.
+    pub fn ui(&mut self, ui: &mut egui::Ui, enabled: bool) -> bool {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.label("Budget");
+            ui.add(
+                DragValue::new(&mut self.budget)
+                    .clamp_range(1..=10_000)
+                    .suffix(" flips"),
+            );
+            ui.button("Attack")
+                .on_hover_text(
+                    "Places the flips where they do the most harm: in as many codewords as it \
+                     can, in the pattern that gets the most wrong data bits past the decoder \
+                     unnoticed. Random flips of the same number mostly land alone and are \
+                     corrected.",
+                )
+                .clicked()
+        })
+        .inner
+    }
+}
@@ -0,0 +1,258 @@
+use crate::codes::CodeInfo;
+
+/// Bit `bit` of `bytes`, counting least significant bit first.
+pub fn get_bit(bytes: &[u8], bit: usize) -> bool {
+    bytes
+        .get(bit / 8)
+        .is_some_and(|b| (b >> (bit % 8)) & 1 == 1)
+}
+
+/// Sets bit `bit` of `bytes`, counting least significant bit first.
+pub fn set_bit(bytes: &mut [u8], bit: usize, value: bool) {
+    let mask = 1 << (bit % 8);
+    if value {
+        bytes[bit / 8] |= mask;
+    } else {
+        bytes[bit / 8] &= !mask;
+    }
+}
+
+/// The bits of codeword `j` of an encoded stream, or `None` if the stream is too short.
+pub fn codeword(info: &CodeInfo, bytes: &[u8], j: usize) -> Option<Vec<bool>> {
+    let start = j * info.n;
+    (start + info.n <= bytes.len() * 8)
+        .then(|| (start..start + info.n).map(|b| get_bit(bytes, b)).collect())
+}
+
+/// The number of whole codewords in an encoded stream.
+pub fn codeword_count(info: &CodeInfo, bytes: &[u8]) -> usize {
+    bytes.len() * 8 / info.n
+}
+
+/// The message bit that bit `bit` of the encoded stream carries: its byte, and its significance
+/// there, from 7 for the most significant bit down to 0. `None` for parity bits.
+pub fn source_bit(info: &CodeInfo, bit: usize) -> Option<(usize, usize)> {
+    let (codeword, i) = info.locate(bit);
+    if info.is_parity(i) {
+        return None;
+    }
+    let m = codeword * info.k + (0..i).filter(|&b| !info.is_parity(b)).count();
+    Some((m / 8, m % 8))
+}
+
+/// How many bits of each whole codeword are flipped by `error`.
+pub fn error_counts(info: &CodeInfo, error: &[u8]) -> Vec<usize> {
+    (0..codeword_count(info, error))
+        .map(|j| {
+            (j * info.n..(j + 1) * info.n)
+                .filter(|&b| get_bit(error, b))
+                .count()
+        })
+        .collect()
+}
+
+/// The first byte of the encoded stream holding part of codeword `j`.
+pub fn codeword_row(info: &CodeInfo, j: usize) -> usize {
+    j * info.n / 8
+}
+
+pub struct Check {
+    /// XOR of the positions of every set bit; zero when every parity check passes.
+    pub syndrome: usize,
+    /// Whether the overall parity is even, for extended codes.
+    pub overall_even: Option<bool>,
+    /// Hamming distance to the nearest codeword.
+    pub distance: usize,
+    /// The bit that, flipped, gives the nearest codeword, when that is unambiguous.
+    pub flip: Option<usize>,
+}
+
+impl Check {
+    pub fn is_valid(&self) -> bool {
+        self.distance == 0
+    }
+}
+
+/// Checks `word`, given in codeword bit order, against the code.
+pub fn check(info: &CodeInfo, word: &[bool]) -> Check {
+    let syndrome = word
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b)
+        .fold(0, |s, (i, _)| s ^ info.position(i));
+    let overall_even = info
+        .extended
+        .then(|| word.iter().filter(|&&b| b).count() % 2 == 0);
+    // Bit `i` is at position `i` in an extended code and `i + 1` otherwise.
+    let bit_at = |position: usize| (0..info.n).find(|&i| info.position(i) == position);
+    let (distance, flip) = match (syndrome, overall_even) {
+        (0, None | Some(true)) => (0, None),
+        (0, Some(false)) => (1, bit_at(0)),
+        (s, None | Some(false)) => match bit_at(s) {
+            Some(i) => (1, Some(i)),
+            // Only reachable in shortened codes, where the syndrome can name a position that
+            // doesn't exist; flipping two bits always works there.
+            None => (2, None),
+        },
+        (_, Some(true)) => (2, None),
+    };
+    Check {
+        syndrome,
+        overall_even,
+        distance,
+        flip,
+    }
+}
+
+/// The data bits of `word`: every bit that isn't a parity bit, in order.
+pub fn data_bits(info: &CodeInfo, word: &[bool]) -> Vec<bool> {
+    (0..info.n)
+        .filter(|&i| !info.is_parity(i))
+        .map(|i| word[i])
+        .collect()
+}
+
+/// Encodes `data` (`k` bits) into a codeword, in codeword bit order.
+pub fn encode_word(info: &CodeInfo, data: &[bool]) -> Vec<bool> {
+    let mut word = vec![false; info.n];
+    let mut data = data.iter();
+    for (i, bit) in word.iter_mut().enumerate() {
+        if !info.is_parity(i) {
+            *bit = *data.next().unwrap_or(&false);
+        }
+    }
+    for p in (0..info.n).filter(|&i| info.is_parity(i) && info.position(i) != 0) {
+        word[p] = (0..info.n)
+            .filter(|&i| i != p && info.covers(p, i))
+            .fold(false, |acc, i| acc ^ word[i]);
+    }
+    if info.extended {
+        word[0] = word[1..].iter().fold(false, |acc, &b| acc ^ b);
+    }
+    word
+}
+
+/// How many codewords there are of each weight, from 0 up to `n`.
+pub fn weight_distribution(info: &CodeInfo) -> Vec<usize> {
+    let mut counts = vec![0; info.n + 1];
+    for data in 0..1usize << info.k {
+        let bits: Vec<bool> = (0..info.k).map(|b| (data >> b) & 1 == 1).collect();
+        let weight = encode_word(info, &bits).into_iter().filter(|&b| b).count();
+        counts[weight] += 1;
+    }
+    counts
+}
+
+/// A nonzero codeword of the least weight, which is the minimum distance. Flipping its bits turns
+/// any codeword into another, so no decoder can tell. In a Hamming code one has a single data bit
+/// set: the one checked by the fewest parity bits.
+pub fn lightest_codeword(info: &CodeInfo) -> Vec<bool> {
+    (0..info.k)
+        .map(|b| {
+            let mut data = vec![false; info.k];
+            data[b] = true;
+            encode_word(info, &data)
+        })
+        .min_by_key(|word| word.iter().filter(|&&bit| bit).count())
+        .unwrap_or_else(|| vec![false; info.n])
+}
+
+/// The largest `k` for which every codeword is enumerated when searching.
+pub const MAX_ENUMERATED_K: usize = 16;
+
+pub struct Candidate {
+    pub distance: usize,
+    pub codeword: Vec<bool>,
+    pub data: Vec<bool>,
+}
+
+/// The `count` codewords nearest to `word`, nearest first, found by trying every codeword.
+/// Returns `None` for codes with too many codewords to enumerate.
+pub fn nearest(info: &CodeInfo, word: &[bool], count: usize) -> Option<Vec<Candidate>> {
+    if info.k > MAX_ENUMERATED_K {
+        return None;
+    }
+    let mut candidates: Vec<Candidate> = (0..1usize << info.k)
+        .map(|m| {
+            let data: Vec<bool> = (0..info.k)
+                .map(|b| (m >> (info.k - 1 - b)) & 1 == 1)
+                .collect();
+            let codeword = encode_word(info, &data);
+            let distance = codeword.iter().zip(word).filter(|(a, b)| a != b).count();
+            Candidate {
+                distance,
+                codeword,
+                data,
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|c| c.distance);
+    candidates.truncate(count);
+    Some(candidates)
+}
+
+/// Packs a word into an integer, bit `i` of the word becoming bit `i` of the integer.
+pub fn to_mask(word: &[bool]) -> u32 {
+    word.iter()
+        .enumerate()
+        .fold(0, |m, (i, &b)| m | (u32::from(b) << i))
+}
+
+pub fn from_mask(mask: u32, n: usize) -> Vec<bool> {
+    (0..n).map(|i| (mask >> i) & 1 == 1).collect()
+}
+
+/// The largest `n` for which a standard array is generated.
+pub const MAX_ARRAY_N: usize = 10;
+
+/// The standard array of a code: one row per coset, headed by its minimum-weight leader, and
+/// one column per codeword. The word in row `r` and column `c` is `leaders[r] ^ codewords[c]`.
+pub struct StandardArray {
+    pub codewords: Vec<u32>,
+    pub leaders: Vec<u32>,
+}
+
+impl StandardArray {
+    pub fn cell(&self, row: usize, col: usize) -> u32 {
+        self.leaders[row] ^ self.codewords[col]
+    }
+
+    /// The row and column of `word`.
+    pub fn locate(&self, word: u32) -> Option<(usize, usize)> {
+        self.leaders.iter().enumerate().find_map(|(r, &leader)| {
+            self.codewords
+                .iter()
+                .position(|&c| c == word ^ leader)
+                .map(|c| (r, c))
+        })
+    }
+}
+
+pub fn standard_array(info: &CodeInfo) -> Option<StandardArray> {
+    if info.n > MAX_ARRAY_N {
+        return None;
+    }
+    let codewords: Vec<u32> = (0..1u32 << info.k)
+        .map(|m| {
+            let data: Vec<bool> = (0..info.k)
+                .map(|b| (m >> (info.k - 1 - b)) & 1 == 1)
+                .collect();
+            to_mask(&encode_word(info, &data))
+        })
+        .collect();
+
+    let mut words: Vec<u32> = (0..1u32 << info.n).collect();
+    words.sort_by_key(|w| (w.count_ones(), *w));
+    let mut seen = vec![false; 1 << info.n];
+    let mut leaders = Vec::new();
+    for w in words {
+        if seen[w as usize] {
+            continue;
+        }
+        leaders.push(w);
+        for &c in &codewords {
+            seen[(w ^ c) as usize] = true;
+        }
+    }
+    Some(StandardArray { codewords, leaders })
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub const ALL: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Easing::Linear => "Linear",
+            Easing::EaseIn => "Ease in",
+            Easing::EaseOut => "Ease out",
+            Easing::EaseInOut => "Ease in-out",
+        }
+    }
+
+    /// Maps linear progress `t` in `0..=1` onto the eased curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationSettings {
+    /// Multiplier on every animation's speed; 2.0 plays them twice as fast.
+    pub speed: f32,
+    pub easing: Easing,
+    /// Skips animations entirely, jumping straight to their end state.
+    pub reduce_motion: bool,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            easing: Easing::EaseInOut,
+            reduce_motion: false,
+        }
+    }
+}
+
+impl AnimationSettings {
+    /// How long an animation that takes `seconds` at normal speed should take.
+    pub fn duration(&self, seconds: f32) -> f32 {
+        if self.reduce_motion {
+            0.0
+        } else {
+            seconds / self.speed.max(0.01)
+        }
+    }
+
+    /// Eased progress in `0..=1` of an animation `elapsed` seconds in, which takes `seconds` at
+    /// normal speed.
+    pub fn progress(&self, elapsed: f32, seconds: f32) -> f32 {
+        let duration = self.duration(seconds);
+        if duration <= 0.0 {
+            1.0
+        } else {
+            self.easing.apply(elapsed / duration)
+        }
+    }
+}
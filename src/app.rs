@@ -0,0 +1,1441 @@
+use crate::{
+    adaptive::Adaptive,
+    adversary::Adversary,
+    analysis,
+    audio::AudioDemo,
+    bookmarks::Bookmarks,
+    burst::Bursts,
+    channel::{self, GilbertElliott},
+    checker::Checker,
+    checksums::Checksums,
+    cli::Launch,
+    codes::{self, CodeInfo},
+    coding_gain::CodingGain,
+    corpus::CorpusWindow,
+    corrections,
+    ensemble::Ensemble,
+    examples::EXAMPLES,
+    exercise::{Bench, Engine},
+    export,
+    file_mode::FileMode,
+    format::InputFormat,
+    help::{Graphics, Help},
+    history::History,
+    image_demo::ImageDemo,
+    ingest::Ingest,
+    interleave::Interleaver,
+    leaderboard::Leaderboard,
+    manifest::{self, FlipCount, Manifest},
+    notes::NotesWindow,
+    overview::{Overview, StageSummary},
+    panel_model::{PanelModel, Stages, View},
+    panels::{self, Arrangement, ByteDisplay, PanelId},
+    platform,
+    recent::Recent,
+    scratchpad::Scratchpad,
+    script::{self, DemoScript, Scene, SceneError},
+    search::Search,
+    sensitivity::Sensitivity,
+    session::{self, Notes, Session},
+    settings::{Settings, SettingsWindow, Theme},
+    share::Share,
+    standard_array::StandardArrayView,
+    tolerance::Tolerance,
+    watch::Watch,
+    welcome::{Start, Welcome},
+    widget::{HammingDemoWidget, MAX_MESSAGE_BYTES},
+};
+use egui::{Align, Color32, Id, Key, LayerId, Order, Rect, Stroke, TextEdit};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::{thread_rng, Rng};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[cfg(feature = "bundle")]
+use crate::bundle::{self, Bundle};
+#[cfg(feature = "explorers")]
+use crate::{bch_explorer::BchExplorer, gf_explorer::GfExplorer};
+#[cfg(feature = "simulation")]
+use crate::{simulate::Simulate, soak::Soak};
+
+/// The files that `App::open` understands.
+const OPEN_EXTENSIONS: &[&str] = if cfg!(feature = "bundle") {
+    &["json", "zip", "toml", "bin"]
+} else {
+    &["json", "toml", "bin"]
+};
+
+/// The error probability the interactive demo starts with: enough for a few flips in the
+/// example message, few enough that most are corrected.
+const DEMO_PROBABILITY: f64 = 0.02;
+
+/// How long a panel stays highlighted after jumping to it, in seconds.
+const FOCUS_TIME: f32 = 1.5;
+
+/// How long encoding and decoding can take before a spinner says so, which spares small messages
+/// a flicker on every keystroke.
+const SPINNER_DELAY: Duration = Duration::from_millis(150);
+
+pub struct App {
+    demo: HammingDemoWidget,
+    prob_str: String,
+    /// The seed to randomize the error with. Empty for a fresh one each time.
+    seed_str: String,
+    share: Share,
+    /// The session last handed to `share` when leading, with the randomization it was taken
+    /// with, so that a new one is only built when something in it changed.
+    published: Option<(Session, Option<(u64, f64)>)>,
+    leaderboard: Leaderboard,
+    exercises: Engine,
+    settings: Settings,
+    settings_window: SettingsWindow,
+    overview: Overview,
+    scratchpad: Scratchpad,
+    checker: Checker,
+    sensitivity: Sensitivity,
+    checksums: Checksums,
+    standard_array: StandardArrayView,
+    file_mode: FileMode,
+    tolerance: Tolerance,
+    ensemble: Ensemble,
+    history: History,
+    script: DemoScript,
+    #[cfg(feature = "simulation")]
+    simulate: Simulate,
+    audio: AudioDemo,
+    image_demo: ImageDemo,
+    coding_gain: CodingGain,
+    #[cfg(feature = "explorers")]
+    gf_explorer: GfExplorer,
+    corpus: CorpusWindow,
+    ingest: Ingest,
+    search: Search,
+    #[cfg(feature = "explorers")]
+    bch_explorer: BchExplorer,
+    focus: Option<(PanelId, Instant)>,
+    scroll_to_focus: bool,
+    /// The panel focus mode leaves undimmed, or `None` when it is off.
+    spotlight: Option<PanelId>,
+    /// The codeword last navigated to.
+    block: Option<usize>,
+    scroll_to_block: bool,
+    bookmarks: Bookmarks,
+    #[cfg(feature = "simulation")]
+    soak: Soak,
+    adaptive: Adaptive,
+    help: Help,
+    welcome: Welcome,
+    recent: Recent,
+    watch: Watch,
+    notes: Notes,
+    notes_window: NotesWindow,
+    /// The outcome of the last session save or open.
+    session_status: Option<Result<String, String>>,
+    /// The file last opened or saved, which the window is titled after.
+    file: Option<PathBuf>,
+    /// The seed and probability the error was last randomized with, for saving as a manifest.
+    randomized: Option<(u64, f64)>,
+    /// How many bits the last randomization flipped, against how many it was expected to.
+    flips: Option<FlipCount>,
+    /// The error from the last randomization, until its decoding is in and recorded as a trial.
+    trial: Option<Vec<u8>>,
+    /// The model the second row of error controls randomizes with.
+    model: channel::Model,
+    bursts: Bursts,
+    gilbert_elliott: GilbertElliott,
+    adversary: Adversary,
+    /// What the last error from a model other than the uniform one was, and the error it left.
+    model_error: Option<(String, Vec<u8>)>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        let (code_info, code) = codes::pipeline_code(codes::EH16_11.name).unwrap();
+        Self {
+            demo: HammingDemoWidget::new(),
+            prob_str: String::new(),
+            seed_str: String::new(),
+            share: Share::new(),
+            published: None,
+            leaderboard: Leaderboard::load(),
+            exercises: Engine::new(),
+            settings: Settings::load(),
+            settings_window: SettingsWindow::new(),
+            overview: Overview::new(),
+            scratchpad: Scratchpad::new(),
+            checker: Checker::new(),
+            sensitivity: Sensitivity::new(),
+            checksums: Checksums::new(),
+            standard_array: StandardArrayView::new(),
+            file_mode: FileMode::new(),
+            tolerance: Tolerance::new(),
+            ensemble: Ensemble::new(),
+            history: History::new(),
+            script: DemoScript::new(),
+            #[cfg(feature = "simulation")]
+            simulate: Simulate::new(),
+            audio: AudioDemo::new(),
+            image_demo: ImageDemo::new(),
+            coding_gain: CodingGain::new(),
+            #[cfg(feature = "explorers")]
+            gf_explorer: GfExplorer::new(),
+            corpus: CorpusWindow::new(code, code_info),
+            ingest: Ingest::new(),
+            search: Search::new(),
+            #[cfg(feature = "explorers")]
+            bch_explorer: BchExplorer::new(),
+            focus: None,
+            scroll_to_focus: false,
+            spotlight: None,
+            block: None,
+            scroll_to_block: false,
+            bookmarks: Bookmarks::new(),
+            #[cfg(feature = "simulation")]
+            soak: Soak::new(),
+            adaptive: Adaptive::new(),
+            help: Help::new(),
+            welcome: Welcome::load(),
+            recent: Recent::load(),
+            watch: Watch::new(),
+            notes: Notes::default(),
+            notes_window: NotesWindow::new(),
+            session_status: None,
+            file: None,
+            randomized: None,
+            flips: None,
+            trial: None,
+            model: channel::Model::Bursts,
+            bursts: Bursts::new(),
+            gilbert_elliott: GilbertElliott::new(),
+            adversary: Adversary::new(),
+            model_error: None,
+        }
+    }
+
+    fn session(&self) -> Session {
+        Session {
+            code: self.demo.code_info.name.to_string(),
+            message_in: self.demo.message_in.clone(),
+            format: self.demo.input_format,
+            error: self.demo.error.clone(),
+            prob_str: self.prob_str.clone(),
+            seed_str: self.seed_str.clone(),
+            interleave_depth: self.demo.interleave_depth,
+            notes: self.notes.clone(),
+            randomized: self.randomized_as_is(),
+        }
+    }
+
+    /// Whether `session` still describes the app, checked without building a new one.
+    fn is_current(&self, session: &Session, randomized: Option<(u64, f64)>) -> bool {
+        randomized == self.randomized
+            && session.code == self.demo.code_info.name
+            && session.message_in == self.demo.message_in
+            && session.format == self.demo.input_format
+            && session.error == self.demo.error
+            && session.prob_str == self.prob_str
+            && session.seed_str == self.seed_str
+            && session.interleave_depth == self.demo.interleave_depth
+            && session.notes == self.notes
+    }
+
+    fn apply_session(&mut self, session: Session) {
+        if let Some(pipeline) = codes::pipeline_code(&session.code) {
+            self.set_code(pipeline);
+        }
+        self.demo.message_in = session.message_in;
+        self.demo.input_format = session.format;
+        self.demo.error = session.error;
+        self.prob_str = session.prob_str;
+        self.seed_str = session.seed_str;
+        self.demo.interleave_depth = session.interleave_depth.max(1);
+        self.notes = session.notes;
+        self.randomized = session.randomized;
+        self.flips = session
+            .randomized
+            .map(|(_, prob)| FlipCount::new(&self.demo.error, prob));
+    }
+
+    /// The app as it is now, as a scene of a demo script. An error straight from Randomize Error
+    /// is kept as its seed.
+    fn scene(&self, name: String) -> Scene {
+        let error = match self.randomized_as_is() {
+            Some((seed, probability)) => SceneError::Seeded { seed, probability },
+            None => SceneError::Pattern(self.demo.error.clone()),
+        };
+        Scene {
+            name,
+            code: self.demo.code_info.name.to_string(),
+            message_in: self.demo.message_in.clone(),
+            format: self.demo.input_format,
+            error,
+            // Focus mode's panel, if it is on.
+            panel: self.spotlight,
+            dim_others: self.spotlight.is_some(),
+            note: String::new(),
+            presenter_notes: String::new(),
+        }
+    }
+
+    /// Puts the app in `scene`, and draws attention to its panel.
+    fn apply_scene(&mut self, scene: Scene) -> Result<(), String> {
+        let pipeline = codes::pipeline_code(&scene.code)
+            .ok_or_else(|| format!("The scene is for {}, an unknown code.", scene.code))?;
+        let message = scene.format.parse(&scene.message_in)?;
+        self.set_code(pipeline);
+        self.demo.message_in = scene.message_in;
+        self.demo.input_format = scene.format;
+        match scene.error {
+            SceneError::Pattern(error) => {
+                self.demo.error = error;
+                self.randomized = None;
+                self.flips = None;
+            }
+            SceneError::Seeded { seed, probability } => {
+                let len = encode(&message, self.demo.code)
+                    .map_err(|e| format!("{e:?}"))?
+                    .len();
+                self.demo.error.resize(len, 0);
+                self.randomize_error(probability, Some(seed));
+                self.prob_str = self.settings.decimal_separator.format_exact(probability);
+            }
+        }
+        // Going to a scene isn't a trial of the code.
+        self.trial = None;
+        if let Some(id) = scene.panel {
+            self.focus = Some((id, Instant::now()));
+            self.scroll_to_focus = true;
+        }
+        self.spotlight = scene.panel.filter(|_| scene.dim_others);
+        Ok(())
+    }
+
+    /// Switches the pipeline to another code, encoding the message afresh. The error is cleared,
+    /// since its bits no longer line up with the same codewords.
+    fn set_code(&mut self, pipeline: &'static (CodeInfo, Code)) {
+        if !self.demo.set_code(pipeline) {
+            return;
+        }
+        self.randomized = None;
+        self.flips = None;
+        self.trial = None;
+        self.block = None;
+    }
+
+    /// Flips each bit of the encoded stream with chance `prob`, drawing from `seed` if given, so
+    /// that the same seed and probability always flip the same bits.
+    fn randomize_error(&mut self, prob: f64, seed: Option<u64>) {
+        // Seeded either way, so that the pattern can be saved as a manifest and replayed.
+        let seed = seed.unwrap_or_else(|| manifest::new_seed(&mut thread_rng()));
+        self.demo.error = manifest::error_pattern(seed, self.demo.error.len(), prob);
+        self.randomized = Some((seed, prob));
+        self.flips = Some(FlipCount::new(&self.demo.error, prob));
+        self.trial = Some(self.demo.error.clone());
+    }
+
+    /// Replaces the error with one from the chosen model, drawing from `seed` if given.
+    fn randomize_model(&mut self, seed: Option<u64>) {
+        let seed = seed.unwrap_or_else(|| manifest::new_seed(&mut thread_rng()));
+        let len = self.demo.error.len();
+        let (error, what) = match self.model {
+            channel::Model::Bursts => {
+                let (error, bursts) = self.bursts.pattern(seed, len);
+                (error, format!("{bursts} bursts"))
+            }
+            channel::Model::GilbertElliott => {
+                let (error, bad) = self.gilbert_elliott.pattern(seed, len);
+                (
+                    error,
+                    format!("{bad} of {} bits sent in the bad state", len * 8),
+                )
+            }
+            channel::Model::Adversary => {
+                let info = &self.demo.code_info;
+                let (error, damage) = self.adversary.pattern(info, len);
+                // The adversary knows the interleaver and aims at the codewords themselves, so
+                // interleaving is no defence against it.
+                let interleaver = Interleaver::new(info, len, self.demo.interleave_depth);
+                (
+                    interleaver.interleave(&error),
+                    format!("Aimed to get {damage} wrong data bits past the decoder unnoticed"),
+                )
+            }
+        };
+        // The models give the error in the order the channel sends the bits, which the
+        // interleaver takes back to codeword order.
+        let interleaver = Interleaver::new(&self.demo.code_info, len, self.demo.interleave_depth);
+        self.demo.error = interleaver.deinterleave(&error);
+        self.randomized = None;
+        self.flips = None;
+        self.trial = None;
+        self.model_error = Some((what, self.demo.error.clone()));
+    }
+
+    /// How many codewords the last error from a model defeated, while the error is still as it
+    /// left it.
+    fn model_summary(&self, ui: &mut egui::Ui) {
+        let what = match &self.model_error {
+            Some((what, error)) if *error == self.demo.error => what,
+            _ => return,
+        };
+        let counts = analysis::error_counts(&self.demo.code_info, &self.demo.error);
+        let flips: usize = counts.iter().sum();
+        let hit = counts.iter().filter(|&&c| c > 0).count();
+        let defeated = counts.iter().filter(|&&c| c > 1).count();
+        ui.label(format!(
+            "{what}. {flips} flips hit {hit} codewords, {defeated} of them with more than one \
+             flip, which the code can't correct."
+        ));
+    }
+
+    /// Replaces the error with the lightest pattern that defeats the code, in the current
+    /// codeword or the first, and brings that codeword into view.
+    fn demonstrate_failure(&mut self) {
+        let info = &self.demo.code_info;
+        let j = self
+            .block
+            .filter(|&j| j < analysis::codeword_count(info, &self.demo.error))
+            .unwrap_or(0);
+        self.demo.error.iter_mut().for_each(|b| *b = 0);
+        for (i, &flip) in analysis::lightest_codeword(info).iter().enumerate() {
+            if flip {
+                analysis::set_bit(&mut self.demo.error, j * info.n + i, true);
+            }
+        }
+        self.randomized = None;
+        self.flips = None;
+        self.trial = None;
+        self.block = Some(j);
+        self.scroll_to_block = true;
+    }
+
+    /// Replaces the error with a single flip at random in every codeword: the most errors the
+    /// code can always correct.
+    fn demonstrate_correction(&mut self) {
+        let info = &self.demo.code_info;
+        let mut rng = thread_rng();
+        self.demo.error.iter_mut().for_each(|b| *b = 0);
+        for j in 0..analysis::codeword_count(info, &self.demo.error) {
+            let i = rng.gen_range(0..info.n);
+            analysis::set_bit(&mut self.demo.error, j * info.n + i, true);
+        }
+        self.randomized = None;
+        self.flips = None;
+        self.trial = None;
+    }
+
+    /// Sets the app up for the way in picked on the welcome screen.
+    fn start(&mut self, start: Start) {
+        match start {
+            Start::Demo => {
+                self.demo.message_in = EXAMPLES[0].text.to_string();
+                self.demo.input_format = InputFormat::Text;
+                self.prob_str = self.settings.decimal_separator.format(DEMO_PROBABILITY, 2);
+                let len =
+                    encode(self.demo.message_in.as_bytes(), self.demo.code).map_or(0, |e| e.len());
+                self.demo.error.resize(len, 0);
+                self.randomize_error(DEMO_PROBABILITY, None);
+            }
+            Start::DecodeFile => {
+                if let Some(path) = export::open_path("Encoded stream", &["bin"]) {
+                    self.open(&path);
+                }
+            }
+            #[cfg(feature = "simulation")]
+            Start::Simulation => self.soak.open = true,
+            #[cfg(not(feature = "simulation"))]
+            Start::Simulation => {}
+            Start::Tutorial => self.help.getting_started = true,
+        }
+    }
+
+    /// The seed as entered: `Some(None)` for none, and `None` if it isn't a whole number.
+    fn seed(&self) -> Option<Option<u64>> {
+        let seed = self.seed_str.trim();
+        if seed.is_empty() {
+            Some(None)
+        } else {
+            manifest::parse_seed(seed).map(Some)
+        }
+    }
+
+    /// Expected against actual flips for the last randomization, while the error is still as it
+    /// left it, so that a small sample that strays from the expectation isn't taken for a broken
+    /// randomizer. The seed it drew from can be kept for the next.
+    fn flip_count(&mut self, ui: &mut egui::Ui) {
+        let flips = match self.flips {
+            Some(f) => f,
+            None => return,
+        };
+        let observed: usize = self
+            .demo
+            .error
+            .iter()
+            .map(|b| b.count_ones() as usize)
+            .sum();
+        if observed != flips.observed {
+            return;
+        }
+        let separator = self.settings.decimal_separator;
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Expected {} flips, got {} (percentile {})",
+                separator.format(flips.expected, 1),
+                flips.observed,
+                separator.format(flips.percentile, 0)
+            ))
+            .on_hover_text(
+                "Where this count falls among the counts the same probability gives: around 50 \
+                 is typical, and even 5 or 95 turns up one time in ten.",
+            );
+            if let Some((seed, _)) = self.randomized {
+                if ui
+                    .small_button(format!("Seed {seed}"))
+                    .on_hover_text("Randomize with this seed from now on.")
+                    .clicked()
+                {
+                    self.seed_str = seed.to_string();
+                }
+            }
+        });
+    }
+
+    /// Loads a session, a session bundle, a manifest, or an encoded stream such as one written
+    /// by chunked encoding, going by the file extension.
+    pub fn open(&mut self, path: &Path) {
+        let opened = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => session::read(path).map(|s| self.apply_session(s)),
+            #[cfg(feature = "bundle")]
+            Some("zip") => bundle::read_session(path).map(|s| self.apply_session(s)),
+            Some("toml") => self.replay(path),
+            _ => self.open_encoded(path),
+        };
+        if opened.is_ok() {
+            self.welcome.open = false;
+            self.recent.add(path);
+            self.file = Some(path.to_path_buf());
+        }
+        self.session_status = Some(opened.map(|()| format!("Opened {}", path.display())));
+    }
+
+    /// Starts in the state asked for on the command line: the file opened first, then the code,
+    /// message, and error set on top of it.
+    pub fn launch(&mut self, launch: Launch) {
+        if let Some(path) = &launch.file {
+            self.open(path);
+        }
+        if launch.sets_up() {
+            self.welcome.open = false;
+        }
+        if let Some(pipeline) = launch.code {
+            self.set_code(pipeline);
+        }
+        if let Some(message) = &launch.message {
+            self.demo.set_text(message);
+        }
+        if let Some(seed) = launch.seed {
+            self.seed_str = seed.to_string();
+        }
+        if let Some(probability) = launch.probability {
+            self.prob_str = self.settings.decimal_separator.format_exact(probability);
+            // The error is randomized before the pipeline has encoded the message, so it is
+            // sized here.
+            let encoded = self
+                .demo
+                .input_format
+                .parse(&self.demo.message_in)
+                .ok()
+                .and_then(|m| encode(&m, self.demo.code).ok());
+            if let Some(encoded) = encoded {
+                self.demo.error.resize(encoded.len(), 0);
+                self.randomize_error(probability, launch.seed);
+            }
+        }
+    }
+
+    /// Records what the window backend draws with, for the About window.
+    pub fn set_graphics(&mut self, graphics: Graphics) {
+        self.help.graphics = Some(graphics);
+    }
+
+    pub fn window_title(&self) -> String {
+        platform::title(self.file.as_deref(), self.ingest.progress())
+    }
+
+    /// Loads the input of the manifest at `path` and flips the bits it determines.
+    fn replay(&mut self, path: &Path) -> Result<(), String> {
+        let manifest = Manifest::read(path)?;
+        let pipeline = codes::pipeline_code(&manifest.code)
+            .ok_or_else(|| format!("The manifest is for {}, an unknown code.", manifest.code))?;
+        let input = manifest.read_input(path)?;
+        self.set_code(pipeline);
+        let encoded = encode(&input, self.demo.code).map_err(|e| format!("{e:?}"))?;
+        self.demo.error = manifest.error_pattern(encoded.len());
+        self.set_message(input);
+        self.prob_str = self
+            .settings
+            .decimal_separator
+            .format_exact(manifest.probability);
+        self.randomized = Some((manifest.seed, manifest.probability));
+        self.flips = Some(FlipCount::new(&self.demo.error, manifest.probability));
+        Ok(())
+    }
+
+    /// The seed and probability the error was last randomized with, unless it has been changed
+    /// since.
+    fn randomized_as_is(&self) -> Option<(u64, f64)> {
+        self.randomized.filter(|&(seed, prob)| {
+            manifest::error_pattern(seed, self.demo.error.len(), prob) == self.demo.error
+        })
+    }
+
+    /// Writes a manifest for the current run, with the message beside it as its input.
+    fn save_manifest(&self, path: &Path) -> Result<(), String> {
+        let (seed, probability) = self.randomized_as_is().ok_or_else(|| {
+            "Only an error straight from Randomize Error can be saved as a manifest.".to_string()
+        })?;
+        let input = path.with_extension("txt");
+        fs::write(&input, &self.demo.message).map_err(|e| e.to_string())?;
+        Manifest {
+            code: self.demo.code_info.name.to_string(),
+            seed,
+            probability,
+            input: PathBuf::from(input.file_name().unwrap_or_default()),
+            input_hash: manifest::hash(&self.demo.message),
+        }
+        .write(path)
+    }
+
+    /// Decodes a received stream into the message, and keeps the bits the decoder corrected as
+    /// the error, so the stream shows as it was received.
+    fn open_encoded(&mut self, path: &Path) -> Result<(), String> {
+        let received = fs::read(path).map_err(|e| e.to_string())?;
+        let decoded = decode(&received, self.demo.code).map_err(|e| format!("{e:?}"))?;
+        let encoded = encode(&decoded, self.demo.code).map_err(|e| format!("{e:?}"))?;
+        self.demo.error = Iterator::zip(encoded.iter(), received.iter())
+            .map(|(e, r)| e ^ r)
+            .collect();
+        self.set_message(decoded);
+        Ok(())
+    }
+
+    /// Makes `message` the message, as text if it is UTF-8 and in hex if not.
+    fn set_message(&mut self, message: Vec<u8>) {
+        (self.demo.message_in, self.demo.input_format) = match String::from_utf8(message) {
+            Ok(text) => (text, InputFormat::Text),
+            Err(e) => (
+                InputFormat::Hex.write(e.as_bytes()).unwrap_or_default(),
+                InputFormat::Hex,
+            ),
+        };
+    }
+
+    fn stage_summaries(&mut self) -> Vec<StageSummary> {
+        let (panels, stages, _) = self.split_panels();
+        panels.iter().filter_map(|p| p.summary(&stages)).collect()
+    }
+
+    /// Splits the app into its panels, the stages they show, and the search that highlights
+    /// them, so that all three can be borrowed at once.
+    fn split_panels(&mut self) -> (&mut Vec<Box<dyn PanelModel>>, Stages<'_>, &mut Search) {
+        let App {
+            demo,
+            notes,
+            search,
+            settings,
+            ..
+        } = self;
+        let (panels, stages) = demo.split_panels(notes, settings.byte_display);
+        (panels, stages, search)
+    }
+
+    /// Runs the message through the encode, error, and decode stages.
+    pub fn update(&mut self) {
+        if let Some(session) = self.share.poll() {
+            self.apply_session(session);
+        }
+        #[cfg(feature = "network")]
+        self.share.apply_toggles(&mut self.demo.error);
+        self.demo.update();
+        self.record_trial();
+    }
+
+    /// Records the last randomization as a trial once it has been decoded, going by the chosen
+    /// decoder.
+    fn record_trial(&mut self) {
+        let decoded = self.trial.is_some()
+            && self.demo.decoded_with == self.trial
+            && self.demo.encoded_from.as_ref() == Some(&self.demo.message);
+        if !decoded {
+            return;
+        }
+        if let Some((_, probability)) = self.randomized {
+            let success = self
+                .demo
+                .outcomes
+                .iter()
+                .find(|o| o.algorithm == self.demo.algorithm)
+                .and_then(|o| o.decoded.as_deref())
+                == Some(self.demo.message.as_slice());
+            self.history.record(
+                &self.demo.code_info,
+                &self.demo.message,
+                &self.demo.encoded,
+                probability,
+                success,
+            );
+        }
+        self.trial = None;
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context) {
+        self.settings.apply(ctx);
+        self.spotlight_keys(ctx);
+        let read_only = self.share.is_following();
+        if !read_only {
+            self.ingest.intercept(ctx);
+        }
+
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| self.menu_bar(ui, read_only));
+        });
+        egui::TopBottomPanel::top("set_error").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let mut selected = None;
+                ui.add_enabled_ui(!read_only, |ui| {
+                    egui::ComboBox::from_label("Code")
+                        .selected_text(self.demo.code_info.name)
+                        .show_ui(ui, |ui| {
+                            for pipeline in &codes::PIPELINE {
+                                let name = pipeline.0.name;
+                                let current = self.demo.code_info == pipeline.0;
+                                if ui.selectable_label(current, name).clicked() {
+                                    selected = Some(pipeline);
+                                }
+                            }
+                        });
+                });
+                if let Some(pipeline) = selected {
+                    self.set_code(pipeline);
+                }
+                let info = &self.demo.code_info;
+                ui.label(format!(
+                    "n = {}, k = {}, rate {}",
+                    info.n,
+                    info.k,
+                    self.settings.decimal_separator.format(info.rate(), 2)
+                ));
+                if let Some(pending) = self.demo.pipeline.pending_for() {
+                    // Nothing else repaints when the results come in.
+                    ctx.request_repaint();
+                    if pending > SPINNER_DELAY {
+                        ui.spinner();
+                        ui.label("Working...");
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Error Probability:");
+                ui.add(TextEdit::singleline(&mut self.prob_str).interactive(!read_only));
+                let prob = self
+                    .settings
+                    .decimal_separator
+                    .parse(&self.prob_str)
+                    .filter(|prob| (0.0..=1.0).contains(prob));
+                ui.add(
+                    TextEdit::singleline(&mut self.seed_str)
+                        .hint_text("Seed (optional)")
+                        .desired_width(140.0)
+                        .interactive(!read_only),
+                )
+                .on_hover_text(format!(
+                    "A whole number from 0 to {}, the most a saved manifest can hold.",
+                    i64::MAX
+                ));
+                let seed = self.seed();
+                let enabled = !read_only && prob.is_some() && seed.is_some();
+                if ui
+                    .add_enabled(enabled, egui::Button::new("Randomize Error"))
+                    .clicked()
+                {
+                    self.randomize_error(prob.unwrap(), seed.unwrap());
+                }
+                let d = self.demo.code_info.min_distance();
+                let blocks = analysis::codeword_count(&self.demo.code_info, &self.demo.encoded);
+                if ui
+                    .add_enabled(
+                        !read_only && blocks > 0,
+                        egui::Button::new("Demonstrate Failure"),
+                    )
+                    .on_hover_text(format!(
+                        "Flips {d} bits of the current codeword, or the first: the fewest that \
+                         turn it into another codeword, so that it decodes to the wrong data."
+                    ))
+                    .clicked()
+                {
+                    self.demonstrate_failure();
+                }
+                if ui
+                    .add_enabled(
+                        !read_only && blocks > 0,
+                        egui::Button::new("Demonstrate Correction"),
+                    )
+                    .on_hover_text(
+                        "Flips one bit at random in every codeword, so that every codeword is \
+                         corrected.",
+                    )
+                    .clicked()
+                {
+                    self.demonstrate_correction();
+                }
+                let mut locked = self.demo.error_lock.is_some();
+                if ui
+                    .add_enabled(!read_only, egui::Checkbox::new(&mut locked, "Lock error"))
+                    .on_hover_text(
+                        "Keeps the error as the message changes, cut short or padded with zeros \
+                         to fit, so that the same error can be tried on different messages.",
+                    )
+                    .changed()
+                {
+                    self.demo.error_lock = locked.then(|| self.demo.error.clone());
+                }
+                if prob.is_none() && !self.prob_str.trim().is_empty() {
+                    ui.colored_label(Color32::RED, "Enter a probability between 0 and 1.");
+                } else if seed.is_none() {
+                    ui.colored_label(Color32::RED, "Enter a seed as a whole number, or none.");
+                } else if prob == Some(0.0) {
+                    ui.label("Clears every error.");
+                } else if prob == Some(1.0) {
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        "Flips every bit; no code can correct that.",
+                    );
+                }
+            });
+            self.flip_count(ui);
+            ui.horizontal(|ui| {
+                let seed = self.seed();
+                ui.add_enabled_ui(!read_only, |ui| {
+                    egui::ComboBox::from_id_source("error_model")
+                        .selected_text(self.model.name())
+                        .show_ui(ui, |ui| {
+                            for model in channel::Model::ALL {
+                                ui.selectable_value(&mut self.model, model, model.name());
+                            }
+                        });
+                });
+                let enabled = !read_only && seed.is_some();
+                let clicked = match self.model {
+                    channel::Model::Bursts => self.bursts.ui(ui, enabled),
+                    channel::Model::GilbertElliott => self.gilbert_elliott.ui(ui, enabled),
+                    channel::Model::Adversary => self.adversary.ui(ui, enabled),
+                };
+                if clicked {
+                    self.randomize_model(seed.flatten());
+                }
+            });
+            self.model_summary(ui);
+            self.share.ui(ui);
+        });
+
+        egui::TopBottomPanel::top("error_navigation").show(ctx, |ui| self.error_navigation(ui));
+        if !self.settings.hide_statistics {
+            egui::TopBottomPanel::bottom("statistics").show(ctx, |ui| self.statistics(ui));
+        }
+
+        let (panels, stages, search) = self.split_panels();
+        search.show(ctx, |id| {
+            panels
+                .iter()
+                .find(|p| p.id() == id)
+                .map_or_else(Vec::new, |p| p.output(&stages))
+        });
+
+        let blocks = analysis::codeword_count(&self.demo.code_info, &self.demo.encoded);
+        if let Some(block) = self.bookmarks.show(ctx, blocks) {
+            self.block = Some(block);
+            self.scroll_to_block = true;
+        }
+
+        self.notes_window.show(
+            ctx,
+            &mut self.notes,
+            blocks,
+            self.demo.encoded.len() * 8,
+            read_only,
+        );
+
+        #[cfg(feature = "network")]
+        self.share.show_collab(ctx, &mut self.demo.error);
+        let mut bench = Bench {
+            code: self.demo.code,
+            message_in: &mut self.demo.message_in,
+            format: &mut self.demo.input_format,
+            error: &mut self.demo.error,
+        };
+        self.exercises.show(ctx, &mut bench, &mut self.leaderboard);
+        self.leaderboard.show(ctx);
+        self.settings_window.show(ctx, &mut self.settings);
+        self.scratchpad.show(ctx);
+        self.checker
+            .show(ctx, &self.demo.code_info, &self.demo.with_error);
+        self.sensitivity
+            .show(ctx, &self.demo.code_info, &self.demo.error);
+        self.standard_array.show(ctx);
+        self.coding_gain.show(ctx, &self.demo.code_info);
+        // Checked against the message the stream was encoded from, which can trail the one
+        // being typed while the pipeline catches up.
+        self.tolerance.show(
+            ctx,
+            self.demo.code,
+            &self.demo.code_info,
+            self.demo.encoded_from.as_deref().unwrap_or_default(),
+            &self.demo.encoded,
+        );
+        self.history.show(ctx);
+        match self.script.show(ctx) {
+            Some(script::Request::Go(scene)) => {
+                if let Err(e) = self.apply_scene(scene) {
+                    self.session_status = Some(Err(e));
+                }
+            }
+            Some(script::Request::Capture) => {
+                let name = format!("Scene {}", self.script.scene_count() + 1);
+                let scene = self.scene(name);
+                self.script.add(scene);
+            }
+            None => {}
+        }
+        self.ensemble.show(
+            ctx,
+            self.demo.code,
+            &self.demo.code_info,
+            self.demo.encoded_from.as_deref().unwrap_or_default(),
+            &self.demo.encoded,
+        );
+        self.checksums
+            .show(ctx, &self.demo.code_info, &self.demo.message);
+        #[cfg(feature = "explorers")]
+        {
+            self.gf_explorer.show(ctx);
+            self.bch_explorer.show(ctx);
+        }
+        self.corpus.show(ctx, self.demo.code, &self.demo.code_info);
+        self.watch.show(ctx, self.demo.code);
+        #[cfg(feature = "simulation")]
+        {
+            self.soak.show(ctx, self.demo.code, &self.demo.code_info);
+            self.simulate
+                .show(ctx, self.demo.code, &self.demo.code_info);
+        }
+        self.adaptive.show(ctx);
+        self.audio.show(ctx, self.demo.code, &self.demo.code_info);
+        self.image_demo.show(
+            ctx,
+            self.demo.code,
+            &self.demo.code_info,
+            &self.settings.animation,
+        );
+        self.file_mode
+            .show(ctx, self.demo.code, &self.demo.code_info);
+        self.help.show(ctx);
+        if let Some(start) = self.welcome.show(ctx) {
+            self.start(start);
+        }
+        let keep = MAX_MESSAGE_BYTES.saturating_sub(self.demo.message_in.len());
+        if let Some(text) = self
+            .ingest
+            .show(ctx, self.demo.code, &self.demo.code_info, keep)
+        {
+            if self.demo.input_format.is_text() {
+                self.demo.message_in.push_str(&text);
+            } else {
+                let mut message = self.demo.message.clone();
+                message.extend_from_slice(text.as_bytes());
+                self.demo.message_in = self.demo.input_format.write(&message).unwrap_or_default();
+            }
+        }
+
+        if self.overview.open {
+            let stages = self.stage_summaries();
+            if let Some(id) = self.overview.show(ctx, &stages) {
+                self.focus = Some((id, Instant::now()));
+                self.scroll_to_focus = true;
+            }
+        }
+
+        let scroll_to = self
+            .block
+            .filter(|_| self.scroll_to_block)
+            .map(|j| analysis::codeword_row(&self.demo.code_info, j));
+        let scroll_to_focus = self.focus.map(|(f, _)| f).filter(|_| self.scroll_to_focus);
+        let arrangement = self.settings.arrangement;
+        let hidden = self.settings.hidden_panels.clone();
+        let (panels, mut stages, search) = self.split_panels();
+        let mut rects = Vec::new();
+        // The part of the window the panels can be seen in.
+        let mut visible = Rect::EVERYTHING;
+        let mut panel_ui = |ui: &mut egui::Ui, panel: &mut Box<dyn PanelModel>| {
+            let id = panel.id();
+            panels::header(ui, id, || panel.copy_text(&stages));
+            let view = View {
+                highlights: search.highlights(id),
+                scroll_to,
+                read_only,
+            };
+            panel.ui(ui, &mut stages, view);
+        };
+        match arrangement {
+            Arrangement::Columns => {
+                for panel in panels.iter_mut().filter(|p| !hidden.contains(&p.id())) {
+                    let id = panel.id();
+                    let response =
+                        egui::SidePanel::left(id.id()).show(ctx, |ui| panel_ui(ui, panel));
+                    rects.push((id, response.response.rect));
+                }
+            }
+            Arrangement::Pipeline => {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        visible = ui.clip_rect();
+                        let shown = panels.iter_mut().filter(|p| !hidden.contains(&p.id()));
+                        for (i, panel) in shown.enumerate() {
+                            if i > 0 {
+                                panels::arrow(ui);
+                            }
+                            let id = panel.id();
+                            let response = ui.group(|ui| panel_ui(ui, panel));
+                            if scroll_to_focus == Some(id) {
+                                response.response.scroll_to_me(Some(Align::Min));
+                            }
+                            rects.push((id, response.response.rect));
+                        }
+                    });
+                });
+            }
+        }
+        self.scroll_to_focus = false;
+        self.scroll_to_block = false;
+        self.highlight_focus(ctx, &rects);
+        self.dim_panels(ctx, &rects, visible);
+
+        if self.share.is_leading() {
+            let current = self.share.has_published()
+                && matches!(&self.published, Some((s, r)) if self.is_current(s, *r));
+            if !current {
+                let session = self.session();
+                self.share.publish(&session);
+                self.published = Some((session, self.randomized));
+            }
+        }
+    }
+
+    /// Previous and next buttons that scroll the byte panels between codewords with errors, and
+    /// a button to bookmark the codeword navigated to.
+    fn error_navigation(&mut self, ui: &mut egui::Ui) {
+        let blocks: Vec<usize> = analysis::error_counts(&self.demo.code_info, &self.demo.error)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, flips)| flips > 0)
+            .map(|(j, _)| j)
+            .collect();
+        ui.horizontal(|ui| {
+            let enabled = !blocks.is_empty();
+            let mut target = None;
+            if ui
+                .add_enabled(enabled, egui::Button::new("⏴ Previous error block"))
+                .clicked()
+            {
+                target = self
+                    .block
+                    .and_then(|current| blocks.iter().rev().find(|&&j| j < current))
+                    .or_else(|| blocks.last())
+                    .copied();
+            }
+            if ui
+                .add_enabled(enabled, egui::Button::new("Next error block ⏵"))
+                .clicked()
+            {
+                target = self
+                    .block
+                    .and_then(|current| blocks.iter().find(|&&j| j > current))
+                    .or_else(|| blocks.first())
+                    .copied();
+            }
+            if target.is_some() {
+                self.block = target;
+                self.scroll_to_block = true;
+            }
+            match self
+                .block
+                .and_then(|j| blocks.iter().position(|&b| b == j).map(|i| (j, i)))
+            {
+                Some((j, i)) => {
+                    let label = ui.label(format!("Codeword {j} ({} of {})", i + 1, blocks.len()));
+                    if let Some(note) = self.notes.blocks.get(&j).filter(|n| !n.is_empty()) {
+                        label.on_hover_text(note);
+                    }
+                }
+                None if blocks.is_empty() => {
+                    ui.label("No codewords have errors.");
+                }
+                None => {
+                    ui.label(format!("{} codewords have errors.", blocks.len()));
+                }
+            }
+            if let Some(j) = self.block {
+                if ui
+                    .add_enabled(
+                        !self.bookmarks.contains(j),
+                        egui::Button::new(format!("Bookmark codeword {j}")),
+                    )
+                    .clicked()
+                {
+                    self.bookmarks.add(j, String::new());
+                    self.bookmarks.open = true;
+                }
+            }
+        });
+    }
+
+    /// The File, View, Tools and Help menus, followed by how the last save or open went. While
+    /// `read_only`, as when following a shared session, whatever would replace the session is
+    /// disabled.
+    fn menu_bar(&mut self, ui: &mut egui::Ui, read_only: bool) {
+        ui.menu_button("File", |ui| self.file_menu(ui, read_only));
+        ui.menu_button("View", |ui| self.view_menu(ui));
+        ui.menu_button("Tools", |ui| self.tools_menu(ui));
+        ui.menu_button("Help", |ui| {
+            ui.checkbox(&mut self.welcome.open, "Welcome screen");
+            ui.checkbox(&mut self.help.getting_started, "Getting started");
+            ui.checkbox(&mut self.help.shortcuts, "Keyboard shortcuts");
+            ui.checkbox(&mut self.help.about, "About");
+        });
+        match &self.session_status {
+            Some(Ok(s)) => {
+                ui.label(s.as_str());
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e.as_str());
+            }
+            None => {}
+        }
+    }
+
+    fn file_menu(&mut self, ui: &mut egui::Ui, read_only: bool) {
+        if ui
+            .add_enabled(!read_only, egui::Button::new("Open..."))
+            .clicked()
+        {
+            ui.close_menu();
+            if let Some(path) = export::open_path("Session", OPEN_EXTENSIONS) {
+                self.open(&path);
+            }
+        }
+        let mut recent = None;
+        ui.menu_button("Recent", |ui| recent = self.recent.menu(ui, !read_only));
+        if let Some(path) = recent {
+            self.open(&path);
+        }
+        if ui.button("Save session...").clicked() {
+            ui.close_menu();
+            if let Some(path) = export::save_path("session.json", "Session", "json") {
+                let saved = session::write(&path, &self.session());
+                if saved.is_ok() {
+                    self.recent.add(&path);
+                    self.file = Some(path.clone());
+                }
+                self.session_status = Some(saved.map(|()| format!("Saved to {}", path.display())));
+            }
+        }
+        ui.separator();
+        if ui.button("Encode a file...").clicked() {
+            ui.close_menu();
+            self.file_mode.open = true;
+            self.file_mode
+                .open_file(self.demo.code, &self.demo.code_info);
+        }
+        ui.separator();
+        if ui.button("Save manifest...").clicked() {
+            ui.close_menu();
+            if let Some(path) = export::save_path("run.toml", "Manifest", "toml") {
+                self.session_status = Some(
+                    self.save_manifest(&path)
+                        .map(|()| format!("Saved manifest to {}", path.display())),
+                );
+            }
+        }
+        if ui
+            .add_enabled(!read_only, egui::Button::new("Replay manifest..."))
+            .clicked()
+        {
+            ui.close_menu();
+            if let Some(path) = export::open_path("Manifest", &["toml"]) {
+                self.open(&path);
+            }
+        }
+        if ui.button("Export codewords CSV...").clicked() {
+            ui.close_menu();
+            let csv = corrections::codewords_csv(
+                &self.demo.code_info,
+                &self.demo.error,
+                &self.demo.corrections,
+                self.randomized_as_is().map(|(_, p)| p),
+            );
+            match export::save_text("codewords.csv", "CSV", "csv", &csv) {
+                Ok(Some(path)) => {
+                    self.session_status = Some(Ok(format!("Saved {}", path.display())))
+                }
+                Ok(None) => {}
+                Err(e) => self.session_status = Some(Err(e)),
+            }
+        }
+        #[cfg(feature = "bundle")]
+        {
+            ui.separator();
+            if ui.button("Export bundle...").clicked() {
+                ui.close_menu();
+                if let Some(path) = export::save_path("session.zip", "Zip archive", "zip") {
+                    let session = self.session();
+                    let bundle = Bundle {
+                        session: &session,
+                        info: &self.demo.code_info,
+                        outcomes: &self.demo.outcomes,
+                    };
+                    self.session_status = Some(
+                        bundle
+                            .write(&path)
+                            .map(|()| format!("Bundled into {}", path.display())),
+                    );
+                }
+            }
+        }
+    }
+
+    fn view_menu(&mut self, ui: &mut egui::Ui) {
+        let before = self.settings.clone();
+        ui.menu_button("Panels", |ui| {
+            let hidden = &mut self.settings.hidden_panels;
+            for id in PanelId::ALL {
+                let mut shown = !hidden.contains(&id);
+                if ui.checkbox(&mut shown, id.title()).changed() {
+                    if shown {
+                        hidden.retain(|&h| h != id);
+                    } else {
+                        hidden.push(id);
+                    }
+                }
+            }
+        });
+        ui.menu_button("Layout", |ui| {
+            for a in Arrangement::ALL {
+                ui.radio_value(&mut self.settings.arrangement, a, a.name());
+            }
+        });
+        ui.menu_button("Theme", |ui| {
+            for t in Theme::ALL {
+                ui.radio_value(&mut self.settings.theme, t, t.name());
+            }
+        });
+        ui.menu_button("Bytes", |ui| {
+            for d in ByteDisplay::ALL {
+                ui.radio_value(&mut self.settings.byte_display, d, d.name());
+            }
+        });
+        let mut statistics = !self.settings.hide_statistics;
+        if ui.checkbox(&mut statistics, "Statistics bar").changed() {
+            self.settings.hide_statistics = !statistics;
+        }
+        if self.settings != before {
+            if let Err(e) = self.settings.save() {
+                self.session_status = Some(Err(e));
+            }
+        }
+        ui.menu_button("Focus mode", |ui| {
+            ui.radio_value(&mut self.spotlight, None, "Off");
+            for id in PanelId::ALL {
+                ui.radio_value(&mut self.spotlight, Some(id), id.title());
+            }
+        });
+        ui.separator();
+        ui.checkbox(&mut self.overview.open, "Overview");
+        ui.checkbox(&mut self.search.open, "Find");
+        ui.checkbox(&mut self.bookmarks.open, "Bookmarks");
+        let notes = ui.checkbox(&mut self.notes_window.open, "Notes");
+        if !self.notes.session.is_empty() {
+            notes.on_hover_text(&self.notes.session);
+        }
+        ui.separator();
+        ui.checkbox(&mut self.settings_window.open, "Settings");
+    }
+
+    fn tools_menu(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.exercises.open, "Exercises");
+        ui.checkbox(&mut self.leaderboard.open, "Leaderboard");
+        ui.separator();
+        ui.checkbox(&mut self.scratchpad.open, "Scratchpad");
+        ui.checkbox(&mut self.checker.open, "Checker");
+        ui.checkbox(&mut self.sensitivity.open, "Single-bit sensitivity");
+        ui.checkbox(&mut self.standard_array.open, "Standard array");
+        ui.checkbox(&mut self.tolerance.open, "Tolerable error probability");
+        ui.checkbox(&mut self.coding_gain.open, "Coding gain");
+        ui.checkbox(&mut self.ensemble.open, "Ensemble");
+        ui.checkbox(&mut self.checksums.open, "Detection vs correction");
+        ui.checkbox(&mut self.history.open, "Randomization history");
+        ui.checkbox(&mut self.script.open, "Demo script");
+        ui.checkbox(&mut self.script.notes_open, "Presenter notes");
+        #[cfg(feature = "explorers")]
+        {
+            ui.checkbox(&mut self.gf_explorer.open, "GF(2^m) explorer");
+            ui.checkbox(&mut self.bch_explorer.open, "BCH explorer");
+        }
+        ui.separator();
+        if self.corpus.enabled() {
+            ui.checkbox(&mut self.corpus.open, "Corpus");
+        }
+        ui.checkbox(&mut self.watch.open, "Watch folder");
+        #[cfg(feature = "simulation")]
+        {
+            ui.checkbox(&mut self.soak.open, "Soak test");
+            ui.checkbox(&mut self.simulate.open, "Simulate");
+        }
+        ui.checkbox(&mut self.adaptive.open, "Adaptive coding");
+        ui.checkbox(&mut self.audio.open, "Audio");
+        ui.checkbox(&mut self.image_demo.open, "Image");
+    }
+
+    /// Live counts over the whole stream: what the error flipped, what the decoder put right, and
+    /// what is still wrong.
+    fn statistics(&self, ui: &mut egui::Ui) {
+        let tallies = corrections::tally(
+            &self.demo.code_info,
+            &self.demo.error,
+            &self.demo.corrections,
+        );
+        let flipped: usize = tallies.iter().map(|t| t.flipped).sum();
+        let corrected: usize = tallies.iter().map(|t| t.corrected).sum();
+        let residual: usize = tallies.iter().map(|t| t.residual).sum();
+        let failed = tallies.iter().filter(|t| t.residual > 0).count();
+        let decoded = self
+            .demo
+            .outcomes
+            .iter()
+            .find(|o| o.algorithm == self.demo.algorithm)
+            .and_then(|o| o.decoded.as_deref());
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("{} bits sent", self.demo.encoded.len() * 8));
+            ui.separator();
+            ui.label(format!("{flipped} flipped"));
+            ui.separator();
+            ui.label(format!("{corrected} corrected"))
+                .on_hover_text("Flipped bits the decoder flipped back.");
+            ui.separator();
+            let residual_color = if residual > 0 {
+                Color32::RED
+            } else {
+                ui.visuals().text_color()
+            };
+            ui.colored_label(residual_color, format!("{residual} still wrong"))
+                .on_hover_text(
+                    "Bits of the stream wrong after decoding: flips the decoder missed, and bits \
+                     it flipped that were never in error.",
+                );
+            ui.separator();
+            ui.label(format!("{failed} of {} codewords failed", tallies.len()))
+                .on_hover_text("Codewords with any bit still wrong after decoding.");
+            ui.separator();
+            match decoded {
+                Some(decoded) => {
+                    let wrong: u32 = Iterator::zip(self.demo.message.iter(), decoded.iter())
+                        .map(|(a, b)| (a ^ b).count_ones())
+                        .sum();
+                    ui.label(format!("{wrong} message bits wrong"))
+                }
+                None => ui.label("Message undecodable"),
+            };
+        });
+    }
+
+    /// Focus mode: picks the panel to leave undimmed with the number keys, 1 for the first
+    /// panel and so on, or turns it off with 0.
+    fn spotlight_keys(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        const KEYS: [Key; 8] = [
+            Key::Num1,
+            Key::Num2,
+            Key::Num3,
+            Key::Num4,
+            Key::Num5,
+            Key::Num6,
+            Key::Num7,
+            Key::Num8,
+        ];
+        let input = ctx.input();
+        if input.key_pressed(Key::Num0) {
+            self.spotlight = None;
+        }
+        for (key, id) in KEYS.iter().zip(PanelId::ALL) {
+            if input.key_pressed(*key) {
+                self.spotlight = Some(id);
+            }
+        }
+    }
+
+    /// Dims every panel but the one in focus mode's spotlight, where it can be seen.
+    fn dim_panels(&self, ctx: &egui::Context, rects: &[(PanelId, Rect)], visible: Rect) {
+        let spotlight = match self.spotlight {
+            Some(id) => id,
+            None => return,
+        };
+        // Over the panels, but under any windows.
+        let painter = ctx.layer_painter(LayerId::new(Order::PanelResizeLine, Id::new("dim")));
+        for (_, rect) in rects.iter().filter(|(id, _)| *id != spotlight) {
+            painter.rect_filled(rect.intersect(visible), 0.0, Color32::from_black_alpha(160));
+        }
+    }
+
+    /// Outlines the panel that was just jumped to, fading out over time.
+    fn highlight_focus(&mut self, ctx: &egui::Context, rects: &[(PanelId, Rect)]) {
+        let (id, since) = match self.focus {
+            Some(focus) => focus,
+            None => return,
+        };
+        let animation = &self.settings.animation;
+        let elapsed = since.elapsed().as_secs_f32();
+        let end = if animation.reduce_motion {
+            FOCUS_TIME
+        } else {
+            animation.duration(FOCUS_TIME)
+        };
+        if elapsed >= end {
+            self.focus = None;
+            return;
+        }
+        // With reduced motion the outline stays solid rather than fading.
+        let strength = if animation.reduce_motion {
+            1.0
+        } else {
+            1.0 - animation.progress(elapsed, FOCUS_TIME)
+        };
+        if let Some((_, rect)) = rects.iter().find(|(i, _)| *i == id) {
+            ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("panel_focus")))
+                .rect_stroke(
+                    *rect,
+                    4.0,
+                    Stroke::new(3.0, Color32::YELLOW.linear_multiply(strength)),
+                );
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{
+    channel::Transmission,
+    codes::CodeInfo,
+    export,
+    unequal::UnequalProtection,
+    wav::{self, Wav},
+};
+use egui::{
+    plot::{Legend, Line, Plot, PlotPoints},
+    Color32, DragValue, Grid,
+};
+use hamming::Code;
+use std::fs;
+
+/// The longest clip taken from a WAV file, in seconds. Longer files are cut short, to keep
+/// encoding quick.
+const MAX_SECONDS: u32 = 10;
+
+/// The most points each waveform is plotted with.
+const PLOT_POINTS: usize = 2000;
+
+/// A WAV file as loaded.
+struct Clip {
+    name: String,
+    wav: Wav,
+    /// The length of the file in seconds, if it was cut short.
+    truncated_from: Option<f64>,
+}
+
+impl Clip {
+    fn seconds(&self) -> f64 {
+        self.wav.samples.len() as f64
+            / f64::from(self.wav.channels)
+            / f64::from(self.wav.sample_rate)
+    }
+}
+
+/// The clip after going through the channel, sent as it is and sent protected by a code.
+struct Results {
+    code: &'static str,
+    probability: f64,
+    unprotected: Vec<i16>,
+    protected: Vec<i16>,
+    /// Bits flipped by the channel in each.
+    unprotected_flips: usize,
+    protected_flips: usize,
+    /// Samples that came out different from the original in each.
+    unprotected_damaged: usize,
+    protected_damaged: usize,
+    unequal: Option<Unequal>,
+}
+
+/// The clip after going through the channel with unequal protection.
+struct Unequal {
+    name: String,
+    samples: Vec<i16>,
+    flips: usize,
+    damaged: usize,
+}
+
+/// The mean difference between each sample of `original` and `out`, out of 32768.
+fn mean_error(original: &[i16], out: &[i16]) -> f64 {
+    let total: u64 = Iterator::zip(original.iter(), out.iter())
+        .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+        .sum();
+    total as f64 / original.len().max(1) as f64
+}
+
+impl Results {
+    /// Sends `samples` through the channel twice, as they are and protected by `code`, and a
+    /// third time with `unequal` protection if it is enabled.
+    fn new(
+        code: &Code,
+        info: &CodeInfo,
+        samples: &[i16],
+        probability: f64,
+        unequal: &UnequalProtection,
+    ) -> Self {
+        let bytes = wav::to_bytes(samples);
+        let sent = Transmission::new(code, info, &bytes, probability);
+        let unprotected = wav::from_bytes(&sent.unprotected);
+        let protected = wav::from_bytes(&sent.protected);
+        let damaged = |out: &[i16]| {
+            Iterator::zip(samples.iter(), out.iter())
+                .filter(|(a, b)| a != b)
+                .count()
+        };
+        let unequal = unequal.enabled.then(|| {
+            let arrival = unequal.send(&bytes, 16, probability);
+            let samples = wav::from_bytes(&arrival.bytes);
+            Unequal {
+                name: unequal.name(),
+                damaged: damaged(&samples),
+                samples,
+                flips: arrival.flips,
+            }
+        });
+        Self {
+            code: info.name,
+            probability,
+            unprotected_damaged: damaged(&unprotected),
+            protected_damaged: damaged(&protected),
+            unprotected,
+            protected,
+            unprotected_flips: sent.unprotected_flips,
+            protected_flips: sent.protected_flips,
+            unequal,
+        }
+    }
+}
+
+/// Plays samples through the default output device until stopped or dropped.
+#[cfg(feature = "audio")]
+struct Player {
+    _stream: rodio::OutputStream,
+    sink: rodio::Sink,
+}
+
+#[cfg(feature = "audio")]
+impl Player {
+    fn play(channels: u16, sample_rate: u32, samples: Vec<i16>) -> Result<Self, String> {
+        let (stream, handle) = rodio::OutputStream::try_default().map_err(|e| e.to_string())?;
+        let sink = rodio::Sink::try_new(&handle).map_err(|e| e.to_string())?;
+        sink.append(rodio::buffer::SamplesBuffer::new(
+            channels,
+            sample_rate,
+            samples,
+        ));
+        Ok(Self {
+            _stream: stream,
+            sink,
+        })
+    }
+
+    fn playing(&self) -> bool {
+        !self.sink.empty()
+    }
+}
+
+/// Makes the effect of a code audible: a short WAV clip is corrupted with and without the code's
+/// protection, and both results can be played or saved.
+pub struct AudioDemo {
+    pub open: bool,
+    clip: Option<Clip>,
+    probability: f64,
+    results: Option<Results>,
+    /// The outcome of the last open, save or play.
+    status: Option<Result<String, String>>,
+    unequal: UnequalProtection,
+    #[cfg(feature = "audio")]
+    player: Option<Player>,
+}
+
+impl AudioDemo {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            clip: None,
+            probability: 0.001,
+            results: None,
+            status: None,
+            unequal: UnequalProtection::new(),
+            #[cfg(feature = "audio")]
+            player: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code, info: &CodeInfo) {
+        #[cfg(feature = "audio")]
+        if matches!(&self.player, Some(player) if player.playing()) {
+            // Nothing else repaints when it finishes.
+            ctx.request_repaint();
+        }
+        let mut open = self.open;
+        egui::Window::new("Audio")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, code, info));
+        self.open = open;
+        #[cfg(feature = "audio")]
+        if !self.open {
+            self.player = None;
+        }
+    }
+
+    fn open_clip(&mut self) {
+        let path = match export::open_path("WAV audio", &["wav"]) {
+            Some(path) => path,
+            None => return,
+        };
+        let opened = fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| wav::parse(&bytes));
+        self.status = match opened {
+            Ok(wav) => {
+                let max = (wav.sample_rate * MAX_SECONDS) as usize * usize::from(wav.channels);
+                let mut clip = Clip {
+                    name: path
+                        .file_name()
+                        .map_or_else(String::new, |n| n.to_string_lossy().into_owned()),
+                    wav,
+                    truncated_from: None,
+                };
+                if clip.wav.samples.len() > max {
+                    clip.truncated_from = Some(clip.seconds());
+                    clip.wav.samples.truncate(max);
+                }
+                self.clip = Some(clip);
+                self.results = None;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        };
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, code: &Code, info: &CodeInfo) {
+        ui.horizontal(|ui| {
+            if ui.button("Open WAV...").clicked() {
+                self.open_clip();
+            }
+            if let Some(clip) = &self.clip {
+                ui.label(format!(
+                    "{}: {:.1} s, {} Hz, {} channels",
+                    clip.name,
+                    clip.seconds(),
+                    clip.wav.sample_rate,
+                    clip.wav.channels
+                ));
+            }
+        });
+        if let Some(seconds) = self.clip.as_ref().and_then(|c| c.truncated_from) {
+            ui.small(format!(
+                "Only the first {MAX_SECONDS} s of {seconds:.1} s are used."
+            ));
+        }
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.0001)
+                    .max_decimals(5),
+            );
+            if ui
+                .add_enabled(
+                    self.clip.is_some(),
+                    egui::Button::new(format!("Corrupt with and without {}", info.name)),
+                )
+                .clicked()
+            {
+                if let Some(clip) = &self.clip {
+                    self.results = Some(Results::new(
+                        code,
+                        info,
+                        &clip.wav.samples,
+                        self.probability,
+                        &self.unequal,
+                    ));
+                }
+            }
+        });
+        self.unequal.ui(ui, "sample", 16);
+        ui.small(
+            "Each 16 bit sample is sent as two bytes, low byte first, so a flip can land \
+             anywhere from the quietest bit to the sign.",
+        );
+        match &self.status {
+            Some(Ok(s)) => {
+                ui.label(s.as_str());
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e.as_str());
+            }
+            None => {}
+        }
+        let (clip, results) = match (&self.clip, &self.results) {
+            (Some(clip), Some(results)) => (clip, results),
+            (None, _) => {
+                ui.label("Open a short WAV file to hear what the channel does to it.");
+                return;
+            }
+            (Some(_), None) => return,
+        };
+        ui.separator();
+
+        let total = clip.wav.samples.len();
+        #[cfg(feature = "audio")]
+        let mut play = None;
+        let mut save = None;
+        Grid::new("audio_results").striped(true).show(ui, |ui| {
+            ui.strong("");
+            ui.strong("Bits flipped");
+            ui.strong("Samples damaged");
+            ui.strong("Mean error");
+            ui.end_row();
+            let mut rows = vec![
+                ("Original", None, 0, clip.wav.samples.as_slice()),
+                (
+                    "Unprotected",
+                    Some(results.unprotected_flips),
+                    results.unprotected_damaged,
+                    results.unprotected.as_slice(),
+                ),
+                (
+                    results.code,
+                    Some(results.protected_flips),
+                    results.protected_damaged,
+                    results.protected.as_slice(),
+                ),
+            ];
+            if let Some(unequal) = &results.unequal {
+                rows.push((
+                    &unequal.name,
+                    Some(unequal.flips),
+                    unequal.damaged,
+                    &unequal.samples,
+                ));
+            }
+            for (name, flips, damaged, samples) in rows {
+                ui.label(name);
+                ui.label(flips.map_or_else(String::new, |f| f.to_string()));
+                ui.label(format!(
+                    "{damaged} of {total} ({:.2}%)",
+                    damaged as f64 / total.max(1) as f64 * 100.0
+                ));
+                ui.label(format!("{:.1}", mean_error(&clip.wav.samples, samples)));
+                #[cfg(feature = "audio")]
+                if ui.button("▶ Play").clicked() {
+                    play = Some(samples.to_vec());
+                }
+                if ui.button("Save WAV...").clicked() {
+                    save = Some((name, samples.to_vec()));
+                }
+                ui.end_row();
+            }
+        });
+        ui.small(format!(
+            "Error probability {} for both. Codewords of {} with more errors than it corrects \
+             come out as silence.",
+            results.probability, results.code
+        ));
+        if results.unequal.is_some() {
+            ui.small(
+                "With unequal protection the high bits of each sample, which make the loud \
+                 clicks, are sent through the strong code and the low bits weakly or not at \
+                 all: more samples come out a little off, but the result is quieter hiss \
+                 rather than clicks.",
+            );
+        }
+        #[cfg(feature = "audio")]
+        if matches!(&self.player, Some(player) if player.playing()) && ui.button("■ Stop").clicked()
+        {
+            self.player = None;
+        }
+        #[cfg(not(feature = "audio"))]
+        ui.small("Built without the audio feature: save the clips to play them.");
+
+        // The first channel, thinned out to at most `PLOT_POINTS` points.
+        let channels = usize::from(clip.wav.channels);
+        let step = (total / channels / PLOT_POINTS).max(1) * channels;
+        let rate = f64::from(clip.wav.sample_rate);
+        let waveform = |samples: &[i16]| {
+            let points: Vec<[f64; 2]> = samples
+                .iter()
+                .step_by(step)
+                .enumerate()
+                .map(|(i, &s)| [(i * step / channels) as f64 / rate, f64::from(s) / 32768.0])
+                .collect();
+            Line::new(PlotPoints::new(points))
+        };
+        Plot::new("audio_waveforms")
+            .height(200.0)
+            .legend(Legend::default())
+            .include_y(-1.0)
+            .include_y(1.0)
+            .show(ui, |plot| {
+                plot.line(waveform(&clip.wav.samples).name("Original"));
+                plot.line(waveform(&results.unprotected).name("Unprotected"));
+                plot.line(waveform(&results.protected).name(results.code));
+                if let Some(unequal) = &results.unequal {
+                    plot.line(waveform(&unequal.samples).name(&unequal.name));
+                }
+            });
+        ui.small("x: seconds, y: first channel");
+
+        let (channels, sample_rate) = (clip.wav.channels, clip.wav.sample_rate);
+        if let Some((name, samples)) = save {
+            let file_name = format!("{}.wav", name.to_lowercase());
+            let bytes = wav::write(&Wav {
+                sample_rate,
+                channels,
+                samples,
+            });
+            self.status = match export::save_bytes(&file_name, &bytes) {
+                Ok(Some(path)) => Some(Ok(format!("Saved {}", path.display()))),
+                Ok(None) => self.status.take(),
+                Err(e) => Some(Err(e)),
+            };
+        }
+        #[cfg(feature = "audio")]
+        if let Some(samples) = play {
+            match Player::play(channels, sample_rate, samples) {
+                Ok(player) => self.player = Some(player),
+                Err(e) => self.status = Some(Err(e)),
+            }
+        }
+    }
+}
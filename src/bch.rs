@@ -0,0 +1,297 @@
+use crate::gf::Field;
+
+/// The product of two polynomials over GF(2), with `p[i]` the coefficient of `x^i`.
+fn mul_bits(a: &[bool], b: &[bool]) -> Vec<bool> {
+    let mut p = vec![false; a.len() + b.len() - 1];
+    for (i, _) in a.iter().enumerate().filter(|(_, &x)| x) {
+        for (j, _) in b.iter().enumerate().filter(|(_, &y)| y) {
+            p[i + j] ^= true;
+        }
+    }
+    p
+}
+
+/// The remainder of `a` divided by `g`, which must have a leading coefficient of one.
+fn rem_bits(a: &[bool], g: &[bool]) -> Vec<bool> {
+    let deg = g.len() - 1;
+    let mut r = a.to_vec();
+    for i in (deg..r.len()).rev() {
+        if r[i] {
+            for (j, &c) in g.iter().enumerate() {
+                r[i - deg + j] ^= c;
+            }
+        }
+    }
+    r.truncate(deg);
+    r
+}
+
+/// A binary, narrow-sense, primitive BCH code of length `2^m - 1` with designed distance
+/// `2t + 1`. Codewords are systematic, with bit `i` the coefficient of `x^i`: the `n - k` parity
+/// bits come first and the data bits last.
+pub struct Bch {
+    field: Field,
+    t: usize,
+    generator: Vec<bool>,
+}
+
+/// What the decoder found in a received word.
+pub struct Decoding {
+    /// `S_1` to `S_2t`, the received word evaluated at `α^1` to `α^2t`.
+    pub syndromes: Vec<u32>,
+    /// The error locator polynomial `Λ(x)` from Berlekamp–Massey, lowest coefficient first.
+    pub locator: Vec<u32>,
+    /// The bits found to be in error.
+    pub errors: Vec<usize>,
+    /// The corrected codeword, or `None` if the errors were beyond the code's reach.
+    pub codeword: Option<Vec<bool>>,
+}
+
+impl Bch {
+    /// Returns `None` when `t` is so large that the code would carry no data.
+    pub fn new(m: u32, t: usize) -> Option<Self> {
+        let field = Field::new(m);
+        let n = field.order();
+        if t == 0 || 2 * t >= n {
+            return None;
+        }
+        // The generator is the product of the distinct minimal polynomials of α^1 to α^2t. Only
+        // odd powers need visiting, since α^2i shares the minimal polynomial of α^i.
+        let mut generator = vec![true];
+        let mut seen = vec![false; n];
+        for i in (1..=2 * t).step_by(2) {
+            if seen[i] {
+                continue;
+            }
+            let mut minimal = vec![1u32];
+            let mut j = i;
+            while !seen[j] {
+                seen[j] = true;
+                // Multiply by (x + α^j).
+                let root = field.alpha_pow(j as i64);
+                let mut next = vec![0; minimal.len() + 1];
+                for (d, &c) in minimal.iter().enumerate() {
+                    next[d + 1] ^= c;
+                    next[d] ^= field.mul(c, root);
+                }
+                minimal = next;
+                j = 2 * j % n;
+            }
+            let minimal: Vec<bool> = minimal.iter().map(|&c| c == 1).collect();
+            generator = mul_bits(&generator, &minimal);
+        }
+        (generator.len() - 1 < n).then_some(Self {
+            field,
+            t,
+            generator,
+        })
+    }
+
+    pub fn n(&self) -> usize {
+        self.field.order()
+    }
+
+    pub fn k(&self) -> usize {
+        self.n() - (self.generator.len() - 1)
+    }
+
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    pub fn field(&self) -> &Field {
+        &self.field
+    }
+
+    pub fn generator(&self) -> &[bool] {
+        &self.generator
+    }
+
+    /// Encodes `k` data bits into an `n`-bit codeword.
+    pub fn encode(&self, data: &[bool]) -> Vec<bool> {
+        assert_eq!(data.len(), self.k(), "BCH data must be k bits long");
+        let parity_bits = self.n() - self.k();
+        let mut shifted = vec![false; parity_bits];
+        shifted.extend_from_slice(data);
+        let mut codeword = rem_bits(&shifted, &self.generator);
+        codeword.extend_from_slice(data);
+        codeword
+    }
+
+    /// The data bits of a codeword.
+    pub fn data<'a>(&self, codeword: &'a [bool]) -> &'a [bool] {
+        &codeword[self.n() - self.k()..]
+    }
+
+    /// Decodes an `n`-bit word with Berlekamp–Massey and a Chien search.
+    pub fn decode(&self, received: &[bool]) -> Decoding {
+        assert_eq!(received.len(), self.n(), "BCH words must be n bits long");
+        let f = &self.field;
+        let syndromes: Vec<u32> = (1..=2 * self.t as i64)
+            .map(|j| {
+                received
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &b)| b)
+                    .fold(0, |s, (i, _)| f.add(s, f.alpha_pow(j * i as i64)))
+            })
+            .collect();
+
+        let locator = self.berlekamp_massey(&syndromes);
+        let degree = locator.iter().rposition(|&c| c != 0).unwrap_or(0);
+        // Λ has a root at α^-i for each error at bit i.
+        let errors: Vec<usize> = (0..self.n())
+            .filter(|&i| {
+                let x = f.alpha_pow(-(i as i64));
+                locator
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, &c| f.add(f.mul(acc, x), c))
+                    == 0
+            })
+            .collect();
+
+        let codeword = (degree <= self.t && errors.len() == degree).then(|| {
+            let mut corrected = received.to_vec();
+            for &i in &errors {
+                corrected[i] ^= true;
+            }
+            corrected
+        });
+        Decoding {
+            syndromes,
+            locator: locator[..=degree].to_vec(),
+            errors,
+            codeword,
+        }
+    }
+
+    /// The shortest linear feedback shift register generating `syndromes`, as its connection
+    /// polynomial.
+    fn berlekamp_massey(&self, syndromes: &[u32]) -> Vec<u32> {
+        let f = &self.field;
+        let mut c = vec![0; syndromes.len() + 1];
+        let mut b = c.clone();
+        c[0] = 1;
+        b[0] = 1;
+        let mut len = 0;
+        let mut shift = 1;
+        let mut last = 1;
+        for r in 0..syndromes.len() {
+            let discrepancy =
+                (1..=len).fold(syndromes[r], |d, i| f.add(d, f.mul(c[i], syndromes[r - i])));
+            if discrepancy == 0 {
+                shift += 1;
+                continue;
+            }
+            let scale = f.div(discrepancy, last);
+            let previous = c.clone();
+            for (i, ci) in c.iter_mut().enumerate().skip(shift) {
+                *ci = f.add(*ci, f.mul(scale, b[i - shift]));
+            }
+            if 2 * len <= r {
+                len = r + 1 - len;
+                b = previous;
+                last = discrepancy;
+                shift = 1;
+            } else {
+                shift += 1;
+            }
+        }
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+
+    /// `(m, t, k)` for a few codes, with `k` as tabulated for primitive BCH codes.
+    const CODES: [(u32, usize, usize); 6] = [
+        (4, 1, 11),
+        (4, 2, 7),
+        (4, 3, 5),
+        (5, 2, 21),
+        (5, 3, 16),
+        (6, 3, 45),
+    ];
+
+    /// A random codeword of `bch`, and its data.
+    fn codeword(bch: &Bch, rng: &mut StdRng) -> (Vec<bool>, Vec<bool>) {
+        let data: Vec<bool> = (0..bch.k()).map(|_| rng.gen()).collect();
+        (bch.encode(&data), data)
+    }
+
+    /// `word` with `count` distinct bits flipped, and which they were, in order.
+    fn flip(word: &[bool], count: usize, rng: &mut StdRng) -> (Vec<bool>, Vec<usize>) {
+        let mut flipped = word.to_vec();
+        let mut bits = index::sample(rng, word.len(), count).into_vec();
+        bits.sort_unstable();
+        for &i in &bits {
+            flipped[i] ^= true;
+        }
+        (flipped, bits)
+    }
+
+    #[test]
+    fn dimensions() {
+        for (m, t, k) in CODES {
+            let bch = Bch::new(m, t).unwrap();
+            assert_eq!((bch.n(), bch.k()), ((1 << m) - 1, k), "m = {m}, t = {t}");
+        }
+        assert!(Bch::new(4, 0).is_none());
+        assert!(Bch::new(4, 8).is_none());
+    }
+
+    #[test]
+    fn codewords_are_systematic_and_clean() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (m, t, _) in CODES {
+            let bch = Bch::new(m, t).unwrap();
+            let (word, data) = codeword(&bch, &mut rng);
+            assert_eq!(bch.data(&word), data);
+            let decoding = bch.decode(&word);
+            assert!(decoding.syndromes.iter().all(|&s| s == 0));
+            assert!(decoding.errors.is_empty());
+            assert_eq!(decoding.codeword, Some(word));
+        }
+    }
+
+    #[test]
+    fn corrects_up_to_t_errors() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for (m, t, _) in CODES {
+            let bch = Bch::new(m, t).unwrap();
+            for errors in 1..=t {
+                for _ in 0..50 {
+                    let (word, _) = codeword(&bch, &mut rng);
+                    let (received, bits) = flip(&word, errors, &mut rng);
+                    let decoding = bch.decode(&received);
+                    // Λ has one root for each error, and so degree `errors`.
+                    assert_eq!(decoding.locator.len(), errors + 1);
+                    assert_eq!(decoding.errors, bits, "m = {m}, t = {t}");
+                    assert_eq!(decoding.codeword.as_ref(), Some(&word));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn t_plus_one_errors_are_not_corrected() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for (m, t, _) in CODES {
+            let bch = Bch::new(m, t).unwrap();
+            for _ in 0..50 {
+                let (word, _) = codeword(&bch, &mut rng);
+                let (received, _) = flip(&word, t + 1, &mut rng);
+                // Either the decoder gives up, or it lands on some other codeword within t.
+                assert_ne!(
+                    bch.decode(&received).codeword,
+                    Some(word),
+                    "m = {m}, t = {t}"
+                );
+            }
+        }
+    }
+}
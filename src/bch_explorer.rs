@@ -0,0 +1,243 @@
+use crate::{
+    bch::{Bch, Decoding},
+    gf::{self, Field},
+};
+use egui::{Color32, DragValue, RichText, TextEdit};
+use rand::{seq::index, thread_rng, Rng};
+
+/// Well-known codes, as `(m, t)`.
+const PRESETS: [(u32, usize); 3] = [(3, 1), (4, 2), (5, 3)];
+
+fn parse_bits(s: &str) -> Result<Vec<bool>, char> {
+    s.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            c => Err(c),
+        })
+        .collect()
+}
+
+fn format_bits(bits: &[bool]) -> String {
+    bits.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
+
+/// Writes a polynomial over GF(2^m), lowest coefficient first in `p`, e.g. `α^3x^2 + x + 1`.
+fn format_locator(field: &Field, p: &[u32]) -> String {
+    let terms: Vec<String> = p
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &c)| c != 0)
+        .map(|(i, &c)| {
+            let coefficient = match (c, i) {
+                (1, 0) => "1".to_string(),
+                (1, _) => String::new(),
+                _ => field.format_power(c),
+            };
+            match i {
+                0 => coefficient,
+                1 => format!("{coefficient}x"),
+                i => format!("{coefficient}x^{i}"),
+            }
+        })
+        .collect();
+    terms.join(" + ")
+}
+
+/// Encodes and decodes single BCH codewords, showing the steps of Berlekamp–Massey decoding.
+pub struct BchExplorer {
+    pub open: bool,
+    m: u32,
+    t: usize,
+    code: Bch,
+    data: String,
+    received: String,
+}
+
+impl BchExplorer {
+    pub fn new() -> Self {
+        let (m, t) = PRESETS[1];
+        Self {
+            open: false,
+            m,
+            t,
+            code: Bch::new(m, t).unwrap(),
+            data: String::new(),
+            received: String::new(),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("BCH Codes")
+            .open(&mut open)
+            .default_width(500.0)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn set_code(&mut self, m: u32, t: usize) {
+        if let Some(code) = Bch::new(m, t) {
+            self.m = m;
+            self.t = t;
+            self.code = code;
+            self.data.clear();
+            self.received.clear();
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            for (m, t) in PRESETS {
+                let n = (1usize << m) - 1;
+                let k = Bch::new(m, t).map_or(0, |c| c.k());
+                if ui
+                    .selectable_label((self.m, self.t) == (m, t), format!("({n},{k},{t})"))
+                    .clicked()
+                {
+                    self.set_code(m, t);
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let (mut m, mut t) = (self.m, self.t);
+            ui.label("m =");
+            ui.add(DragValue::new(&mut m).clamp_range(3..=gf::MAX_M));
+            // Designed distance 2t + 1 can't exceed the length 2^m - 1.
+            let max_t = ((1usize << m) - 2) / 2;
+            ui.label("t =");
+            ui.add(DragValue::new(&mut t).clamp_range(1..=max_t));
+            let t = t.min(max_t);
+            if (m, t) != (self.m, self.t) {
+                self.set_code(m, t);
+            }
+        });
+        let code = &self.code;
+        ui.label(format!(
+            "({}, {}) code correcting {} errors, g(x) = {}",
+            code.n(),
+            code.k(),
+            code.t(),
+            gf::format_poly_bits(code.generator())
+        ));
+        ui.label("Bits are in order of the power of x they multiply, starting from x^0.");
+        ui.label(
+            "BCH codes work here one codeword at a time. They aren't in the Code menu, since the \
+             pipeline only runs the Hamming codes the hamming crate implements over bytes.",
+        );
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Data:");
+            ui.add(
+                TextEdit::singleline(&mut self.data)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(format!("{} bits", self.code.k())),
+            );
+            if ui.button("Random").clicked() {
+                let mut rng = thread_rng();
+                let data: Vec<bool> = (0..self.code.k()).map(|_| rng.gen()).collect();
+                self.data = format_bits(&data);
+            }
+        });
+        let codeword = match parse_bits(&self.data) {
+            Err(c) => {
+                ui.colored_label(Color32::RED, format!("{c:?} is not a binary digit."));
+                return;
+            }
+            Ok(data) if data.len() != self.code.k() => {
+                ui.label(format!("{} of {} bits entered.", data.len(), self.code.k()));
+                return;
+            }
+            Ok(data) => self.code.encode(&data),
+        };
+        ui.horizontal(|ui| {
+            ui.label("Codeword:");
+            ui.label(RichText::new(format_bits(&codeword)).monospace());
+        });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label("Received:");
+            ui.add(
+                TextEdit::singleline(&mut self.received)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(format!("{} bits", self.code.n())),
+            );
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Copy codeword").clicked() {
+                self.received = format_bits(&codeword);
+            }
+            for errors in [self.t, self.t + 1] {
+                if ui.button(format!("Codeword + {errors} errors")).clicked() {
+                    let mut word = codeword.clone();
+                    for i in index::sample(&mut thread_rng(), word.len(), errors).iter() {
+                        word[i] ^= true;
+                    }
+                    self.received = format_bits(&word);
+                }
+            }
+        });
+        match parse_bits(&self.received) {
+            Err(c) => {
+                ui.colored_label(Color32::RED, format!("{c:?} is not a binary digit."));
+            }
+            Ok(received) if received.len() != self.code.n() => {
+                ui.label(format!(
+                    "{} of {} bits entered.",
+                    received.len(),
+                    self.code.n()
+                ));
+            }
+            Ok(received) => {
+                let decoding = self.code.decode(&received);
+                self.decoding_ui(ui, &decoding, &codeword);
+            }
+        }
+    }
+
+    fn decoding_ui(&self, ui: &mut egui::Ui, decoding: &Decoding, sent: &[bool]) {
+        let field = self.code.field();
+        let syndromes: Vec<String> = decoding
+            .syndromes
+            .iter()
+            .enumerate()
+            .map(|(j, &s)| format!("S{} = {}", j + 1, field.format_power(s)))
+            .collect();
+        ui.label(format!("Syndromes: {}", syndromes.join(", ")));
+        ui.label(format!(
+            "Error locator: Λ(x) = {}",
+            format_locator(field, &decoding.locator)
+        ));
+        ui.label(format!("Roots of Λ found at bits {:?}", decoding.errors));
+        match &decoding.codeword {
+            Some(corrected) => {
+                ui.horizontal(|ui| {
+                    ui.label("Corrected:");
+                    ui.label(RichText::new(format_bits(corrected)).monospace());
+                });
+                ui.label(format!(
+                    "Decoded data: {}",
+                    format_bits(self.code.data(corrected))
+                ));
+                if corrected == sent {
+                    ui.colored_label(Color32::GREEN, "Matches the codeword sent.");
+                } else {
+                    ui.colored_label(
+                        Color32::RED,
+                        "Decoded to a different codeword: too many errors to correct.",
+                    );
+                }
+            }
+            None => {
+                ui.colored_label(
+                    Color32::RED,
+                    "Λ(x) doesn't have as many roots as its degree; the errors can only be detected.",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use egui::{vec2, Align2, Color32, FontId, Rect, Response, Sense, Ui};
+
+/// The size of one cell, and so the height of one row.
+pub const CELL: f32 = 14.0;
+
+pub struct GridResponse {
+    pub response: Response,
+    pub hovered: Option<usize>,
+    pub clicked: Option<usize>,
+}
+
+/// Draws `bytes` as a grid of bits, one byte per row with the most significant bit first (as in
+/// `{:08b}`). Bits are indexed `Lsb0`, matching `BitVec<u8, Lsb0>`. `fill` gives the background
+/// of a cell from its bit index and value.
+pub fn bit_grid(ui: &mut Ui, bytes: &[u8], fill: impl Fn(usize, bool) -> Color32) -> GridResponse {
+    let size = vec2(8.0 * CELL, bytes.len() as f32 * CELL);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+
+    let bit_at = |pos: egui::Pos2| {
+        if !rect.contains(pos) {
+            return None;
+        }
+        let col = ((pos.x - rect.left()) / CELL) as usize;
+        let row = ((pos.y - rect.top()) / CELL) as usize;
+        (row < bytes.len() && col < 8).then(|| row * 8 + 7 - col)
+    };
+    let hovered = response.hover_pos().and_then(&bit_at);
+    let clicked = if response.clicked() {
+        response.interact_pointer_pos().and_then(&bit_at)
+    } else {
+        None
+    };
+
+    let clip = ui.clip_rect();
+    if clip.intersects(rect) {
+        let first_row = (((clip.top() - rect.top()) / CELL).max(0.0)) as usize;
+        let last_row =
+            ((((clip.bottom() - rect.top()) / CELL).max(0.0)) as usize + 1).min(bytes.len());
+        let painter = ui.painter();
+        let text_color = ui.visuals().strong_text_color();
+        for (row, byte) in bytes.iter().enumerate().take(last_row).skip(first_row) {
+            for col in 0..8 {
+                let bit = row * 8 + 7 - col;
+                let set = (byte >> (7 - col)) & 1 == 1;
+                let cell = Rect::from_min_size(
+                    rect.min + vec2(col as f32 * CELL, row as f32 * CELL),
+                    vec2(CELL, CELL),
+                )
+                .shrink(1.0);
+                painter.rect_filled(cell, 2.0, fill(bit, set));
+                painter.text(
+                    cell.center(),
+                    Align2::CENTER_CENTER,
+                    if set { "1" } else { "0" },
+                    FontId::monospace(CELL * 0.75),
+                    text_color,
+                );
+            }
+        }
+    }
+
+    GridResponse {
+        response,
+        hovered,
+        clicked,
+    }
+}
+
+/// The width of the minimap column.
+const MINIMAP_WIDTH: f32 = 16.0;
+
+pub struct Minimap {
+    pub rect: Rect,
+    /// The row clicked on, if any.
+    pub clicked: Option<usize>,
+}
+
+/// Draws a column `height` tall standing for all `rows` rows of a bit grid, squeezed to fit, with
+/// `markers` (a first row, a row count, and a colour) drawn across it.
+pub fn minimap(
+    ui: &mut Ui,
+    rows: usize,
+    height: f32,
+    markers: &[(usize, usize, Color32)],
+) -> Minimap {
+    let (rect, response) = ui.allocate_exact_size(vec2(MINIMAP_WIDTH, height), Sense::click());
+    let row_height = height / rows.max(1) as f32;
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    for &(row, count, color) in markers {
+        let top = rect.top() + row as f32 * row_height;
+        // However many rows share a pixel, a marker stays visible.
+        let h = (count as f32 * row_height).max(1.0);
+        painter.rect_filled(
+            Rect::from_min_size(egui::pos2(rect.left(), top), vec2(rect.width(), h)),
+            0.0,
+            color,
+        );
+    }
+    let clicked = if response.clicked() {
+        response
+            .interact_pointer_pos()
+            .map(|pos| (((pos.y - rect.top()) / row_height) as usize).min(rows.saturating_sub(1)))
+    } else {
+        None
+    };
+    Minimap { rect, clicked }
+}
+
+/// Outlines, on a minimap, the rows `first` to `first + count` that are in view.
+pub fn minimap_viewport(ui: &Ui, minimap: &Minimap, rows: usize, first: f32, count: f32) {
+    let rect = minimap.rect;
+    let row_height = rect.height() / rows.max(1) as f32;
+    let top = (rect.top() + first * row_height).min(rect.bottom());
+    let bottom = (top + count * row_height).clamp(top + 2.0, rect.bottom());
+    ui.painter_at(rect).rect_stroke(
+        Rect::from_min_max(
+            egui::pos2(rect.left(), top),
+            egui::pos2(rect.right(), bottom),
+        ),
+        1.0,
+        ui.visuals().selection.stroke,
+    );
+}
@@ -0,0 +1,142 @@
+/// An image as rows of RGB pixels, top row first.
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    /// Three bytes per pixel.
+    pub rgb: Vec<u8>,
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(i..i + 4)?.try_into().ok()?))
+}
+
+/// Reads an uncompressed BMP file of 24 or 32 bit pixels.
+pub fn parse(bytes: &[u8]) -> Result<Bitmap, String> {
+    if bytes.get(0..2) != Some(b"BM") {
+        return Err("That isn't a BMP file.".to_string());
+    }
+    let truncated = || "The BMP file is cut short.".to_string();
+    let offset = u32_at(bytes, 10).ok_or_else(truncated)? as usize;
+    let width = u32_at(bytes, 18).ok_or_else(truncated)? as i32;
+    let height = u32_at(bytes, 22).ok_or_else(truncated)? as i32;
+    let bits = u16_at(bytes, 28).ok_or_else(truncated)?;
+    let compression = u32_at(bytes, 30).ok_or_else(truncated)?;
+    // Bit fields are allowed for 32 bit pixels as long as they are the usual ones, which isn't
+    // checked.
+    if compression != 0 && !(compression == 3 && bits == 32) {
+        return Err("Only uncompressed BMP files can be read.".to_string());
+    }
+    if bits != 24 && bits != 32 {
+        return Err(format!(
+            "{bits} bit pixels aren't supported, only 24 and 32."
+        ));
+    }
+    if width <= 0 || height == 0 {
+        return Err("The BMP file has no pixels.".to_string());
+    }
+    // Rows run bottom to top unless the height is negative.
+    let (width, bottom_up) = (width as usize, height > 0);
+    let height = height.unsigned_abs() as usize;
+    let pixel_bytes = usize::from(bits / 8);
+    // The sides come from the file, so the pixel data they describe is checked to be there
+    // before anything is allocated for it. Rows are padded to a multiple of four bytes.
+    let row_bytes = width.checked_mul(pixel_bytes).ok_or_else(truncated)?;
+    let stride = row_bytes
+        .checked_next_multiple_of(4)
+        .ok_or_else(truncated)?;
+    let end = stride
+        .checked_mul(height - 1)
+        .and_then(|n| n.checked_add(offset))
+        .and_then(|n| n.checked_add(row_bytes))
+        .ok_or_else(truncated)?;
+    if end > bytes.len() {
+        return Err(truncated());
+    }
+    // No bigger than the pixel data, having at least three bytes a pixel.
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        let row = if bottom_up { height - 1 - y } else { y };
+        let start = offset + row * stride;
+        let row = &bytes[start..start + row_bytes];
+        for pixel in row.chunks_exact(pixel_bytes) {
+            // Stored blue, green, red.
+            rgb.extend_from_slice(&[pixel[2], pixel[1], pixel[0]]);
+        }
+    }
+    Ok(Bitmap { width, height, rgb })
+}
+
+impl Bitmap {
+    /// The image shrunk by keeping every `step`th pixel across and down, so that neither side is
+    /// longer than `max_side`.
+    pub fn fit(self, max_side: usize) -> Bitmap {
+        let step = self.width.max(self.height).div_ceil(max_side);
+        if step <= 1 {
+            return self;
+        }
+        let (width, height) = (self.width.div_ceil(step), self.height.div_ceil(step));
+        // Never more than the pixels there are, whatever the sides say.
+        let capacity = width
+            .checked_mul(height)
+            .and_then(|n| n.checked_mul(3))
+            .map_or(0, |n| n.min(self.rgb.len()));
+        let mut rgb = Vec::with_capacity(capacity);
+        for y in (0..self.height).step_by(step) {
+            for x in (0..self.width).step_by(step) {
+                let i = (y * self.width + x) * 3;
+                rgb.extend_from_slice(&self.rgb[i..i + 3]);
+            }
+        }
+        Bitmap { width, height, rgb }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The 54-byte header of a 24 bit BMP file with pixel data straight after it.
+    fn header(width: i32, height: i32) -> Vec<u8> {
+        let mut bytes = vec![0; 54];
+        bytes[0..2].copy_from_slice(b"BM");
+        bytes[10..14].copy_from_slice(&54u32.to_le_bytes());
+        bytes[14..18].copy_from_slice(&40u32.to_le_bytes());
+        bytes[18..22].copy_from_slice(&width.to_le_bytes());
+        bytes[22..26].copy_from_slice(&height.to_le_bytes());
+        bytes[26..28].copy_from_slice(&1u16.to_le_bytes());
+        bytes[28..30].copy_from_slice(&24u16.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn bottom_up_rows() {
+        let mut bytes = header(1, 2);
+        // Each row is one pixel, blue, green, red, padded to four bytes.
+        bytes.extend_from_slice(&[1, 2, 3, 0, 4, 5, 6, 0]);
+        let bitmap = parse(&bytes).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (1, 2));
+        assert_eq!(bitmap.rgb, [6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn oversized_header() {
+        for (width, height) in [
+            (i32::MAX, i32::MAX),
+            (i32::MAX, i32::MIN + 1),
+            (1, i32::MAX),
+        ] {
+            assert!(parse(&header(width, height)).is_err());
+        }
+    }
+
+    #[test]
+    fn truncated_pixels() {
+        let mut bytes = header(2, 2);
+        bytes.extend_from_slice(&[0; 12]);
+        assert!(parse(&bytes).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use egui::{DragValue, TextEdit};
+
+pub struct Bookmark {
+    pub block: usize,
+    pub note: String,
+}
+
+/// Codewords marked for coming back to, listed in a side panel.
+pub struct Bookmarks {
+    pub open: bool,
+    pub list: Vec<Bookmark>,
+    new_block: usize,
+    new_note: String,
+}
+
+impl Bookmarks {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            list: Vec::new(),
+            new_block: 0,
+            new_note: String::new(),
+        }
+    }
+
+    pub fn contains(&self, block: usize) -> bool {
+        self.list.iter().any(|b| b.block == block)
+    }
+
+    /// Bookmarks `block`, keeping the list in order, unless it already is.
+    pub fn add(&mut self, block: usize, note: String) {
+        if let Err(i) = self.list.binary_search_by_key(&block, |b| b.block) {
+            self.list.insert(i, Bookmark { block, note });
+        }
+    }
+
+    /// Shows the bookmark list. `blocks` is the number of codewords in the stream. Returns the
+    /// codeword to go to, if one was picked.
+    pub fn show(&mut self, ctx: &egui::Context, blocks: usize) -> Option<usize> {
+        if !self.open {
+            return None;
+        }
+        let mut go = None;
+        egui::SidePanel::right("bookmarks").show(ctx, |ui| {
+            ui.heading("Bookmarks");
+            ui.horizontal(|ui| {
+                ui.label("Codeword");
+                ui.add(
+                    DragValue::new(&mut self.new_block).clamp_range(0..=blocks.saturating_sub(1)),
+                );
+            });
+            ui.add(TextEdit::singleline(&mut self.new_note).hint_text("Note (optional)"));
+            let exists = self.contains(self.new_block);
+            if ui
+                .add_enabled(blocks > 0 && !exists, egui::Button::new("Add bookmark"))
+                .clicked()
+            {
+                let note = std::mem::take(&mut self.new_note);
+                self.add(self.new_block, note);
+            }
+            ui.separator();
+
+            if self.list.is_empty() {
+                ui.label("No bookmarks yet.");
+            }
+            let mut remove = None;
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (i, b) in self.list.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        let missing = b.block >= blocks;
+                        if ui
+                            .add_enabled(
+                                !missing,
+                                egui::Button::new(format!("Codeword {}", b.block)),
+                            )
+                            .on_disabled_hover_text("The stream is too short for this codeword.")
+                            .clicked()
+                        {
+                            go = Some(b.block);
+                        }
+                        if ui.small_button("✕").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                    ui.add(TextEdit::singleline(&mut b.note).hint_text("Note"));
+                }
+            });
+            if let Some(i) = remove {
+                self.list.remove(i);
+            }
+        });
+        go
+    }
+}
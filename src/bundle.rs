@@ -0,0 +1,120 @@
+use crate::{
+    analysis,
+    codes::CodeInfo,
+    decoders::Outcome,
+    session::{self, Session},
+};
+use std::{
+    fs::File,
+    io::{Read, Seek, Write},
+    path::Path,
+};
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const README: &str = "\
+session.json          Open with Session > Open... in hamming-gui to pick up where this left off.
+input/message.txt     The message that was encoded.
+errors/error.bin      The error pattern XORed onto the encoded stream.
+errors/flipped.csv    Every flipped bit, and the codeword it falls in.
+reports/codewords.csv How many bits of each codeword are flipped.
+reports/decoders.csv  What each decoder made of the received stream.
+";
+
+/// Everything that goes into a session bundle.
+pub struct Bundle<'a> {
+    pub session: &'a Session,
+    pub info: &'a CodeInfo,
+    pub outcomes: &'a [Outcome],
+}
+
+impl Bundle<'_> {
+    /// Writes the session, its input, its error pattern, and reports on it into one zip file,
+    /// to hand out as a complete exercise.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut zip = ZipWriter::new(file);
+        let session = serde_json::to_string_pretty(self.session).map_err(|e| e.to_string())?;
+        add(&mut zip, "README.txt", README.as_bytes())?;
+        add(&mut zip, "session.json", session.as_bytes())?;
+        add(
+            &mut zip,
+            "input/message.txt",
+            self.session.message_in.as_bytes(),
+        )?;
+        add(&mut zip, "errors/error.bin", &self.session.error)?;
+        add(
+            &mut zip,
+            "errors/flipped.csv",
+            self.flipped_csv().as_bytes(),
+        )?;
+        add(
+            &mut zip,
+            "reports/codewords.csv",
+            self.codewords_csv().as_bytes(),
+        )?;
+        add(
+            &mut zip,
+            "reports/decoders.csv",
+            self.decoders_csv().as_bytes(),
+        )?;
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn flipped_csv(&self) -> String {
+        let error = &self.session.error;
+        let mut csv = "bit,codeword,position\n".to_string();
+        for bit in (0..error.len() * 8).filter(|&b| analysis::get_bit(error, b)) {
+            let (codeword, i) = self.info.locate(bit);
+            csv.push_str(&format!("{bit},{codeword},{}\n", self.info.position(i)));
+        }
+        csv
+    }
+
+    fn codewords_csv(&self) -> String {
+        let mut csv = "codeword,flipped\n".to_string();
+        for (j, flips) in analysis::error_counts(self.info, &self.session.error)
+            .into_iter()
+            .enumerate()
+        {
+            csv.push_str(&format!("{j},{flips}\n"));
+        }
+        csv
+    }
+
+    fn decoders_csv(&self) -> String {
+        let original = self.session.message_in.as_bytes();
+        let mut csv = "decoder,result,ms\n".to_string();
+        for o in self.outcomes {
+            let result = match &o.decoded {
+                Some(d) if d == original => "recovered",
+                Some(_) => "wrong",
+                None => "failed",
+            };
+            csv.push_str(&format!(
+                "{},{result},{:.3}\n",
+                o.algorithm.name(),
+                o.elapsed.as_secs_f64() * 1000.0
+            ));
+        }
+        csv
+    }
+}
+
+/// Reads the session back out of a bundle.
+pub fn read_session(path: &Path) -> Result<Session, String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let mut zip = ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut s = String::new();
+    zip.by_name("session.json")
+        .map_err(|e| e.to_string())?
+        .read_to_string(&mut s)
+        .map_err(|e| e.to_string())?;
+    session::parse(&s)
+}
+
+fn add<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, contents: &[u8]) -> Result<(), String> {
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    zip.write_all(contents).map_err(|e| e.to_string())
+}
@@ -0,0 +1,117 @@
+use crate::analysis;
+use egui::DragValue;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How the bursts of an error are placed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// A set number of bursts, each starting anywhere in the stream.
+    #[default]
+    Count,
+    /// A burst may start at each bit with a set chance.
+    Chance,
+}
+
+impl Placement {
+    pub const ALL: [Placement; 2] = [Placement::Count, Placement::Chance];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Placement::Count => "Number of bursts",
+            Placement::Chance => "Start probability",
+        }
+    }
+}
+
+/// Errors that flip runs of neighbouring bits rather than bits on their own, as scratches and
+/// fades do. A Hamming code corrects one flip per codeword, so a burst of two or more flips that
+/// lands in one codeword defeats it.
+pub struct Bursts {
+    /// Bits flipped by each burst.
+    pub length: usize,
+    pub placement: Placement,
+    pub count: usize,
+    pub probability: f64,
+}
+
+impl Bursts {
+    pub fn new() -> Self {
+        Self {
+            length: 4,
+            placement: Placement::Count,
+            count: 3,
+            probability: 0.001,
+        }
+    }
+
+    /// A `len`-byte error pattern of bursts drawn from `seed`, and how many bursts it has.
+    /// Bursts that overlap merge into one longer run, and those that run off the end are cut
+    /// short.
+    pub fn pattern(&self, seed: u64, len: usize) -> (Vec<u8>, usize) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let bits = len * 8;
+        let mut error = vec![0; len];
+        if bits == 0 {
+            return (error, 0);
+        }
+        let starts: Vec<usize> = match self.placement {
+            Placement::Count => (0..self.count).map(|_| rng.gen_range(0..bits)).collect(),
+            Placement::Chance => {
+                let mut starts = Vec::new();
+                let mut i = 0;
+                while i < bits {
+                    if rng.gen_bool(self.probability) {
+                        starts.push(i);
+                        // A burst can't start inside another.
+                        i += self.length.max(1);
+                    } else {
+                        i += 1;
+                    }
+                }
+                starts
+            }
+        };
+        for &start in &starts {
+            for bit in start..(start + self.length).min(bits) {
+                analysis::set_bit(&mut error, bit, true);
+            }
+        }
+        (error, starts.len())
+    }
+
+    /// The burst settings on one line, with a button to randomize the error with them. Returns
+    /// whether it was pressed.
+    pub fn ui(&mut self, ui: &mut egui::Ui, enabled: bool) -> bool {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.label("Bursts of");
+            ui.add(
+                DragValue::new(&mut self.length)
+                    .clamp_range(1..=64)
+                    .suffix(" bits"),
+            );
+            egui::ComboBox::from_id_source("burst_placement")
+                .selected_text(self.placement.name())
+                .show_ui(ui, |ui| {
+                    for placement in Placement::ALL {
+                        ui.selectable_value(&mut self.placement, placement, placement.name());
+                    }
+                });
+            match self.placement {
+                Placement::Count => ui.add(DragValue::new(&mut self.count).clamp_range(1..=1000)),
+                Placement::Chance => ui.add(
+                    DragValue::new(&mut self.probability)
+                        .clamp_range(0.0..=1.0)
+                        .speed(0.0001)
+                        .max_decimals(4),
+                ),
+            };
+            ui.button("Randomize Bursts")
+                .on_hover_text(
+                    "Flips runs of neighbouring bits. One flip per codeword is corrected, so a \
+                     burst that puts two in a codeword defeats the code.",
+                )
+                .clicked()
+        })
+        .inner
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{analysis, codes::CodeInfo, decoders, manifest};
+use egui::DragValue;
+use hamming::{decode, encode, Code};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+/// Bytes after going through a channel that flips each bit with the same chance, sent as they
+/// are and sent protected by a code.
+pub struct Transmission {
+    pub unprotected: Vec<u8>,
+    pub protected: Vec<u8>,
+    /// The data bits of each protected codeword as they were received, before any correction.
+    pub protected_raw: Vec<u8>,
+    /// Bits flipped by the channel in each.
+    pub unprotected_flips: usize,
+    pub protected_flips: usize,
+}
+
+impl Transmission {
+    /// Sends `bytes` through the channel twice, as they are and encoded with `code`, with each
+    /// bit flipped with chance `probability`. Codewords the code can't correct are zeroed, as in
+    /// decoding codeword by codeword.
+    pub fn new(code: &Code, info: &CodeInfo, bytes: &[u8], probability: f64) -> Self {
+        let seed = manifest::new_seed(&mut thread_rng());
+        let flips = |error: &[u8]| error.iter().map(|b| b.count_ones() as usize).sum();
+
+        let error = manifest::error_pattern(seed, bytes.len(), probability);
+        let unprotected = Iterator::zip(bytes.iter(), error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        let unprotected_flips = flips(&error);
+
+        let encoded = encode(bytes, code).unwrap();
+        let error = manifest::error_pattern(seed, encoded.len(), probability);
+        let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        let protected = decode(&received, code)
+            .ok()
+            .or_else(|| decoders::decode_blocks(code, info, &received).map(|(d, _)| d))
+            .unwrap_or_else(|| vec![0; bytes.len()]);
+        let mut protected_raw = vec![0; bytes.len()];
+        for j in 0..analysis::codeword_count(info, &received) {
+            let word = analysis::codeword(info, &received, j).unwrap();
+            for (i, bit) in analysis::data_bits(info, &word).into_iter().enumerate() {
+                let b = j * info.k + i;
+                if bit && b < bytes.len() * 8 {
+                    analysis::set_bit(&mut protected_raw, b, true);
+                }
+            }
+        }
+        Self {
+            unprotected,
+            protected,
+            protected_raw,
+            unprotected_flips,
+            protected_flips: flips(&error),
+        }
+    }
+}
+
+/// Sends `bytes` through a channel flipping each bit with chance `probability`, encoded with
+/// `code` or bare. Codewords the code can't correct are zeroed. Returns what arrived, and how
+/// many bits were flipped.
+pub fn send(
+    code: Option<&(CodeInfo, Code)>,
+    bytes: &[u8],
+    seed: u64,
+    probability: f64,
+) -> (Vec<u8>, usize) {
+    let sent = match code {
+        Some((_, code)) => encode(bytes, code).unwrap(),
+        None => bytes.to_vec(),
+    };
+    let error = manifest::error_pattern(seed, sent.len(), probability);
+    let flips = error.iter().map(|b| b.count_ones() as usize).sum();
+    let received: Vec<u8> = Iterator::zip(sent.iter(), error.iter())
+        .map(|(b, e)| b ^ e)
+        .collect();
+    let mut arrived = match code {
+        Some((info, code)) => decode(&received, code)
+            .ok()
+            .or_else(|| decoders::decode_blocks(code, info, &received).map(|(d, _)| d))
+            .unwrap_or_default(),
+        None => received,
+    };
+    arrived.resize(bytes.len(), 0);
+    (arrived, flips)
+}
+
+/// The ways of randomizing the error other than flipping each bit with the same chance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Model {
+    #[default]
+    Bursts,
+    GilbertElliott,
+    Adversary,
+}
+
+impl Model {
+    pub const ALL: [Model; 3] = [Model::Bursts, Model::GilbertElliott, Model::Adversary];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Model::Bursts => "Bursts",
+            Model::GilbertElliott => "Gilbert-Elliott",
+            Model::Adversary => "Adversary",
+        }
+    }
+}
+
+/// A channel that is either good or bad, each with its own bit error rate, and moves between
+/// the two at random from one bit to the next. Long stays in the bad state give the bursty
+/// errors of real links.
+pub struct GilbertElliott {
+    /// The chance of going from the good state to the bad after each bit.
+    pub to_bad: f64,
+    /// The chance of going from the bad state back to the good after each bit.
+    pub to_good: f64,
+    pub good_ber: f64,
+    pub bad_ber: f64,
+}
+
+impl GilbertElliott {
+    pub fn new() -> Self {
+        Self {
+            to_bad: 0.002,
+            to_good: 0.1,
+            good_ber: 0.0001,
+            bad_ber: 0.3,
+        }
+    }
+
+    /// The share of bits sent in the bad state, in the long run.
+    pub fn bad_share(&self) -> f64 {
+        let total = self.to_bad + self.to_good;
+        if total > 0.0 {
+            self.to_bad / total
+        } else {
+            0.0
+        }
+    }
+
+    /// The bit error rate over the long run.
+    pub fn mean_ber(&self) -> f64 {
+        let bad = self.bad_share();
+        bad * self.bad_ber + (1.0 - bad) * self.good_ber
+    }
+
+    /// A `len`-byte error pattern drawn from `seed`, and how many of its bits were sent in the
+    /// bad state. The first bit's state is drawn with the long-run chances, so that the start of
+    /// the stream is like any other part of it.
+    pub fn pattern(&self, seed: u64, len: usize) -> (Vec<u8>, usize) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut error = vec![0; len];
+        let mut bad = rng.gen_bool(self.bad_share());
+        let mut bad_bits = 0;
+        for i in 0..len * 8 {
+            bad_bits += usize::from(bad);
+            let ber = if bad { self.bad_ber } else { self.good_ber };
+            if rng.gen_bool(ber) {
+                analysis::set_bit(&mut error, i, true);
+            }
+            let change = if bad { self.to_good } else { self.to_bad };
+            if rng.gen_bool(change) {
+                bad = !bad;
+            }
+        }
+        (error, bad_bits)
+    }
+
+    /// The model's settings on one line, with a button to randomize the error with them. Returns
+    /// whether it was pressed.
+    pub fn ui(&mut self, ui: &mut egui::Ui, enabled: bool) -> bool {
+        fn probability(value: &mut f64) -> DragValue<'_> {
+            DragValue::new(value)
+                .clamp_range(0.0..=1.0)
+                .speed(0.0001)
+                .max_decimals(4)
+        }
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.label("Good to bad");
+            ui.add(probability(&mut self.to_bad));
+            ui.label("Bad to good");
+            ui.add(probability(&mut self.to_good));
+            ui.label("BER good");
+            ui.add(probability(&mut self.good_ber));
+            ui.label("BER bad");
+            ui.add(probability(&mut self.bad_ber));
+            let stays = if self.to_good > 0.0 {
+                format!("in stays of {:.1} bits on average", 1.0 / self.to_good)
+            } else {
+                "and never leaves it".to_string()
+            };
+            ui.button("Randomize Gilbert-Elliott")
+                .on_hover_text(format!(
+                    "In the long run {:.1}% of bits are sent in the bad state, {stays}, for a bit \
+                     error rate of {:.5}.",
+                    self.bad_share() * 100.0,
+                    self.mean_ber()
+                ))
+                .clicked()
+        })
+        .inner
+    }
+}
@@ -0,0 +1,76 @@
+use egui::{text::LayoutJob, Color32, TextFormat, TextStyle};
+
+/// A restriction on which characters a message may contain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    Unrestricted,
+    Ascii,
+    /// DEC SIXBIT: space through underscore, without lowercase letters.
+    Sixbit,
+}
+
+impl Alphabet {
+    pub const ALL: [Alphabet; 3] = [Alphabet::Unrestricted, Alphabet::Ascii, Alphabet::Sixbit];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Alphabet::Unrestricted => "Any (UTF-8)",
+            Alphabet::Ascii => "7-bit ASCII",
+            Alphabet::Sixbit => "6-bit (SIXBIT)",
+        }
+    }
+
+    /// Why `c` can't be encoded with this alphabet, if it can't.
+    pub fn reject(self, c: char) -> Option<&'static str> {
+        match self {
+            Alphabet::Unrestricted => None,
+            Alphabet::Ascii if !c.is_ascii() => Some("not a 7-bit ASCII character"),
+            Alphabet::Ascii => None,
+            Alphabet::Sixbit if c.is_ascii_lowercase() => Some("SIXBIT has no lowercase letters"),
+            Alphabet::Sixbit if !(' '..='_').contains(&c) => {
+                Some("outside the SIXBIT range (space to '_')")
+            }
+            Alphabet::Sixbit => None,
+        }
+    }
+
+    /// Every character of `text` that can't be encoded, with its byte offset.
+    pub fn rejected(self, text: &str) -> Vec<(usize, char, &'static str)> {
+        text.char_indices()
+            .filter_map(|(i, c)| self.reject(c).map(|why| (i, c, why)))
+            .collect()
+    }
+
+    /// Lays out `text` with the characters that can't be encoded highlighted.
+    pub fn highlight(self, ui: &egui::Ui, text: &str, wrap_width: f32) -> LayoutJob {
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let mut job = LayoutJob::simple(String::new(), font_id.clone(), color, wrap_width);
+        let valid = TextFormat {
+            font_id: font_id.clone(),
+            color,
+            ..Default::default()
+        };
+        let invalid = TextFormat {
+            font_id,
+            color: Color32::WHITE,
+            background: Color32::from_rgb(200, 40, 40),
+            ..Default::default()
+        };
+
+        let mut start = 0;
+        let mut run_invalid = false;
+        for (i, c) in text.char_indices() {
+            let is_invalid = self.reject(c).is_some();
+            if is_invalid != run_invalid {
+                let format = if run_invalid { &invalid } else { &valid };
+                job.append(&text[start..i], 0.0, format.clone());
+                start = i;
+                run_invalid = is_invalid;
+            }
+        }
+        let format = if run_invalid { invalid } else { valid };
+        job.append(&text[start..], 0.0, format);
+        job
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    analysis::{self, Check},
+    codes::CodeInfo,
+};
+use egui::{Color32, DragValue, Grid, RichText, TextEdit};
+
+fn format_bits(bits: &[bool]) -> String {
+    bits.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
+
+/// Reports whether a word is a valid codeword, its syndrome, and how far it is from one.
+pub struct Checker {
+    pub open: bool,
+    word: String,
+    block: usize,
+    candidates: usize,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            word: String::new(),
+            block: 0,
+            candidates: 5,
+        }
+    }
+
+    /// `received` is the encoded stream after errors, which blocks can be copied from.
+    pub fn show(&mut self, ctx: &egui::Context, info: &CodeInfo, received: &[u8]) {
+        let mut open = self.open;
+        egui::Window::new("Codeword Checker")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info, received));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo, received: &[u8]) {
+        ui.label(format!(
+            "Enter {} bits of a {} codeword, in position order starting from position {}.",
+            info.n,
+            info.name,
+            info.position(0)
+        ));
+        ui.add(
+            TextEdit::singleline(&mut self.word)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(f32::INFINITY),
+        );
+
+        let blocks = analysis::codeword_count(info, received);
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(blocks > 0, |ui| {
+                ui.label("Received block");
+                ui.add(DragValue::new(&mut self.block).clamp_range(0..=blocks.saturating_sub(1)));
+                if ui.button("Load").clicked() {
+                    if let Some(bits) = analysis::codeword(info, received, self.block) {
+                        self.word = format_bits(&bits);
+                    }
+                }
+            });
+        });
+        ui.separator();
+
+        let word: Vec<bool> = self
+            .word
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c == '1')
+            .collect();
+        if let Some(c) = self
+            .word
+            .chars()
+            .find(|c| !c.is_whitespace() && *c != '0' && *c != '1')
+        {
+            ui.colored_label(Color32::RED, format!("{c:?} is not a binary digit."));
+            return;
+        }
+        if word.len() != info.n {
+            ui.label(format!("{} of {} bits entered.", word.len(), info.n));
+            return;
+        }
+
+        let check = analysis::check(info, &word);
+        if check.is_valid() {
+            ui.colored_label(Color32::GREEN, "Valid codeword.");
+        } else {
+            ui.colored_label(Color32::RED, "Not a codeword.");
+        }
+        let Check {
+            syndrome,
+            overall_even,
+            distance,
+            flip,
+        } = check;
+        ui.label(format!("Syndrome: {syndrome} ({syndrome:b})"));
+        if let Some(even) = overall_even {
+            ui.label(format!(
+                "Overall parity: {}",
+                if even { "even (passes)" } else { "odd (fails)" }
+            ));
+        }
+        ui.label(format!("Distance to nearest codeword: {distance}"));
+        match flip {
+            Some(i) => {
+                ui.label(format!(
+                    "Nearest codeword: flip the bit at position {}.",
+                    info.position(i)
+                ));
+            }
+            None if distance > 1 => {
+                ui.label("Several codewords are equally near; the error can only be detected.");
+            }
+            None => {}
+        }
+
+        ui.separator();
+        self.nearest_ui(ui, info, &word);
+    }
+
+    fn nearest_ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo, word: &[bool]) {
+        ui.horizontal(|ui| {
+            ui.label("Nearest codewords:");
+            ui.add(DragValue::new(&mut self.candidates).clamp_range(1..=32));
+        });
+        let candidates = match analysis::nearest(info, word, self.candidates) {
+            Some(candidates) => candidates,
+            None => {
+                ui.label(format!(
+                    "{} has too many codewords to search them all.",
+                    info.name
+                ));
+                return;
+            }
+        };
+
+        let best = candidates.first().map_or(0, |c| c.distance);
+        let ties = candidates.iter().filter(|c| c.distance == best).count();
+        if ties > 1 {
+            ui.colored_label(
+                Color32::YELLOW,
+                format!(
+                    "{ties} codewords are at distance {best}; maximum-likelihood decoding has to guess."
+                ),
+            );
+        } else {
+            ui.label(format!(
+                "Maximum-likelihood decoding picks the only codeword at distance {best}."
+            ));
+        }
+
+        Grid::new("nearest_codewords").striped(true).show(ui, |ui| {
+            ui.strong("Distance");
+            ui.strong("Codeword");
+            ui.strong("Data");
+            ui.end_row();
+            for c in &candidates {
+                ui.label(c.distance.to_string());
+                ui.label(RichText::new(format_bits(&c.codeword)).monospace());
+                ui.label(RichText::new(format_bits(&c.data)).monospace());
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{analysis, codes::CodeInfo, manifest};
+use egui::{Color32, DragValue, Grid};
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+/// CRC generator polynomials by number of check bits, the top bit included, starting from one.
+/// Three check bits give x^3 + x + 1, which generates the (7, 4) Hamming code itself.
+const POLYNOMIALS: [u32; 8] = [
+    0b11,
+    0b111,
+    0b1011,
+    0b1_0011,
+    0b10_0101,
+    0b100_0011,
+    0b1000_1001,
+    0b1_0000_0111,
+];
+
+/// The ways of protecting a block of data compared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scheme {
+    Parity,
+    Crc,
+    Hamming,
+}
+
+impl Scheme {
+    const ALL: [Scheme; 3] = [Scheme::Parity, Scheme::Crc, Scheme::Hamming];
+
+    fn name(self) -> &'static str {
+        match self {
+            Scheme::Parity => "Parity",
+            Scheme::Crc => "CRC",
+            Scheme::Hamming => "Hamming",
+        }
+    }
+
+    /// The check bits added to each block of `info.k` data bits.
+    fn check_bits(self, info: &CodeInfo) -> usize {
+        match self {
+            Scheme::Parity => 1,
+            Scheme::Crc | Scheme::Hamming => info.n - info.k,
+        }
+    }
+}
+
+/// What came of one block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Verdict {
+    /// No bits were flipped.
+    Clean,
+    /// Bits were flipped and the right data came out anyway.
+    Corrected,
+    /// Bits were flipped and the block was flagged as damaged.
+    Detected,
+    /// Bits were flipped and wrong data came out unflagged.
+    Missed,
+}
+
+impl Verdict {
+    const ALL: [Verdict; 4] = [
+        Verdict::Clean,
+        Verdict::Corrected,
+        Verdict::Detected,
+        Verdict::Missed,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Clean => "Clean",
+            Verdict::Corrected => "Corrected",
+            Verdict::Detected => "Detected",
+            Verdict::Missed => "Missed",
+        }
+    }
+
+    fn color(self) -> Option<Color32> {
+        match self {
+            Verdict::Clean => None,
+            Verdict::Corrected => Some(Color32::GREEN),
+            Verdict::Detected => Some(Color32::YELLOW),
+            Verdict::Missed => Some(Color32::RED),
+        }
+    }
+}
+
+/// The remainder of `bits`, highest power first, divided by `polynomial` of degree `r`.
+fn remainder(bits: &[bool], polynomial: u32, r: usize) -> u32 {
+    bits.iter().fold(0, |rem, &b| {
+        let rem = rem << 1 | u32::from(b);
+        if rem >> r & 1 == 1 {
+            rem ^ polynomial
+        } else {
+            rem
+        }
+    })
+}
+
+/// `data` with `r` CRC bits after it.
+fn crc_encode(data: &[bool], r: usize) -> Vec<bool> {
+    let polynomial = POLYNOMIALS[r.clamp(1, POLYNOMIALS.len()) - 1];
+    let mut word = data.to_vec();
+    word.resize(data.len() + r, false);
+    let rem = remainder(&word, polynomial, r);
+    for i in 0..r {
+        word[data.len() + i] = rem >> (r - 1 - i) & 1 == 1;
+    }
+    word
+}
+
+/// What `scheme` makes of `data` sent with `flips` flipped. `flips` is as long as the longest
+/// block of any scheme; shorter blocks take the start of it.
+fn verdict(scheme: Scheme, info: &CodeInfo, data: &[bool], flips: &[bool]) -> Verdict {
+    let sent = match scheme {
+        Scheme::Parity => {
+            let mut word = data.to_vec();
+            word.push(data.iter().filter(|&&b| b).count() % 2 == 1);
+            word
+        }
+        Scheme::Crc => crc_encode(data, info.n - info.k),
+        Scheme::Hamming => analysis::encode_word(info, data),
+    };
+    if !flips[..sent.len()].contains(&true) {
+        return Verdict::Clean;
+    }
+    let received: Vec<bool> = Iterator::zip(sent.iter(), flips.iter())
+        .map(|(s, f)| s ^ f)
+        .collect();
+    let out = match scheme {
+        Scheme::Parity => {
+            let even = received.iter().filter(|&&b| b).count() % 2 == 0;
+            even.then(|| received[..data.len()].to_vec())
+        }
+        Scheme::Crc => {
+            let r = info.n - info.k;
+            let polynomial = POLYNOMIALS[r.clamp(1, POLYNOMIALS.len()) - 1];
+            (remainder(&received, polynomial, r) == 0).then(|| received[..data.len()].to_vec())
+        }
+        Scheme::Hamming => {
+            let check = analysis::check(info, &received);
+            (check.distance <= 1).then(|| {
+                let mut word = received.clone();
+                if let Some(i) = check.flip {
+                    word[i] = !word[i];
+                }
+                analysis::data_bits(info, &word)
+            })
+        }
+    };
+    match out {
+        None => Verdict::Detected,
+        Some(out) if out == data => Verdict::Corrected,
+        Some(_) => Verdict::Missed,
+    }
+}
+
+/// How many blocks each scheme gave each verdict, in the order of `Scheme::ALL` and
+/// `Verdict::ALL`.
+struct Tally {
+    counts: [[usize; 4]; 3],
+    blocks: usize,
+}
+
+impl Tally {
+    /// Sends `blocks` blocks of the message through every scheme, going round the message as
+    /// often as it takes. Each block's flips are drawn once and applied to every scheme.
+    fn new(info: &CodeInfo, message: &[u8], blocks: usize, seed: u64, probability: f64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let bits = message.len() * 8;
+        let in_message = bits.div_ceil(info.k);
+        let mut counts = [[0; 4]; 3];
+        for b in 0..blocks {
+            let start = b % in_message * info.k;
+            let data: Vec<bool> = (start..start + info.k)
+                .map(|i| i < bits && analysis::get_bit(message, i))
+                .collect();
+            let flips: Vec<bool> = (0..info.n.max(info.k + 1))
+                .map(|_| rng.gen_bool(probability))
+                .collect();
+            for (s, scheme) in Scheme::ALL.into_iter().enumerate() {
+                let v = verdict(scheme, info, &data, &flips);
+                counts[s][v as usize] += 1;
+            }
+        }
+        Self { counts, blocks }
+    }
+}
+
+/// Plain parity, a CRC, and the Hamming code run over the same message and errors, tallying
+/// what each detects, corrects, and misses: detection against correction on one screen.
+pub struct Checksums {
+    pub open: bool,
+    probability: f64,
+    blocks: usize,
+    seed: u64,
+    tally: Option<Tally>,
+    /// What `tally` was worked out for: the message, code, block count, seed, and probability.
+    tally_for: Option<(Vec<u8>, &'static str, usize, u64, f64)>,
+}
+
+impl Checksums {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            probability: 0.02,
+            blocks: 10_000,
+            seed: manifest::new_seed(&mut thread_rng()),
+            tally: None,
+            tally_for: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, info: &CodeInfo, message: &[u8]) {
+        if !self.open {
+            return;
+        }
+        let current = matches!(&self.tally_for, Some((m, c, b, s, p))
+            if m == message && *c == info.name && *b == self.blocks && *s == self.seed
+                && *p == self.probability);
+        if !current && !message.is_empty() {
+            self.tally = Some(Tally::new(
+                info,
+                message,
+                self.blocks,
+                self.seed,
+                self.probability,
+            ));
+            self.tally_for = Some((
+                message.to_vec(),
+                info.name,
+                self.blocks,
+                self.seed,
+                self.probability,
+            ));
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Detection vs Correction")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info, message));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo, message: &[u8]) {
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.001)
+                    .max_decimals(4),
+            );
+            ui.label("Blocks:");
+            ui.add(
+                DragValue::new(&mut self.blocks)
+                    .clamp_range(1..=1_000_000)
+                    .speed(100),
+            );
+            if ui.button("Roll again").clicked() {
+                self.seed = manifest::new_seed(&mut thread_rng());
+            }
+        });
+        let tally = match (&self.tally, message.is_empty()) {
+            (Some(tally), false) => tally,
+            _ => {
+                ui.label("Type a message to compare how it fares.");
+                return;
+            }
+        };
+        ui.small(format!(
+            "Blocks of {} data bits from the message, each with the same flips under every \
+             scheme. The CRC has as many check bits as {}, which decodes with them instead of \
+             only checking.",
+            info.k, info.name
+        ));
+        ui.separator();
+
+        let percent = |count: usize| count as f64 / tally.blocks as f64 * 100.0;
+        Grid::new("checksums").striped(true).show(ui, |ui| {
+            ui.strong("Scheme");
+            ui.strong("Check bits");
+            ui.strong("Rate");
+            for v in Verdict::ALL {
+                ui.strong(v.name());
+            }
+            ui.end_row();
+            for (s, scheme) in Scheme::ALL.into_iter().enumerate() {
+                let check = scheme.check_bits(info);
+                ui.label(scheme.name());
+                ui.label(check.to_string());
+                ui.label(format!("{:.3}", info.k as f64 / (info.k + check) as f64));
+                for v in Verdict::ALL {
+                    let count = tally.counts[s][v as usize];
+                    let text = format!("{count} ({:.2}%)", percent(count));
+                    match v.color().filter(|_| count > 0) {
+                        Some(color) => ui.colored_label(color, text),
+                        None => ui.label(text),
+                    };
+                }
+                ui.end_row();
+            }
+        });
+        ui.small(
+            "Parity and the CRC can only flag a damaged block to be sent again; Hamming fixes \
+             single flips in place. Missed blocks pass as good with the wrong data.",
+        );
+    }
+}
@@ -0,0 +1,579 @@
+use crate::{
+    analysis,
+    codes::{self, CodeInfo},
+    differential,
+    manifest::{self, Manifest},
+};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::thread_rng;
+use serde::Serialize;
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const USAGE: &str = "\
+Usage: hamming-gui [--message TEXT] [--code NAME] [--prob P] [--seed N] [FILE]
+       hamming-gui (--encode | --decode) [--code NAME] [--json-stats] < INPUT > OUTPUT
+       hamming-gui --simulate P [--code NAME] [--seed N] [--json-stats] < INPUT > OUTPUT
+       hamming-gui --replay MANIFEST [--json-stats] > OUTPUT
+       hamming-gui --differential COUNT [--code NAME] [--seed N]
+
+With no mode, opens the window, loading FILE if given, then setting up the message, code, and
+error given: with --prob, the error is randomized once, from the seed if there is one.
+
+With --encode or --decode, filters standard input to standard output a chunk at a time, so input
+of any length can be piped through.
+
+With --simulate, encodes standard input, flips each bit with probability P, decodes the result
+to standard output, and reports on standard error how the code fared.
+
+With --replay, encodes the input named by a manifest, flips the bits its seed and probability
+give, and decodes the result to standard output.
+
+With --differential, checks the encoder and decoder against an independent matrix-based model on
+COUNT random inputs.
+
+Window options:
+  --message TEXT
+                Start with TEXT as the message
+  --prob P      Start with an error flipping each bit with probability P
+  --seed N      Draw the starting error from seed N, or fill in the seed field without --prob
+
+Modes:
+  --encode      Encode standard input
+  --decode      Decode standard input, correcting what errors the code can
+  --simulate P  Send standard input through a channel flipping each bit with probability P
+
+Options:
+  --code NAME   The code to use (default eh16_11; available: h7_4, eh8_4, eh16_11)
+  --replay FILE Replay the run described by a manifest
+  --differential COUNT
+                Cross-check the hamming crate on COUNT random inputs
+  --seed N      Seed the flips of --simulate or the random inputs of --differential, to repeat
+                a run. Seeds go up to 9223372036854775807, the most a manifest can hold
+  --input FILE  Read FILE instead of standard input
+  --output FILE Write FILE instead of standard output
+  --json-stats  Write a JSON summary of the run to standard error when done
+  --help        Show this message
+
+Exit status:
+  0   Every codeword was intact, or encoding succeeded
+  1   Errors were found, and every one was corrected
+  2   Some codewords had errors that couldn't be corrected, or --differential found a
+      disagreement
+  64  The options were wrong
+  74  Reading or writing failed, or a manifest didn't match its input";
+
+const EXIT_CLEAN: i32 = 0;
+const EXIT_CORRECTED: i32 = 1;
+const EXIT_UNCORRECTABLE: i32 = 2;
+const EXIT_USAGE: i32 = 64;
+const EXIT_IO: i32 = 74;
+
+/// How many codewords are read and written at once.
+const CHUNK_CODEWORDS: usize = 8 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Mode {
+    Encode,
+    Decode,
+    /// Encode, flip bits with this probability, and decode.
+    Simulate(f64),
+    /// Replay the manifest at this path.
+    Replay(PathBuf),
+    /// Cross-check this many random inputs.
+    Differential(usize),
+}
+
+impl Mode {
+    fn name(&self) -> &'static str {
+        match self {
+            Mode::Encode => "encode",
+            Mode::Decode => "decode",
+            Mode::Simulate(_) => "simulate",
+            Mode::Replay(_) => "replay",
+            Mode::Differential(_) => "differential",
+        }
+    }
+}
+
+struct Options {
+    mode: Mode,
+    info: CodeInfo,
+    code: &'static Code,
+    json_stats: bool,
+    seed: Option<u64>,
+    /// Where to read and write instead of standard input and output.
+    input: Option<PathBuf>,
+    output: Option<PathBuf>,
+}
+
+/// The summary written by `--json-stats`, for grading scripts.
+#[derive(Debug, Default, Serialize)]
+struct Stats {
+    mode: &'static str,
+    code: &'static str,
+    bytes_in: usize,
+    bytes_out: usize,
+    /// Codewords read when decoding, or written when encoding.
+    blocks: usize,
+    /// Codewords with an error the code corrected. Always 0 when encoding.
+    corrected: usize,
+    /// Codewords with errors the code could not correct. Always 0 when encoding.
+    failed: usize,
+    /// Bits flipped by a simulation or a replayed manifest. Always 0 otherwise.
+    errors_injected: usize,
+    /// The chance of each bit flipping in a simulation, and the seed the flips were drawn from.
+    probability: Option<f64>,
+    seed: Option<u64>,
+    /// Whether a simulation or replay decoded back to its input, which a miscorrection can prevent
+    /// even when no codeword was `failed`.
+    matches_input: Option<bool>,
+    /// Time spent encoding or decoding, leaving out reading and writing.
+    codec_ms: f64,
+    /// Time for the whole run.
+    total_ms: f64,
+    /// Why the run stopped early, if it did.
+    error: Option<String>,
+}
+
+/// Why a run stopped early.
+enum Failure {
+    Io(String),
+    /// The code gave up on the data.
+    Codec(String),
+}
+
+impl Failure {
+    fn message(&self) -> &str {
+        match self {
+            Failure::Io(e) | Failure::Codec(e) => e,
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// The options that run the command line rather than open the window.
+const MODES: [&str; 7] = [
+    "--encode",
+    "--decode",
+    "--simulate",
+    "--replay",
+    "--differential",
+    "--help",
+    "-h",
+];
+
+/// Whether the arguments ask for the command line rather than the window.
+pub fn requested(args: &[OsString]) -> bool {
+    args.iter()
+        .any(|a| matches!(a.to_str(), Some(a) if MODES.contains(&a)))
+}
+
+/// The state the window is to start in, as given on the command line.
+#[derive(Default)]
+pub struct Launch {
+    pub(crate) file: Option<PathBuf>,
+    pub(crate) message: Option<String>,
+    pub(crate) code: Option<&'static (CodeInfo, Code)>,
+    pub(crate) probability: Option<f64>,
+    pub(crate) seed: Option<u64>,
+}
+
+impl Launch {
+    /// Whether anything was asked for beyond a file to open.
+    pub(crate) fn sets_up(&self) -> bool {
+        self.message.is_some()
+            || self.code.is_some()
+            || self.probability.is_some()
+            || self.seed.is_some()
+    }
+}
+
+/// Reads the window options, or returns the process exit code after saying what is wrong with
+/// them.
+pub fn launch(args: Vec<OsString>) -> Result<Launch, i32> {
+    parse_launch(args).map_err(|e| {
+        eprintln!("hamming-gui: {e}\n\n{USAGE}");
+        EXIT_USAGE
+    })
+}
+
+fn parse_launch(args: Vec<OsString>) -> Result<Launch, String> {
+    let mut launch = Launch::default();
+    let mut args = args.into_iter().map(|a| {
+        a.into_string()
+            .map_err(|a| format!("{a:?} isn't valid UTF-8"))
+    });
+    while let Some(arg) = args.next() {
+        match arg?.as_str() {
+            "--message" => launch.message = Some(args.next().ok_or("--message needs a text")??),
+            "--code" => {
+                let name = args.next().ok_or("--code needs a code name")??;
+                let pipeline =
+                    codes::pipeline_code(&name).ok_or_else(|| format!("Unknown code {name:?}"))?;
+                launch.code = Some(pipeline);
+            }
+            "--prob" => {
+                let p = args.next().ok_or("--prob needs a probability")??;
+                let probability = p
+                    .parse()
+                    .ok()
+                    .filter(|p| (0.0..=1.0).contains(p))
+                    .ok_or_else(|| format!("{p:?} isn't a probability from 0 to 1"))?;
+                launch.probability = Some(probability);
+            }
+            "--seed" => {
+                let n = args.next().ok_or("--seed needs a number")??;
+                launch.seed = Some(
+                    manifest::parse_seed(&n)
+                        .ok_or_else(|| format!("{n:?} isn't a seed from 0 to {}", i64::MAX))?,
+                );
+            }
+            arg if arg.starts_with("--") => return Err(format!("Unknown option {arg:?}")),
+            path if launch.file.is_none() => launch.file = Some(PathBuf::from(path)),
+            path => return Err(format!("Only one file can be opened, not {path:?} too")),
+        }
+    }
+    Ok(launch)
+}
+
+/// Runs the command line, returning the process exit code.
+pub fn run(args: Vec<OsString>) -> i32 {
+    let options = match parse(args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return EXIT_CLEAN;
+        }
+        Err(e) => {
+            eprintln!("hamming-gui: {e}\n\n{USAGE}");
+            return EXIT_USAGE;
+        }
+    };
+    if let Mode::Differential(trials) = options.mode {
+        return run_differential(&options, trials);
+    }
+    let start = Instant::now();
+    let mut stats = Stats {
+        mode: options.mode.name(),
+        code: options.info.name,
+        ..Default::default()
+    };
+    let result = open(&options).and_then(|(input, output)| match &options.mode {
+        Mode::Replay(path) => replay(path, output, &mut stats),
+        Mode::Simulate(probability) => simulate(&options, *probability, input, output, &mut stats),
+        Mode::Encode | Mode::Decode | Mode::Differential(_) => {
+            filter(&options, input, output, &mut stats)
+        }
+    });
+    stats.total_ms = millis(start.elapsed());
+    if let Err(e) = &result {
+        eprintln!("hamming-gui: {}", e.message());
+        stats.error = Some(e.message().to_string());
+    }
+    if matches!(options.mode, Mode::Simulate(_)) && result.is_ok() && !options.json_stats {
+        eprintln!("{}", summary(&stats));
+    }
+    if options.json_stats {
+        match serde_json::to_string(&stats) {
+            Ok(json) => eprintln!("{json}"),
+            Err(e) => eprintln!("hamming-gui: {e}"),
+        }
+    }
+    match result {
+        Err(Failure::Io(_)) => EXIT_IO,
+        Err(Failure::Codec(_)) => EXIT_UNCORRECTABLE,
+        Ok(()) if stats.failed > 0 || stats.matches_input == Some(false) => EXIT_UNCORRECTABLE,
+        Ok(()) if stats.corrected > 0 => EXIT_CORRECTED,
+        Ok(()) => EXIT_CLEAN,
+    }
+}
+
+/// Reads the options, or `None` if help was asked for.
+fn parse(args: Vec<OsString>) -> Result<Option<Options>, String> {
+    let mut mode = None;
+    let (default_info, default_code) = codes::pipeline_code(codes::EH16_11.name).unwrap();
+    let (mut info, mut code) = (*default_info, default_code);
+    let mut json_stats = false;
+    let mut seed = None;
+    let (mut input, mut output) = (None, None);
+    let mut args = args.into_iter().map(|a| {
+        a.into_string()
+            .map_err(|a| format!("{a:?} isn't valid UTF-8"))
+    });
+    while let Some(arg) = args.next() {
+        match arg?.as_str() {
+            "--help" | "-h" => return Ok(None),
+            "--encode" => mode = Some(Mode::Encode),
+            "--decode" => mode = Some(Mode::Decode),
+            "--json-stats" => json_stats = true,
+            "--replay" => {
+                let path = args.next().ok_or("--replay needs a manifest")??;
+                mode = Some(Mode::Replay(PathBuf::from(path)));
+            }
+            "--differential" => {
+                let count = args.next().ok_or("--differential needs a count")??;
+                let count = count
+                    .parse()
+                    .map_err(|_| format!("{count:?} isn't a count"))?;
+                mode = Some(Mode::Differential(count));
+            }
+            "--simulate" => {
+                let p = args.next().ok_or("--simulate needs a probability")??;
+                let probability = p
+                    .parse()
+                    .ok()
+                    .filter(|p| (0.0..=1.0).contains(p))
+                    .ok_or_else(|| format!("{p:?} isn't a probability from 0 to 1"))?;
+                mode = Some(Mode::Simulate(probability));
+            }
+            "--input" => {
+                let path = args.next().ok_or("--input needs a file")??;
+                input = Some(PathBuf::from(path));
+            }
+            "--output" => {
+                let path = args.next().ok_or("--output needs a file")??;
+                output = Some(PathBuf::from(path));
+            }
+            "--seed" => {
+                let n = args.next().ok_or("--seed needs a number")??;
+                seed = Some(
+                    manifest::parse_seed(&n)
+                        .ok_or_else(|| format!("{n:?} isn't a seed from 0 to {}", i64::MAX))?,
+                );
+            }
+            "--code" => {
+                let name = args.next().ok_or("--code needs a code name")??;
+                let (i, c) =
+                    codes::pipeline_code(&name).ok_or_else(|| format!("Unknown code {name:?}"))?;
+                (info, code) = (*i, c);
+            }
+            arg => return Err(format!("Unknown option {arg:?}")),
+        }
+    }
+    let mode = mode.ok_or("Give --encode, --decode, --simulate, --replay, or --differential")?;
+    Ok(Some(Options {
+        mode,
+        info,
+        code,
+        json_stats,
+        seed,
+        input,
+        output,
+    }))
+}
+
+type Streams = (Box<dyn Read>, Box<dyn Write>);
+
+/// The input and output of a run: the files given, or standard input and output.
+fn open(options: &Options) -> Result<Streams, Failure> {
+    let failure = |path: &Path, e: io::Error| Failure::Io(format!("{}: {e}", path.display()));
+    let input: Box<dyn Read> = match &options.input {
+        Some(path) => Box::new(File::open(path).map_err(|e| failure(path, e))?),
+        None => Box::new(io::stdin().lock()),
+    };
+    let output: Box<dyn Write> = match &options.output {
+        Some(path) => Box::new(File::create(path).map_err(|e| failure(path, e))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    Ok((input, output))
+}
+
+/// Fills `buf` from `input`, stopping short only at the end of the input. Returns how much was
+/// read.
+fn read_chunk(input: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Counts the codewords of a received chunk that need correcting, and those that can't be.
+fn count_errors(info: &CodeInfo, received: &[u8], stats: &mut Stats) {
+    for j in 0..analysis::codeword_count(info, received) {
+        if let Some(word) = analysis::codeword(info, received, j) {
+            match analysis::check(info, &word).distance {
+                0 => {}
+                1 => stats.corrected += 1,
+                _ => stats.failed += 1,
+            }
+        }
+    }
+}
+
+/// Encodes or decodes `input` into `output` in chunks of whole codewords, so memory use doesn't
+/// grow with the input.
+fn filter(
+    options: &Options,
+    mut input: impl Read,
+    output: impl Write,
+    stats: &mut Stats,
+) -> Result<(), Failure> {
+    let io = |e: io::Error| Failure::Io(e.to_string());
+    // A chunk of whole codewords is a whole number of bytes both before and after encoding.
+    let chunk = match options.mode {
+        Mode::Encode => options.info.k * CHUNK_CODEWORDS / 8,
+        _ => options.info.n * CHUNK_CODEWORDS / 8,
+    };
+    let mut buf = vec![0; chunk];
+    let mut output = BufWriter::new(output);
+    loop {
+        let len = read_chunk(&mut input, &mut buf).map_err(io)?;
+        if len == 0 {
+            break;
+        }
+        stats.bytes_in += len;
+        let start = Instant::now();
+        let out = match options.mode {
+            Mode::Encode => encode(&buf[..len], options.code),
+            _ => decode(&buf[..len], options.code),
+        };
+        stats.codec_ms += millis(start.elapsed());
+        if options.mode == Mode::Decode {
+            stats.blocks += analysis::codeword_count(&options.info, &buf[..len]);
+            count_errors(&options.info, &buf[..len], stats);
+        }
+        let out = out.map_err(|e| Failure::Codec(format!("{e:?}")))?;
+        if options.mode == Mode::Encode {
+            stats.blocks += analysis::codeword_count(&options.info, &out);
+        }
+        stats.bytes_out += out.len();
+        output.write_all(&out).map_err(io)?;
+        if len < chunk {
+            break;
+        }
+    }
+    output.flush().map_err(io)
+}
+
+/// Runs a manifest: encodes its input, flips the bits it determines, and decodes the result into
+/// `output`.
+fn replay(path: &Path, output: impl Write, stats: &mut Stats) -> Result<(), Failure> {
+    let manifest = Manifest::read(path).map_err(Failure::Io)?;
+    let (info, code) = codes::pipeline_code(&manifest.code)
+        .ok_or_else(|| Failure::Io(format!("Unknown code {:?}", manifest.code)))?;
+    stats.code = info.name;
+    let input = manifest.read_input(path).map_err(Failure::Io)?;
+    transmit(
+        info,
+        code,
+        &input,
+        |len| manifest.error_pattern(len),
+        output,
+        stats,
+    )
+}
+
+/// Encodes `input`, flips each bit with chance `probability`, and decodes the result into
+/// `output`. The flips are drawn from the seed given, or a new one, which is reported so that
+/// the run can be repeated.
+fn simulate(
+    options: &Options,
+    probability: f64,
+    mut input: impl Read,
+    output: impl Write,
+    stats: &mut Stats,
+) -> Result<(), Failure> {
+    let mut data = Vec::new();
+    input
+        .read_to_end(&mut data)
+        .map_err(|e| Failure::Io(e.to_string()))?;
+    let seed = options
+        .seed
+        .unwrap_or_else(|| manifest::new_seed(&mut thread_rng()));
+    stats.probability = Some(probability);
+    stats.seed = Some(seed);
+    let error = |len| manifest::error_pattern(seed, len, probability);
+    transmit(&options.info, options.code, &data, error, output, stats)
+}
+
+/// Encodes `input`, adds the error `error` gives for the length of the encoding, and decodes the
+/// result into `output`.
+fn transmit(
+    info: &CodeInfo,
+    code: &Code,
+    input: &[u8],
+    error: impl FnOnce(usize) -> Vec<u8>,
+    mut output: impl Write,
+    stats: &mut Stats,
+) -> Result<(), Failure> {
+    stats.bytes_in = input.len();
+    let start = Instant::now();
+    let encoded = encode(input, code).map_err(|e| Failure::Codec(format!("{e:?}")))?;
+    let error = error(encoded.len());
+    let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+        .map(|(b, e)| b ^ e)
+        .collect();
+    let decoded = decode(&received, code);
+    stats.codec_ms = millis(start.elapsed());
+
+    stats.errors_injected = error.iter().map(|b| b.count_ones() as usize).sum();
+    stats.blocks = analysis::codeword_count(info, &received);
+    count_errors(info, &received, stats);
+    let decoded = decoded.map_err(|e| Failure::Codec(format!("{e:?}")))?;
+    stats.bytes_out = decoded.len();
+    stats.matches_input = Some(decoded == input);
+    output
+        .write_all(&decoded)
+        .and_then(|()| output.flush())
+        .map_err(|e| Failure::Io(e.to_string()))
+}
+
+/// What came of a simulation, for people rather than scripts.
+fn summary(stats: &Stats) -> String {
+    let outcome = match stats.matches_input {
+        Some(true) => "the output matches the input",
+        _ => "the output differs from the input",
+    };
+    format!(
+        "{} at probability {} with --seed {}: {} bits flipped, {} of {} codewords corrected, {} \
+         uncorrectable; {outcome}",
+        stats.code,
+        stats.probability.unwrap_or(0.0),
+        stats.seed.unwrap_or(0),
+        stats.errors_injected,
+        stats.corrected,
+        stats.blocks,
+        stats.failed,
+    )
+}
+
+/// Cross-checks the code against the reference model, listing every disagreement.
+fn run_differential(options: &Options, trials: usize) -> i32 {
+    let seed = options
+        .seed
+        .unwrap_or_else(|| manifest::new_seed(&mut thread_rng()));
+    println!(
+        "Cross-checking {} on {trials} random inputs with --seed {seed}",
+        options.info.name
+    );
+    let found = differential::run(options.code, &options.info, trials, seed);
+    for d in &found {
+        println!(
+            "trial {}, {}: {}\n  input {:02x?}",
+            d.trial, d.check, d.detail, d.input
+        );
+    }
+    if found.is_empty() {
+        println!("No disagreements.");
+        EXIT_CLEAN
+    } else {
+        println!("{} disagreements.", found.len());
+        EXIT_UNCORRECTABLE
+    }
+}
@@ -0,0 +1,98 @@
+use hamming::Code;
+
+/// Parameters of a Hamming code, with its bits numbered the textbook way: in an extended code,
+/// position 0 is the overall parity bit; otherwise positions start at 1. Parity bits sit at the
+/// power-of-two positions. Codewords are packed back to back into the encoded bytes, least
+/// significant bit first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CodeInfo {
+    pub name: &'static str,
+    pub n: usize,
+    pub k: usize,
+    pub extended: bool,
+}
+
+pub const H7_4: CodeInfo = CodeInfo {
+    name: "H7_4",
+    n: 7,
+    k: 4,
+    extended: false,
+};
+
+pub const EH8_4: CodeInfo = CodeInfo {
+    name: "EH8_4",
+    n: 8,
+    k: 4,
+    extended: true,
+};
+
+pub const EH16_11: CodeInfo = CodeInfo {
+    name: "EH16_11",
+    n: 16,
+    k: 11,
+    extended: true,
+};
+
+/// The codes the pipeline can run, with their parameters.
+pub static PIPELINE: [(CodeInfo, Code); 3] = [
+    (H7_4, hamming::code::H7_4),
+    (EH8_4, hamming::code::EH8_4),
+    (EH16_11, hamming::code::EH16_11),
+];
+
+/// The pipeline code called `name`, ignoring case.
+pub fn pipeline_code(name: &str) -> Option<&'static (CodeInfo, Code)> {
+    PIPELINE
+        .iter()
+        .find(|(info, _)| info.name.eq_ignore_ascii_case(name))
+}
+
+impl CodeInfo {
+    /// The share of each codeword that is message.
+    pub fn rate(&self) -> f64 {
+        self.k as f64 / self.n as f64
+    }
+
+    /// The fewest bit flips that can turn one codeword into another.
+    pub fn min_distance(&self) -> usize {
+        if self.extended {
+            4
+        } else {
+            3
+        }
+    }
+
+    /// The textbook position number of bit `i` (counted from 0) of a codeword.
+    pub fn position(&self, i: usize) -> usize {
+        if self.extended {
+            i
+        } else {
+            i + 1
+        }
+    }
+
+    /// Whether bit `i` of a codeword is a parity bit.
+    pub fn is_parity(&self, i: usize) -> bool {
+        let p = self.position(i);
+        p == 0 || p.is_power_of_two()
+    }
+
+    /// The codeword and bit within it that bit `bit` of the encoded stream belongs to.
+    pub fn locate(&self, bit: usize) -> (usize, usize) {
+        (bit / self.n, bit % self.n)
+    }
+
+    /// Whether parity bit `parity` of a codeword checks bit `i` of the same codeword.
+    pub fn covers(&self, parity: usize, i: usize) -> bool {
+        match self.position(parity) {
+            // The overall parity bit of an extended code checks everything.
+            0 => true,
+            p => p.is_power_of_two() && self.position(i) & p != 0,
+        }
+    }
+
+    /// Every bit of a codeword that parity bit `parity` checks.
+    pub fn coverage(&self, parity: usize) -> Vec<usize> {
+        (0..self.n).filter(|&i| self.covers(parity, i)).collect()
+    }
+}
@@ -0,0 +1,169 @@
+use crate::codes::CodeInfo;
+use egui::{
+    plot::{Legend, Line, Plot, PlotPoints},
+    DragValue, Grid,
+};
+use std::f64::consts::SQRT_2;
+
+/// The range of Eb/N0 searched and plotted, in dB.
+const MIN_DB: f64 = -2.0;
+const MAX_DB: f64 = 16.0;
+
+/// The complementary error function, to within 1.2e-7 (Numerical Recipes' `erfcc`).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |acc, c| acc * t + c);
+    let r = t * (-z * z + poly).exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// The chance that a standard normal variable exceeds `x`.
+fn q(x: f64) -> f64 {
+    0.5 * erfc(x / SQRT_2)
+}
+
+fn from_db(db: f64) -> f64 {
+    10f64.powf(db / 10.0)
+}
+
+/// The bit error rate of uncoded BPSK at `db` dB Eb/N0.
+fn uncoded_ber(db: f64) -> f64 {
+    q((2.0 * from_db(db)).sqrt())
+}
+
+/// The bit error rate after decoding, with BPSK and hard decisions, at `db` dB Eb/N0. Each
+/// channel bit carries only `k / n` of a message bit's energy. A codeword with `i > 1` flips is
+/// taken to come out with about `i` of its `n` bits wrong, the usual estimate for a code that
+/// corrects one error.
+fn coded_ber(info: &CodeInfo, db: f64) -> f64 {
+    let p = q((2.0 * info.rate() * from_db(db)).sqrt());
+    let n = info.n;
+    // `C(n, i) p^i (1-p)^(n-i)`, built up term by term.
+    let mut choose = 1.0;
+    let mut ber = 0.0;
+    for i in 1..=n {
+        choose *= (n - i + 1) as f64 / i as f64;
+        if i > 1 {
+            let flips = choose * p.powi(i as i32) * (1.0 - p).powi((n - i) as i32);
+            ber += i as f64 * flips;
+        }
+    }
+    ber / n as f64
+}
+
+/// The Eb/N0, in dB, at which `ber` falls to `target`, found by bisection. `None` if it doesn't
+/// within the range searched.
+fn required_db(ber: impl Fn(f64) -> f64, target: f64) -> Option<f64> {
+    if ber(MAX_DB) > target {
+        return None;
+    }
+    let (mut low, mut high) = (MIN_DB, MAX_DB);
+    for _ in 0..60 {
+        let mid = (low + high) / 2.0;
+        if ber(mid) > target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(high)
+}
+
+/// The coding gain of the current code over an additive white Gaussian noise channel: how much
+/// less signal it needs than sending the message uncoded, for the same bit error rate.
+pub struct CodingGain {
+    pub open: bool,
+    /// The target bit error rate is `10^-exponent`.
+    exponent: u32,
+}
+
+impl CodingGain {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            exponent: 5,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, info: &CodeInfo) {
+        let mut open = self.open;
+        egui::Window::new("Coding Gain")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo) {
+        ui.small(
+            "BPSK over an additive white Gaussian noise channel, with hard decisions. The curves \
+             are worked out rather than simulated.",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Target BER: 1e-");
+            ui.add(DragValue::new(&mut self.exponent).clamp_range(1..=12));
+        });
+        let target = 10f64.powi(-(self.exponent as i32));
+        let uncoded = required_db(uncoded_ber, target);
+        let coded = required_db(|db| coded_ber(info, db), target);
+        let format =
+            |db: Option<f64>| db.map_or("out of range".to_string(), |db| format!("{db:.2} dB"));
+        Grid::new("coding_gain").show(ui, |ui| {
+            ui.label("Uncoded Eb/N0");
+            ui.label(format(uncoded));
+            ui.end_row();
+            ui.label(format!("{} Eb/N0", info.name));
+            ui.label(format(coded));
+            ui.end_row();
+            ui.label("Coding gain");
+            match (uncoded, coded) {
+                (Some(u), Some(c)) => ui.strong(format!("{:.2} dB", u - c)),
+                _ => ui.label("out of range"),
+            };
+            ui.end_row();
+        });
+
+        // Plotted as log10 of the BER, which egui's plots can't do for themselves.
+        let curve = |ber: &dyn Fn(f64) -> f64| {
+            let points: Vec<[f64; 2]> = (0..=((MAX_DB - MIN_DB) * 4.0) as usize)
+                .map(|i| MIN_DB + i as f64 / 4.0)
+                .map(|db| [db, ber(db).max(1e-15).log10()])
+                .collect();
+            PlotPoints::new(points)
+        };
+        Plot::new("coding_gain_curves")
+            .height(200.0)
+            .legend(Legend::default())
+            .include_y(-(self.exponent as f64) - 1.0)
+            .show(ui, |plot| {
+                plot.line(Line::new(curve(&uncoded_ber)).name("Uncoded"));
+                plot.line(Line::new(curve(&|db| coded_ber(info, db))).name(info.name));
+                plot.line(
+                    Line::new(PlotPoints::new(vec![
+                        [MIN_DB, -(self.exponent as f64)],
+                        [MAX_DB, -(self.exponent as f64)],
+                    ]))
+                    .name("Target"),
+                );
+            });
+        ui.small("x: Eb/N0 (dB), y: log10 BER");
+    }
+}
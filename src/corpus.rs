@@ -0,0 +1,180 @@
+use crate::{codes::CodeInfo, decoders};
+use egui::{Color32, Grid};
+use hamming::{decode, encode, Code};
+use std::{
+    any::Any,
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// Where crashing inputs are looked for when `HAMMING_GUI_CORPUS` isn't set: where cargo-fuzz
+/// leaves them.
+const DEFAULT_DIR: &str = "fuzz/artifacts";
+
+/// An input that still trips up the pipeline.
+pub struct Failure {
+    pub path: PathBuf,
+    pub stage: &'static str,
+    pub problem: String,
+}
+
+pub struct Report {
+    pub dir: PathBuf,
+    pub inputs: usize,
+    pub failures: Vec<Failure>,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Every file under `dir`, however deeply nested.
+fn files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            files(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Runs one input through the pipeline both as a message and as a received stream.
+fn check(code: &Code, info: &CodeInfo, input: &[u8]) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+    let round_trip = panic::catch_unwind(AssertUnwindSafe(|| {
+        let encoded = encode(input, code).map_err(|e| format!("{e:?}"))?;
+        match decode(&encoded, code) {
+            Ok(decoded) if decoded == input => Ok(()),
+            Ok(_) => Err("decoded to something else".to_string()),
+            Err(e) => Err(format!("{e:?}")),
+        }
+    }));
+    match round_trip {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => problems.push(("round trip", e)),
+        Err(payload) => problems.push(("round trip", panic_message(payload))),
+    }
+    let decoding = panic::catch_unwind(AssertUnwindSafe(|| {
+        decoders::run_all(code, info, input);
+    }));
+    if let Err(payload) = decoding {
+        problems.push(("decode", panic_message(payload)));
+    }
+    problems
+}
+
+/// Runs every input in the corpus directory through the pipeline.
+pub fn run(code: &Code, info: &CodeInfo) -> Report {
+    let dir =
+        env::var_os("HAMMING_GUI_CORPUS").map_or_else(|| PathBuf::from(DEFAULT_DIR), PathBuf::from);
+    let mut paths = Vec::new();
+    files(&dir, &mut paths);
+    paths.sort();
+    let mut failures = Vec::new();
+    for path in &paths {
+        let input = match fs::read(path) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(Failure {
+                    path: path.clone(),
+                    stage: "read",
+                    problem: e.to_string(),
+                });
+                continue;
+            }
+        };
+        for (stage, problem) in check(code, info, &input) {
+            failures.push(Failure {
+                path: path.clone(),
+                stage,
+                problem,
+            });
+        }
+    }
+    Report {
+        dir,
+        inputs: paths.len(),
+        failures,
+    }
+}
+
+/// The results of replaying the fuzz corpus at startup, which only happens in debug builds.
+pub struct CorpusWindow {
+    pub open: bool,
+    report: Option<Report>,
+}
+
+impl CorpusWindow {
+    pub fn new(code: &Code, info: &CodeInfo) -> Self {
+        let report = cfg!(debug_assertions).then(|| run(code, info));
+        Self {
+            open: report.as_ref().is_some_and(|r| !r.failures.is_empty()),
+            report,
+        }
+    }
+
+    /// Whether there is anything to show, i.e. this is a debug build.
+    pub fn enabled(&self) -> bool {
+        self.report.is_some()
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code, info: &CodeInfo) {
+        let mut open = self.open;
+        egui::Window::new("Regression Corpus")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, code, info));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, code: &Code, info: &CodeInfo) {
+        let report = match &self.report {
+            Some(report) => report,
+            None => return,
+        };
+        ui.label(format!(
+            "{} inputs from {}",
+            report.inputs,
+            report.dir.display()
+        ));
+        if report.failures.is_empty() {
+            ui.colored_label(Color32::GREEN, "No regressions.");
+        } else {
+            ui.colored_label(
+                Color32::RED,
+                format!("{} regressions.", report.failures.len()),
+            );
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    Grid::new("corpus_failures").striped(true).show(ui, |ui| {
+                        ui.strong("Input");
+                        ui.strong("Stage");
+                        ui.strong("Problem");
+                        ui.end_row();
+                        for f in &report.failures {
+                            let name = f.path.strip_prefix(&report.dir).unwrap_or(&f.path);
+                            ui.label(name.display().to_string());
+                            ui.label(f.stage);
+                            ui.label(&f.problem);
+                            ui.end_row();
+                        }
+                    });
+                });
+        }
+        if ui.button("Run again").clicked() {
+            self.report = Some(run(code, info));
+        }
+    }
+}
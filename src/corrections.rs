@@ -0,0 +1,133 @@
+use crate::{analysis, codes::CodeInfo, decoders::Outcome};
+use hamming::{encode, Code};
+
+/// What a decoder did to the received stream, bit by bit.
+#[derive(Default)]
+pub struct Corrections {
+    /// The bits the decoder flipped.
+    pub flipped: Vec<u8>,
+    /// Codewords the decoder found errors in but couldn't correct.
+    pub uncorrectable: Vec<usize>,
+}
+
+impl Corrections {
+    /// Works out the corrections behind `outcome`. When the decoder gave an answer, the bits it
+    /// flipped are the difference between what was received and that answer encoded again. When
+    /// it gave up, each codeword is checked on its own instead: a single error is located by its
+    /// syndrome, and anything more is uncorrectable.
+    pub fn new(code: &Code, info: &CodeInfo, received: &[u8], outcome: &Outcome) -> Self {
+        if let Some(sent) = outcome.decoded.as_ref().and_then(|d| encode(d, code).ok()) {
+            return Self {
+                flipped: Iterator::zip(received.iter(), sent.iter())
+                    .map(|(r, s)| r ^ s)
+                    .collect(),
+                uncorrectable: Vec::new(),
+            };
+        }
+        Self::by_codeword(info, received)
+    }
+
+    /// The corrections of a decoder that takes each codeword on its own: a single error is
+    /// located by its syndrome, and anything more is uncorrectable.
+    pub fn by_codeword(info: &CodeInfo, received: &[u8]) -> Self {
+        let mut flipped = vec![0; received.len()];
+        let mut uncorrectable = Vec::new();
+        for j in 0..analysis::codeword_count(info, received) {
+            let word = match analysis::codeword(info, received, j) {
+                Some(word) => word,
+                None => continue,
+            };
+            let check = analysis::check(info, &word);
+            match (check.distance, check.flip) {
+                (0, _) => {}
+                (1, Some(i)) => analysis::set_bit(&mut flipped, j * info.n + i, true),
+                _ => uncorrectable.push(j),
+            }
+        }
+        Self {
+            flipped,
+            uncorrectable,
+        }
+    }
+
+    pub fn is_flipped(&self, bit: usize) -> bool {
+        analysis::get_bit(&self.flipped, bit)
+    }
+}
+
+/// How one codeword came through.
+pub struct Tally {
+    /// Bits the channel flipped.
+    pub flipped: usize,
+    /// Of those, the bits the decoder flipped back.
+    pub corrected: usize,
+    /// Bits still wrong after decoding, counting any the decoder flipped that were never in
+    /// error.
+    pub residual: usize,
+}
+
+/// Tallies each codeword of `error` against the bits the decoder flipped.
+pub fn tally(info: &CodeInfo, error: &[u8], corrections: &Corrections) -> Vec<Tally> {
+    (0..analysis::codeword_count(info, error))
+        .map(|j| {
+            let mut tally = Tally {
+                flipped: 0,
+                corrected: 0,
+                residual: 0,
+            };
+            for bit in j * info.n..(j + 1) * info.n {
+                let (e, c) = (analysis::get_bit(error, bit), corrections.is_flipped(bit));
+                tally.flipped += usize::from(e);
+                tally.corrected += usize::from(e && c);
+                tally.residual += usize::from(e != c);
+            }
+            tally
+        })
+        .collect()
+}
+
+/// A row for each codeword of `error`: how many bits were flipped, corrected, and left wrong,
+/// and whether it decoded right. `probability` is the error probability, if the error was
+/// randomized.
+pub fn codewords_csv(
+    info: &CodeInfo,
+    error: &[u8],
+    corrections: &Corrections,
+    probability: Option<f64>,
+) -> String {
+    let probability = probability.map_or_else(String::new, |p| p.to_string());
+    let mut csv =
+        "probability,codeword,bits_flipped,bits_corrected,residual_errors,success\n".to_string();
+    for (j, t) in tally(info, error, corrections).iter().enumerate() {
+        csv.push_str(&format!(
+            "{probability},{j},{},{},{},{}\n",
+            t.flipped,
+            t.corrected,
+            t.residual,
+            t.residual == 0
+        ));
+    }
+    csv
+}
+
+/// How the corrections compare with the error that was actually injected.
+#[derive(Default)]
+pub struct Comparison {
+    /// Injected errors the decoder flipped back.
+    pub fixed: usize,
+    /// Injected errors the decoder left alone.
+    pub missed: usize,
+    /// Bits the decoder flipped that were never in error.
+    pub spurious: usize,
+}
+
+pub fn compare(error: &[u8], corrections: &Corrections) -> Comparison {
+    let mut comparison = Comparison::default();
+    for (i, &e) in error.iter().enumerate() {
+        let c = corrections.flipped.get(i).copied().unwrap_or(0);
+        comparison.fixed += (e & c).count_ones() as usize;
+        comparison.missed += (e & !c).count_ones() as usize;
+        comparison.spurious += (!e & c).count_ones() as usize;
+    }
+    comparison
+}
@@ -0,0 +1,223 @@
+use crate::{
+    analysis::{self, MAX_ENUMERATED_K},
+    codes::CodeInfo,
+};
+use hamming::{decode, Code};
+use instant::Instant;
+use std::time::Duration;
+
+/// A way of turning the received stream back into the message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// The `hamming` crate's own decoder.
+    Library,
+    /// Bounded-distance decoding: a single error is located by its syndrome and flipped, and
+    /// anything the syndrome can't pin down is rejected.
+    Syndrome,
+    /// Every block replaced by the nearest codeword, found by trying them all. Never rejects a
+    /// block; ties go to the first codeword tried.
+    MaximumLikelihood,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 3] = [
+        Algorithm::Library,
+        Algorithm::Syndrome,
+        Algorithm::MaximumLikelihood,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Library => "Library",
+            Algorithm::Syndrome => "Syndrome (bounded distance)",
+            Algorithm::MaximumLikelihood => "Maximum likelihood",
+        }
+    }
+
+    /// Whether the algorithm can be used with `info`.
+    pub fn supports(self, info: &CodeInfo) -> bool {
+        match self {
+            Algorithm::Library | Algorithm::Syndrome => true,
+            Algorithm::MaximumLikelihood => info.k <= MAX_ENUMERATED_K,
+        }
+    }
+
+    /// The algorithms that can be used with `info`.
+    pub fn available(info: &CodeInfo) -> Vec<Algorithm> {
+        Self::ALL.into_iter().filter(|a| a.supports(info)).collect()
+    }
+}
+
+/// What one algorithm made of the received stream.
+pub struct Outcome {
+    pub algorithm: Algorithm,
+    /// The decoded bytes, or `None` if the algorithm gave up.
+    pub decoded: Option<Vec<u8>>,
+    pub elapsed: Duration,
+    /// How long each codeword took, up to the first one rejected. Empty for the library, which
+    /// decodes the stream in one go.
+    pub block_times: Vec<Duration>,
+    /// What could still be recovered codeword by codeword, if the algorithm gave up.
+    pub partial: Option<Partial>,
+}
+
+/// A stream that didn't decode as a whole, decoded codeword by codeword instead.
+pub struct Partial {
+    /// The message in runs of bytes, each with whether it was recovered. Bytes that weren't are
+    /// shown as U+FFFD, one each.
+    pub runs: Vec<(String, bool)>,
+    /// The codewords that couldn't be corrected.
+    pub failed: Vec<usize>,
+    pub codewords: usize,
+}
+
+impl Outcome {
+    /// The `p`th percentile (0 to 100) of the block times, by nearest rank.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut times = self.block_times.clone();
+        times.sort();
+        let rank = (p / 100.0 * times.len() as f64).ceil() as usize;
+        times.get(rank.saturating_sub(1)).copied()
+    }
+}
+
+/// Decodes `received` with `algorithm`, timing it.
+pub fn run(algorithm: Algorithm, code: &Code, info: &CodeInfo, received: &[u8]) -> Outcome {
+    let start = Instant::now();
+    let mut block_times = Vec::new();
+    let decoded = match algorithm {
+        Algorithm::Library => decode(received, code).ok(),
+        // The in-app algorithms only correct codewords; the library still unpacks the data.
+        Algorithm::Syndrome => correct(info, received, &mut block_times, |word| {
+            let check = analysis::check(info, word);
+            match (check.distance, check.flip) {
+                (0, _) => Some(word.to_vec()),
+                (1, Some(i)) => {
+                    let mut word = word.to_vec();
+                    word[i] ^= true;
+                    Some(word)
+                }
+                _ => None,
+            }
+        })
+        .and_then(|corrected| decode(&corrected, code).ok()),
+        Algorithm::MaximumLikelihood => {
+            let codewords: Vec<u32> = (0..1usize << info.k)
+                .map(|m| {
+                    let data: Vec<bool> = (0..info.k).map(|b| (m >> b) & 1 == 1).collect();
+                    analysis::to_mask(&analysis::encode_word(info, &data))
+                })
+                .collect();
+            correct(info, received, &mut block_times, |word| {
+                let mask = analysis::to_mask(word);
+                codewords
+                    .iter()
+                    .min_by_key(|&&c| (c ^ mask).count_ones())
+                    .map(|&c| analysis::from_mask(c, info.n))
+            })
+            .and_then(|corrected| decode(&corrected, code).ok())
+        }
+    };
+    let elapsed = start.elapsed();
+    let partial = match decoded {
+        Some(_) => None,
+        None => partial(code, info, received),
+    };
+    Outcome {
+        algorithm,
+        decoded,
+        elapsed,
+        block_times,
+        partial,
+    }
+}
+
+/// Decodes each codeword of `received` on its own: a single error is corrected as by the
+/// syndrome, and a codeword with more is zeroed so that the rest of the stream still decodes.
+/// Returns the decoded bytes and the codewords that were zeroed, or `None` if even that doesn't
+/// decode.
+pub fn decode_blocks(
+    code: &Code,
+    info: &CodeInfo,
+    received: &[u8],
+) -> Option<(Vec<u8>, Vec<usize>)> {
+    let codewords = analysis::codeword_count(info, received);
+    let mut patched = received.to_vec();
+    let mut failed = Vec::new();
+    for j in 0..codewords {
+        let word = analysis::codeword(info, received, j)?;
+        let check = analysis::check(info, &word);
+        match (check.distance, check.flip) {
+            (0, _) => {}
+            (1, Some(i)) => analysis::set_bit(&mut patched, j * info.n + i, !word[i]),
+            _ => {
+                failed.push(j);
+                for i in 0..info.n {
+                    analysis::set_bit(&mut patched, j * info.n + i, false);
+                }
+            }
+        }
+    }
+    Some((decode(&patched, code).ok()?, failed))
+}
+
+/// What `decode_blocks` made of `received`, for showing.
+fn partial(code: &Code, info: &CodeInfo, received: &[u8]) -> Option<Partial> {
+    let (bytes, failed) = decode_blocks(code, info, received)?;
+
+    // The data bits of the codewords are packed back to back, so bit `b` of the message comes
+    // from codeword `b / k`.
+    let recovered = |byte: usize| {
+        (byte * 8..byte * 8 + 8).all(|b| failed.binary_search(&(b / info.k)).is_err())
+    };
+    let mut runs: Vec<(String, bool)> = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let ok = recovered(start);
+        let end = (start..bytes.len())
+            .find(|&i| recovered(i) != ok)
+            .unwrap_or(bytes.len());
+        let text = if ok {
+            String::from_utf8_lossy(&bytes[start..end]).into_owned()
+        } else {
+            "\u{FFFD}".repeat(end - start)
+        };
+        runs.push((text, ok));
+        start = end;
+    }
+    Some(Partial {
+        runs,
+        failed,
+        codewords: analysis::codeword_count(info, received),
+    })
+}
+
+/// Runs every algorithm that supports `info` over `received`.
+pub fn run_all(code: &Code, info: &CodeInfo, received: &[u8]) -> Vec<Outcome> {
+    Algorithm::available(info)
+        .into_iter()
+        .map(|a| run(a, code, info, received))
+        .collect()
+}
+
+/// Replaces every whole codeword of `received` with `fix(codeword)`, leaving any trailing bits
+/// alone. Returns `None` if `fix` rejects any codeword. The time spent on each codeword is
+/// pushed to `times`.
+fn correct(
+    info: &CodeInfo,
+    received: &[u8],
+    times: &mut Vec<Duration>,
+    mut fix: impl FnMut(&[bool]) -> Option<Vec<bool>>,
+) -> Option<Vec<u8>> {
+    let mut corrected = received.to_vec();
+    for j in 0..analysis::codeword_count(info, received) {
+        let start = Instant::now();
+        let word = analysis::codeword(info, received, j)?;
+        let fixed = fix(&word);
+        times.push(start.elapsed());
+        for (i, b) in fixed?.into_iter().enumerate() {
+            analysis::set_bit(&mut corrected, j * info.n + i, b);
+        }
+    }
+    Some(corrected)
+}
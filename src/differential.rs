@@ -0,0 +1,164 @@
+use crate::{analysis, codes::CodeInfo, reference::Reference};
+use hamming::{decode, encode, Code};
+use rand::{rngs::StdRng, seq::index, Rng, SeedableRng};
+
+/// The longest random input tried, in bytes.
+const MAX_INPUT: usize = 64;
+
+/// A case where the `hamming` crate and the reference model disagree.
+pub struct Disagreement {
+    pub trial: usize,
+    pub input: Vec<u8>,
+    pub check: &'static str,
+    pub detail: String,
+}
+
+/// Cross-checks the `hamming` crate against the matrix-based reference on `trials` random
+/// inputs. The checks don't depend on how message bits are laid out in the codewords, only on
+/// the codewords themselves, so any disagreement is a real bug in one or the other.
+pub fn run(code: &Code, info: &CodeInfo, trials: usize, seed: u64) -> Vec<Disagreement> {
+    let reference = Reference::new(info.n, info.extended);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut found = Vec::new();
+    for trial in 0..trials {
+        let len = rng.gen_range(0..=MAX_INPUT);
+        let input: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let mut disagree = |check, detail| {
+            found.push(Disagreement {
+                trial,
+                input: input.clone(),
+                check,
+                detail,
+            })
+        };
+
+        let encoded = match encode(&input, code) {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                disagree("encode", format!("the library failed: {e:?}"));
+                continue;
+            }
+        };
+        let blocks = analysis::codeword_count(info, &encoded);
+        let words: Vec<u32> = (0..blocks)
+            .filter_map(|j| analysis::codeword(info, &encoded, j))
+            .map(|w| analysis::to_mask(&w))
+            .collect();
+        for (j, &word) in words.iter().enumerate() {
+            if reference.syndrome(word) != 0 {
+                disagree(
+                    "parity",
+                    format!("codeword {j} ({word:#x}) fails a parity check"),
+                );
+            } else if reference.encode(reference.data(word)) != word {
+                disagree(
+                    "encode",
+                    format!("codeword {j} ({word:#x}) re-encodes differently"),
+                );
+            }
+        }
+        match decode(&encoded, code) {
+            Ok(decoded) if decoded == input => {}
+            Ok(decoded) => disagree("round trip", format!("decoded to {decoded:02x?}")),
+            Err(e) => disagree("round trip", format!("the library failed: {e:?}")),
+        }
+        if blocks == 0 {
+            continue;
+        }
+
+        // Flip one bit of one codeword: both must correct it.
+        let j = rng.gen_range(0..blocks);
+        let bit = rng.gen_range(0..info.n);
+        let mut received = encoded.clone();
+        analysis::set_bit(
+            &mut received,
+            j * info.n + bit,
+            !analysis::get_bit(&encoded, j * info.n + bit),
+        );
+        if reference.correct(words[j] ^ 1 << bit) != Some(words[j]) {
+            disagree(
+                "single error",
+                format!("the reference missed bit {bit} of codeword {j}"),
+            );
+        }
+        match decode(&received, code) {
+            Ok(decoded) if decoded == input => {}
+            Ok(decoded) => disagree(
+                "single error",
+                format!("bit {bit} of codeword {j} flipped; decoded to {decoded:02x?}"),
+            ),
+            Err(e) => disagree(
+                "single error",
+                format!("bit {bit} of codeword {j} flipped; the library failed: {e:?}"),
+            ),
+        }
+
+        // Flip two: an extended code must notice rather than miscorrect.
+        if info.extended {
+            let flips = index::sample(&mut rng, info.n, 2);
+            let mut received = encoded.clone();
+            let mut word = words[j];
+            for i in flips.iter() {
+                let b = j * info.n + i;
+                analysis::set_bit(&mut received, b, !analysis::get_bit(&encoded, b));
+                word ^= 1 << i;
+            }
+            let (a, b) = (flips.index(0), flips.index(1));
+            if reference.correct(word).is_some() {
+                disagree(
+                    "double error",
+                    format!("the reference corrected bits {a} and {b} of codeword {j}"),
+                );
+            }
+            if let Ok(decoded) = decode(&received, code) {
+                disagree(
+                    "double error",
+                    format!(
+                        "bits {a} and {b} of codeword {j} flipped; decoded to {decoded:02x?} \
+                         without an error"
+                    ),
+                );
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codes::PIPELINE;
+
+    #[test]
+    fn reference_corrects_single_errors() {
+        for (info, _) in &PIPELINE {
+            let reference = Reference::new(info.n, info.extended);
+            for data in 0..1 << info.k {
+                let word = reference.encode(data);
+                assert_eq!(reference.syndrome(word), 0, "{}: {data:#x}", info.name);
+                assert_eq!(reference.data(word), data);
+                for i in 0..info.n {
+                    assert_eq!(reference.correct(word ^ 1 << i), Some(word));
+                    if info.extended {
+                        for j in 0..i {
+                            assert_eq!(reference.correct(word ^ 1 << i ^ 1 << j), None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn library_agrees_with_reference() {
+        for (info, code) in &PIPELINE {
+            let found = run(code, info, 100, 0);
+            if let Some(d) = found.first() {
+                panic!(
+                    "{}: {} check, trial {} ({:02x?}): {}",
+                    info.name, d.check, d.trial, d.input, d.detail
+                );
+            }
+        }
+    }
+}
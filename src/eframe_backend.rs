@@ -0,0 +1,90 @@
+use eframe::glow::{self, HasContext};
+use hamming_gui::{platform, App, Graphics};
+
+/// Runs the app with `eframe`, which tracks egui releases more closely than `egui-miniquad` and
+/// copes better with some platforms, such as fractional scaling on Wayland.
+struct EframeApp {
+    app: App,
+    title: String,
+}
+
+impl eframe::App for EframeApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.app.update();
+        self.app.ui(ctx);
+        let title = self.app.window_title();
+        if title != self.title {
+            set_title(frame, &title);
+            self.title = title;
+        }
+    }
+}
+
+/// Tells `app` which OpenGL driver eframe ended up with, if it draws with OpenGL at all.
+fn report_graphics(app: &mut App, cc: &eframe::CreationContext) {
+    let driver = cc.gl.as_ref().map(|gl| {
+        // eframe has made the context current by the time the app is created.
+        let get = |name| unsafe { gl.get_parameter_string(name) };
+        format!(
+            "{} {}, OpenGL {}",
+            get(glow::VENDOR),
+            get(glow::RENDERER),
+            get(glow::VERSION)
+        )
+    });
+    app.set_graphics(Graphics {
+        backend: "eframe",
+        driver,
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn set_title(frame: &mut eframe::Frame, title: &str) {
+    frame.set_window_title(title);
+}
+
+/// On the web the title is the page's, shown on its tab.
+#[cfg(target_arch = "wasm32")]
+fn set_title(_frame: &mut eframe::Frame, title: &str) {
+    if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+        document.set_title(title);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run(app: App) {
+    let title = app.window_title();
+    let options = eframe::NativeOptions {
+        icon_data: Some(eframe::IconData {
+            rgba: platform::icon(64),
+            width: 64,
+            height: 64,
+        }),
+        ..Default::default()
+    };
+    eframe::run_native(
+        &title.clone(),
+        options,
+        Box::new(move |cc| {
+            let mut app = app;
+            report_graphics(&mut app, cc);
+            Box::new(EframeApp { app, title })
+        }),
+    );
+}
+
+/// Runs the app in the canvas of `index.html`.
+#[cfg(target_arch = "wasm32")]
+pub fn run(app: App) {
+    let title = app.window_title();
+    eframe::start_web(
+        "hamming_canvas",
+        eframe::WebOptions::default(),
+        Box::new(move |cc| {
+            let mut app = app;
+            report_graphics(&mut app, cc);
+            Box::new(EframeApp { app, title })
+        }),
+    )
+    .expect("The app couldn't start in the page.");
+}
@@ -0,0 +1,211 @@
+use crate::{analysis, codes::CodeInfo, manifest};
+use egui::{vec2, Color32, DragValue, Rect, Sense};
+use hamming::{decode, Code};
+use rand::thread_rng;
+
+/// The size of one codeword in a thumbnail.
+const CELL: f32 = 6.0;
+
+/// Codewords per row of a thumbnail.
+const THUMBNAIL_COLUMNS: usize = 24;
+
+/// The most rows a thumbnail is drawn with; longer streams are cut short.
+const THUMBNAIL_ROWS: usize = 16;
+
+/// Thumbnails per row of the window.
+const PER_ROW: usize = 4;
+
+/// The longest decoded message shown under a thumbnail, in characters.
+const PREVIEW_CHARS: usize = 24;
+
+/// One seed's error pattern and what came of it.
+struct Outcome {
+    /// How many bits each codeword had flipped.
+    flips: Vec<usize>,
+    total_flips: usize,
+    /// The decoded message, or `None` if the decoder gave up.
+    decoded: Option<Vec<u8>>,
+}
+
+impl Outcome {
+    fn new(code: &Code, info: &CodeInfo, encoded: &[u8], seed: u64, probability: f64) -> Self {
+        let error = manifest::error_pattern(seed, encoded.len(), probability);
+        let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        let flips = analysis::error_counts(info, &error);
+        Self {
+            total_flips: flips.iter().sum(),
+            flips,
+            decoded: decode(&received, code).ok(),
+        }
+    }
+}
+
+/// The same message received with several error patterns of the same probability side by side,
+/// to show how much one run can differ from the next.
+pub struct Ensemble {
+    pub open: bool,
+    probability: f64,
+    seeds: Vec<u64>,
+    outcomes: Vec<Outcome>,
+    /// The stream and probability `outcomes` were worked out for.
+    outcomes_for: Option<(Vec<u8>, f64)>,
+}
+
+impl Ensemble {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            probability: 0.02,
+            seeds: Self::roll(8),
+            outcomes: Vec::new(),
+            outcomes_for: None,
+        }
+    }
+
+    fn roll(count: usize) -> Vec<u64> {
+        let mut rng = thread_rng();
+        (0..count).map(|_| manifest::new_seed(&mut rng)).collect()
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        code: &Code,
+        info: &CodeInfo,
+        message: &[u8],
+        encoded: &[u8],
+    ) {
+        if !self.open {
+            return;
+        }
+        // The same seeds are kept as the message, code and probability change, so that each
+        // thumbnail stays the same draw of the channel.
+        let current = matches!(&self.outcomes_for, Some((e, p))
+            if e == encoded && *p == self.probability && self.outcomes.len() == self.seeds.len());
+        if !current {
+            self.outcomes = self
+                .seeds
+                .iter()
+                .map(|&seed| Outcome::new(code, info, encoded, seed, self.probability))
+                .collect();
+            self.outcomes_for = Some((encoded.to_vec(), self.probability));
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Ensemble")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, message));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, message: &[u8]) {
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.001)
+                    .max_decimals(4),
+            );
+            let mut count = self.seeds.len();
+            ui.label("Seeds:");
+            if ui
+                .add(DragValue::new(&mut count).clamp_range(1..=32))
+                .changed()
+            {
+                let mut rng = thread_rng();
+                self.seeds
+                    .resize_with(count, || manifest::new_seed(&mut rng));
+            }
+            if ui.button("Roll again").clicked() {
+                self.seeds = Self::roll(count);
+            }
+        });
+        if message.is_empty() {
+            ui.label("Type a message to see it received with different errors.");
+            return;
+        }
+        let whole = self
+            .outcomes
+            .iter()
+            .filter(|o| o.decoded.as_deref() == Some(message))
+            .count();
+        ui.label(format!(
+            "{whole} of {} decoded whole. Codewords are green with one flip, which is \
+             corrected, and red with more.",
+            self.outcomes.len()
+        ));
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (row, outcomes) in self.outcomes.chunks(PER_ROW).enumerate() {
+                ui.horizontal_top(|ui| {
+                    for (i, outcome) in outcomes.iter().enumerate() {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                thumbnail(ui, row * PER_ROW + i, outcome, message);
+                            });
+                        });
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Draws one outcome: its codewords as cells colored by how many flips they took, and how it
+/// decoded.
+fn thumbnail(ui: &mut egui::Ui, i: usize, outcome: &Outcome, message: &[u8]) {
+    ui.strong(format!("Seed {}", i + 1));
+    let rows = outcome
+        .flips
+        .len()
+        .div_ceil(THUMBNAIL_COLUMNS)
+        .clamp(1, THUMBNAIL_ROWS);
+    let size = vec2(THUMBNAIL_COLUMNS as f32 * CELL, rows as f32 * CELL);
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter();
+    let faint = ui.visuals().faint_bg_color;
+    for (j, &flips) in outcome
+        .flips
+        .iter()
+        .enumerate()
+        .take(rows * THUMBNAIL_COLUMNS)
+    {
+        let (row, col) = (j / THUMBNAIL_COLUMNS, j % THUMBNAIL_COLUMNS);
+        let cell = Rect::from_min_size(
+            rect.min + vec2(col as f32 * CELL, row as f32 * CELL),
+            vec2(CELL, CELL),
+        )
+        .shrink(0.5);
+        let color = match flips {
+            0 => faint,
+            1 => Color32::DARK_GREEN,
+            _ => Color32::DARK_RED,
+        };
+        painter.rect_filled(cell, 0.0, color);
+    }
+    if outcome.flips.len() > rows * THUMBNAIL_COLUMNS {
+        ui.small(format!(
+            "First {} of {} codewords",
+            rows * THUMBNAIL_COLUMNS,
+            outcome.flips.len()
+        ));
+    }
+    ui.label(format!("{} flips", outcome.total_flips));
+    match &outcome.decoded {
+        Some(d) if d == message => ui.colored_label(Color32::GREEN, "matches original"),
+        Some(_) => ui.colored_label(Color32::RED, "differs from original"),
+        None => ui.colored_label(Color32::RED, "undecodable"),
+    };
+    if let Some(decoded) = &outcome.decoded {
+        let text = String::from_utf8_lossy(decoded);
+        let mut preview: String = text.chars().take(PREVIEW_CHARS).collect();
+        if text.chars().count() > PREVIEW_CHARS {
+            preview.push_str("...");
+        }
+        ui.small(preview);
+    }
+}
@@ -0,0 +1,45 @@
+/// A ready-made message for the Original panel.
+pub struct Example {
+    pub name: &'static str,
+    pub text: &'static str,
+    /// What the example demonstrates.
+    pub note: &'static str,
+}
+
+/// The template library. Most entries stress UTF-8: a bit error the code can't correct inside a
+/// multi-byte character leaves the decoded bytes invalid UTF-8, and the whole message is lost.
+pub const EXAMPLES: [Example; 6] = [
+    Example {
+        name: "Plain ASCII",
+        text: "The quick brown fox jumps over the lazy dog.",
+        note: "One byte per character, so a bad byte only garbles one letter.",
+    },
+    Example {
+        name: "Accented Latin",
+        text: "Crème brûlée, façade, naïve, smörgåsbord.",
+        note: "Two-byte characters among ASCII ones.",
+    },
+    Example {
+        name: "CJK",
+        text: "汉字テスト한국어 — 誤り訂正符号",
+        note: "Three bytes per character: most bytes are continuation bytes, which must start with \
+               10 in binary.",
+    },
+    Example {
+        name: "Emoji",
+        text: "Launch 🚀, family 👨‍👩‍👧, flag 🇳🇿, thumbs 👍🏽",
+        note: "Four-byte characters, some joined into one glyph by zero-width joiners or \
+               modifiers.",
+    },
+    Example {
+        name: "Combining characters",
+        text: "e\u{301} a\u{308} n\u{303} Z\u{335}\u{321}\u{34d}a\u{337}\u{31b}l\u{336}g\u{334}o\u{338}",
+        note: "Accents stored as separate combining characters after their base letter, so a \
+               damaged accent can move to, or vanish from, a letter that survived.",
+    },
+    Example {
+        name: "Mixed scripts",
+        text: "Hello, مرحبا, שלום, Здравствуйте, नमस्ते, 你好",
+        note: "One- to three-byte characters side by side, including right-to-left text.",
+    },
+];
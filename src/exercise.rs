@@ -0,0 +1,338 @@
+use crate::{format::InputFormat, leaderboard::Leaderboard};
+use egui::{Color32, TextEdit};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::{seq::SliceRandom, thread_rng};
+
+/// The parts of the app an exercise may inspect and set up.
+pub struct Bench<'a> {
+    pub code: &'a Code,
+    pub message_in: &'a mut String,
+    pub format: &'a mut InputFormat,
+    pub error: &'a mut Vec<u8>,
+}
+
+pub enum Verdict {
+    /// The answer was not accepted; the player may try again.
+    Retry(String),
+    /// The answer was accepted and the exercise moved on to its next task.
+    Next(String),
+    Finished,
+}
+
+/// A timed, scored task. The quiz, challenge, and game modes are all exercises.
+pub trait Exercise {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn time_limit(&self) -> Option<f64> {
+        None
+    }
+    fn start(&mut self, bench: &mut Bench);
+    fn task(&self) -> String;
+    fn answer_ui(&mut self, _ui: &mut egui::Ui) {}
+    fn validate(&mut self, bench: &mut Bench) -> Verdict;
+    fn score(&self, seconds: f64) -> u32;
+}
+
+struct Run {
+    started: Instant,
+    feedback: Option<Result<String, String>>,
+}
+
+pub struct Engine {
+    pub open: bool,
+    player: String,
+    exercises: Vec<Box<dyn Exercise>>,
+    selected: usize,
+    run: Option<Run>,
+    result: Option<String>,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            player: String::new(),
+            exercises: vec![Box::new(OverheadQuiz::new()), Box::new(FlipBudget::new())],
+            selected: 0,
+            run: None,
+            result: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, bench: &mut Bench, leaderboard: &mut Leaderboard) {
+        let mut open = self.open;
+        egui::Window::new("Exercises")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, bench, leaderboard));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, bench: &mut Bench, leaderboard: &mut Leaderboard) {
+        let run = match &mut self.run {
+            Some(run) => run,
+            None => {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.player);
+                });
+                for (i, exercise) in self.exercises.iter().enumerate() {
+                    ui.radio_value(&mut self.selected, i, exercise.name());
+                }
+                let exercise = &mut self.exercises[self.selected];
+                ui.label(exercise.description());
+                if let Some(limit) = exercise.time_limit() {
+                    ui.label(format!("Time limit: {limit:.0} s"));
+                }
+                if let Some(result) = &self.result {
+                    ui.separator();
+                    ui.label(result.as_str());
+                }
+                if ui
+                    .add_enabled(!self.player.trim().is_empty(), egui::Button::new("Start"))
+                    .clicked()
+                {
+                    exercise.start(bench);
+                    self.result = None;
+                    self.run = Some(Run {
+                        started: Instant::now(),
+                        feedback: None,
+                    });
+                }
+                return;
+            }
+        };
+
+        let exercise = &mut self.exercises[self.selected];
+        let seconds = run.started.elapsed().as_secs_f64();
+        match exercise.time_limit() {
+            Some(limit) => ui.label(format!("Time: {seconds:.1} / {limit:.0} s")),
+            None => ui.label(format!("Time: {seconds:.1} s")),
+        };
+        ui.separator();
+        ui.label(exercise.task());
+        exercise.answer_ui(ui);
+
+        let mut finished = exercise.time_limit().is_some_and(|limit| seconds >= limit);
+        let mut give_up = false;
+        ui.horizontal(|ui| {
+            if ui.button("Submit").clicked() {
+                match exercise.validate(bench) {
+                    Verdict::Retry(why) => run.feedback = Some(Err(why)),
+                    Verdict::Next(msg) => run.feedback = Some(Ok(msg)),
+                    Verdict::Finished => finished = true,
+                }
+            }
+            give_up = ui.button("Give up").clicked();
+        });
+        match &run.feedback {
+            Some(Ok(msg)) => {
+                ui.colored_label(Color32::GREEN, msg.as_str());
+            }
+            Some(Err(why)) => {
+                ui.colored_label(Color32::RED, why.as_str());
+            }
+            None => {}
+        }
+
+        if give_up {
+            self.result = Some("Gave up.".to_string());
+            self.run = None;
+        } else if finished {
+            let score = exercise.score(seconds);
+            leaderboard.record(self.player.trim(), exercise.name(), score, seconds);
+            self.result = Some(format!("Finished with {score} points in {seconds:.1} s."));
+            self.run = None;
+        }
+    }
+}
+
+const WORDS: [&str; 12] = [
+    "parity",
+    "syndrome",
+    "hamming",
+    "bit",
+    "codeword",
+    "noise",
+    "channel",
+    "redundancy",
+    "error",
+    "decode",
+    "hello, world",
+    "information theory",
+];
+const ROUNDS: u32 = 5;
+
+/// How many bytes does a message become once encoded?
+struct OverheadQuiz {
+    round: u32,
+    correct: u32,
+    word: &'static str,
+    answer: String,
+}
+
+impl OverheadQuiz {
+    fn new() -> Self {
+        Self {
+            round: 0,
+            correct: 0,
+            word: WORDS[0],
+            answer: String::new(),
+        }
+    }
+
+    fn next_word(&mut self) {
+        self.word = *WORDS.choose(&mut thread_rng()).unwrap();
+        self.answer.clear();
+    }
+}
+
+impl Exercise for OverheadQuiz {
+    fn name(&self) -> &'static str {
+        "Overhead quiz"
+    }
+
+    fn description(&self) -> &'static str {
+        "Work out how many bytes each message occupies once it is encoded."
+    }
+
+    fn time_limit(&self) -> Option<f64> {
+        Some(120.0)
+    }
+
+    fn start(&mut self, bench: &mut Bench) {
+        self.round = 1;
+        self.correct = 0;
+        bench.message_in.clear();
+        bench.error.clear();
+        self.next_word();
+    }
+
+    fn task(&self) -> String {
+        format!(
+            "Round {}/{ROUNDS}: how many encoded bytes does \"{}\" ({} bytes) become?",
+            self.round,
+            self.word,
+            self.word.len()
+        )
+    }
+
+    fn answer_ui(&mut self, ui: &mut egui::Ui) {
+        ui.add(TextEdit::singleline(&mut self.answer).hint_text("bytes"));
+    }
+
+    fn validate(&mut self, bench: &mut Bench) -> Verdict {
+        let guess = match self.answer.trim().parse::<usize>() {
+            Ok(guess) => guess,
+            Err(_) => return Verdict::Retry("Enter a whole number of bytes.".to_string()),
+        };
+        let expected = encode(self.word.as_bytes(), bench.code).unwrap().len();
+        let feedback = if guess == expected {
+            self.correct += 1;
+            "Correct!".to_string()
+        } else {
+            format!("It was {expected} bytes.")
+        };
+        // Show the message so the answer can be checked in the panels.
+        *bench.message_in = self.word.to_string();
+        *bench.format = InputFormat::Text;
+        if self.round == ROUNDS {
+            return Verdict::Finished;
+        }
+        self.round += 1;
+        let word = self.word;
+        self.next_word();
+        Verdict::Next(format!("{feedback} (\"{word}\" is shown in the panels)"))
+    }
+
+    fn score(&self, seconds: f64) -> u32 {
+        (self.correct * 100).saturating_sub(seconds as u32)
+    }
+}
+
+/// The most bits the flip budget game allows flipping.
+const BUDGET: usize = 8;
+
+/// Corrupt the message so that it decodes wrongly without the decoder noticing, in as few flips
+/// as possible.
+struct FlipBudget {
+    word: &'static str,
+    /// The flips of the accepted attempt.
+    flips: usize,
+}
+
+impl FlipBudget {
+    fn new() -> Self {
+        Self {
+            word: WORDS[0],
+            flips: BUDGET,
+        }
+    }
+}
+
+impl Exercise for FlipBudget {
+    fn name(&self) -> &'static str {
+        "Flip budget"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flip bits in the Error panel so that the message decodes to something else without the \
+         decoder noticing: a silent miscorrection. The fewer flips it takes, the higher the \
+         score."
+    }
+
+    fn start(&mut self, bench: &mut Bench) {
+        self.word = WORDS.choose(&mut thread_rng()).unwrap();
+        self.flips = BUDGET;
+        *bench.message_in = self.word.to_string();
+        *bench.format = InputFormat::Text;
+        bench.error.clear();
+    }
+
+    fn task(&self) -> String {
+        format!(
+            "Make \"{}\" decode wrongly with no complaint from the decoder, flipping at most \
+             {BUDGET} bits.",
+            self.word
+        )
+    }
+
+    fn validate(&mut self, bench: &mut Bench) -> Verdict {
+        let encoded = encode(self.word.as_bytes(), bench.code).unwrap();
+        if bench.error.len() != encoded.len() {
+            return Verdict::Retry("Wait for the message to be encoded.".to_string());
+        }
+        let flips = bench
+            .error
+            .iter()
+            .map(|b| b.count_ones() as usize)
+            .sum::<usize>();
+        if flips == 0 {
+            return Verdict::Retry("Flip some bits in the Error panel first.".to_string());
+        }
+        if flips > BUDGET {
+            return Verdict::Retry(format!(
+                "That's {flips} flips, over the budget of {BUDGET}."
+            ));
+        }
+        let received: Vec<u8> = Iterator::zip(encoded.iter(), bench.error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        match decode(&received, bench.code) {
+            Err(_) => Verdict::Retry(
+                "The decoder noticed and gave up, so the damage wasn't silent.".to_string(),
+            ),
+            Ok(decoded) if decoded == self.word.as_bytes() => Verdict::Retry(format!(
+                "The code corrected all {flips} flips and the message came through."
+            )),
+            Ok(_) => {
+                self.flips = flips;
+                Verdict::Finished
+            }
+        }
+    }
+
+    fn score(&self, seconds: f64) -> u32 {
+        ((BUDGET + 1 - self.flips) as u32 * 100).saturating_sub(seconds as u32)
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use std::path::PathBuf;
+
+// The web has no file system and no blocking file dialogs, so there nothing can be opened, and
+// saving text or bytes downloads them instead. The dialogs act as if cancelled.
+
+/// Asks where to save a file, or `None` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_path(file_name: &str, filter: &str, extension: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .set_file_name(file_name)
+        .add_filter(filter, &[extension])
+        .save_file()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_path(_file_name: &str, _filter: &str, _extension: &str) -> Option<PathBuf> {
+    None
+}
+
+/// Asks which file to open, or `None` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_path(filter: &str, extensions: &[&str]) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter(filter, extensions)
+        .pick_file()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn open_path(_filter: &str, _extensions: &[&str]) -> Option<PathBuf> {
+    None
+}
+
+/// Asks which file to open, of any kind, or `None` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_any_path() -> Option<PathBuf> {
+    rfd::FileDialog::new().pick_file()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn open_any_path() -> Option<PathBuf> {
+    None
+}
+
+/// Asks where to save `contents`, then writes it there. Returns the path written, or `None` if
+/// the dialog was cancelled.
+pub fn save_text(
+    file_name: &str,
+    filter: &str,
+    extension: &str,
+    contents: &str,
+) -> Result<Option<PathBuf>, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (filter, extension);
+        save_bytes(file_name, contents.as_bytes())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let path = match save_path(file_name, filter, extension) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        fs::write(&path, contents).map_err(|e| e.to_string())?;
+        Ok(Some(path))
+    }
+}
+
+/// Asks where to save `contents`, of any kind, then writes it there. Returns the path written, or
+/// `None` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_bytes(file_name: &str, contents: &[u8]) -> Result<Option<PathBuf>, String> {
+    let path = match rfd::FileDialog::new().set_file_name(file_name).save_file() {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(Some(path))
+}
+
+/// Downloads `contents` as `file_name`, returning the name, for the browser to save wherever it
+/// saves downloads.
+#[cfg(target_arch = "wasm32")]
+pub fn save_bytes(file_name: &str, contents: &[u8]) -> Result<Option<PathBuf>, String> {
+    use wasm_bindgen::JsCast;
+
+    let error = |e: wasm_bindgen::JsValue| format!("{e:?}");
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(error)?;
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| "There is no page to download from.".to_string())?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(error)?
+        .dyn_into()
+        .map_err(|_| "Couldn't make a download link.".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url).map_err(error)?;
+    Ok(Some(PathBuf::from(file_name)))
+}
@@ -0,0 +1,215 @@
+use crate::{codes::CodeInfo, export, manifest};
+use egui::{Color32, DragValue, Grid};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::thread_rng;
+use std::{fs, path::PathBuf, time::Duration};
+
+/// A file run through encoding, the channel, and decoding.
+struct Run {
+    path: PathBuf,
+    /// The code the file was run with.
+    code: &'static str,
+    input: Vec<u8>,
+    encoded: Vec<u8>,
+    /// The encoded file with the error applied.
+    received: Vec<u8>,
+    flips: u32,
+    /// The decoded file, or `None` if the decoder gave up.
+    decoded: Option<Vec<u8>>,
+    elapsed: Duration,
+}
+
+impl Run {
+    fn new(
+        path: PathBuf,
+        input: Vec<u8>,
+        code: &Code,
+        info: &CodeInfo,
+        probability: f64,
+    ) -> Result<Self, String> {
+        let start = Instant::now();
+        let encoded = encode(&input, code).map_err(|e| format!("{e:?}"))?;
+        let seed = manifest::new_seed(&mut thread_rng());
+        let error = manifest::error_pattern(seed, encoded.len(), probability);
+        let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        let decoded = decode(&received, code).ok();
+        Ok(Self {
+            path,
+            code: info.name,
+            input,
+            encoded,
+            received,
+            flips: error.iter().map(|b| b.count_ones()).sum(),
+            decoded,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    /// The name of the input file with `suffix` appended, for saving an output beside it.
+    fn file_name(&self, suffix: &str) -> String {
+        let name = self.path.file_name().unwrap_or_default().to_string_lossy();
+        format!("{name}{suffix}")
+    }
+}
+
+/// Runs any file, not just text, through the pipeline, for demonstrating on images, binaries and
+/// firmware. The encoded, received and decoded files can each be saved.
+pub struct FileMode {
+    pub open: bool,
+    probability: f64,
+    run: Option<Run>,
+    status: Option<Result<String, String>>,
+}
+
+impl FileMode {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            probability: 0.001,
+            run: None,
+            status: None,
+        }
+    }
+
+    /// Asks for a file and runs it through the pipeline.
+    pub fn open_file(&mut self, code: &Code, info: &CodeInfo) {
+        let path = match export::open_any_path() {
+            Some(path) => path,
+            None => return,
+        };
+        self.status = None;
+        let run = fs::read(&path)
+            .map_err(|e| format!("{}: {e}", path.display()))
+            .and_then(|input| Run::new(path, input, code, info, self.probability));
+        match run {
+            Ok(run) => self.run = Some(run),
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code, info: &CodeInfo) {
+        let mut open = self.open;
+        egui::Window::new("File Mode")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, code, info));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, code: &Code, info: &CodeInfo) {
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.0001)
+                    .max_decimals(4),
+            );
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Open file...").clicked() {
+                self.open_file(code, info);
+            }
+            let again = ui
+                .add_enabled(self.run.is_some(), egui::Button::new("Run again"))
+                .on_hover_text("The same file, with the current code and a fresh error.");
+            if again.clicked() {
+                self.rerun(code, info);
+            }
+        });
+        ui.separator();
+
+        let run = match &self.run {
+            Some(run) => run,
+            None => {
+                ui.label("Open a file of any kind to encode it, flip bits of it, and decode it.");
+                self.show_status(ui);
+                return;
+            }
+        };
+        Grid::new("file_mode").show(ui, |ui| {
+            ui.label("File");
+            ui.label(run.path.display().to_string());
+            ui.end_row();
+            ui.label("Code");
+            ui.label(run.code);
+            ui.end_row();
+            ui.label("Size");
+            ui.label(format!("{} bytes", run.input.len()));
+            ui.end_row();
+            ui.label("Encoded");
+            ui.label(format!("{} bytes", run.encoded.len()));
+            ui.end_row();
+            ui.label("Bits flipped");
+            ui.label(run.flips.to_string());
+            ui.end_row();
+            ui.label("Decoded");
+            match &run.decoded {
+                Some(d) if *d == run.input => ui.colored_label(Color32::GREEN, "matches original"),
+                Some(_) => ui.colored_label(Color32::RED, "differs from original"),
+                None => ui.colored_label(Color32::RED, "undecodable"),
+            };
+            ui.end_row();
+            ui.label("Time");
+            ui.label(format!("{:.1} ms", run.elapsed.as_secs_f64() * 1000.0));
+            ui.end_row();
+        });
+
+        let mut saved = None;
+        ui.horizontal(|ui| {
+            if ui.button("Save encoded...").clicked() {
+                saved = Some(export::save_bytes(&run.file_name(".bin"), &run.encoded));
+            }
+            if ui.button("Save received...").clicked() {
+                saved = Some(export::save_bytes(
+                    &run.file_name(".received.bin"),
+                    &run.received,
+                ));
+            }
+            if let Some(decoded) = &run.decoded {
+                if ui.button("Save decoded...").clicked() {
+                    saved = Some(export::save_bytes(&run.file_name(".decoded"), decoded));
+                }
+            }
+        });
+        match saved {
+            Some(Ok(Some(path))) => self.status = Some(Ok(format!("Saved to {}", path.display()))),
+            Some(Err(e)) => self.status = Some(Err(e)),
+            _ => {}
+        }
+        self.show_status(ui);
+    }
+
+    /// Runs the current file again, with the current code and a fresh error.
+    fn rerun(&mut self, code: &Code, info: &CodeInfo) {
+        let rerun = match &self.run {
+            Some(run) => Run::new(
+                run.path.clone(),
+                run.input.clone(),
+                code,
+                info,
+                self.probability,
+            ),
+            None => return,
+        };
+        self.status = None;
+        match rerun {
+            Ok(run) => self.run = Some(run),
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+
+    fn show_status(&self, ui: &mut egui::Ui) {
+        match &self.status {
+            Some(Ok(msg)) => {
+                ui.label(msg.as_str());
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e.as_str());
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::telegraph;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Bytes per line when bytes are written in hex.
+pub const HEX_LINE_BYTES: usize = 16;
+
+/// Bytes per line when bytes are written in decimal.
+pub const DECIMAL_LINE_BYTES: usize = 16;
+
+/// Bytes per line when bytes are written in Base64: 64 characters.
+pub const BASE64_LINE_BYTES: usize = 48;
+
+/// How the message is written in the Original panel, so that it can be any bytes at all rather
+/// than only typeable text. The decoded message is written the same way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputFormat {
+    #[default]
+    Text,
+    /// Text of ASCII characters sent seven bits each, with no byte boundaries.
+    Ascii7,
+    /// Text in the five-bit Baudot code of teleprinters.
+    Baudot,
+    Hex,
+    Base64,
+}
+
+impl InputFormat {
+    pub const ALL: [InputFormat; 5] = [
+        InputFormat::Text,
+        InputFormat::Ascii7,
+        InputFormat::Baudot,
+        InputFormat::Hex,
+        InputFormat::Base64,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputFormat::Text => "UTF-8 text",
+            InputFormat::Ascii7 => "7-bit ASCII text",
+            InputFormat::Baudot => "5-bit Baudot text",
+            InputFormat::Hex => "Hex",
+            InputFormat::Base64 => "Base64",
+        }
+    }
+
+    /// Whether the message is typed as characters, rather than as its bytes written out.
+    pub fn is_text(self) -> bool {
+        matches!(
+            self,
+            InputFormat::Text | InputFormat::Ascii7 | InputFormat::Baudot
+        )
+    }
+
+    /// The bytes `text` stands for. Whitespace is ignored in hex and Base64.
+    pub fn parse(self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            InputFormat::Text => Ok(text.as_bytes().to_vec()),
+            InputFormat::Ascii7 => telegraph::encode_ascii7(text),
+            InputFormat::Baudot => telegraph::encode_baudot(text),
+            InputFormat::Hex => parse_hex(text),
+            InputFormat::Base64 => parse_base64(text),
+        }
+    }
+
+    /// `bytes` written in this format, or `None` if they aren't valid UTF-8 text.
+    pub fn write(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            InputFormat::Text => String::from_utf8(bytes.to_vec()).ok(),
+            InputFormat::Ascii7 => Some(telegraph::decode_ascii7(bytes)),
+            InputFormat::Baudot => Some(telegraph::decode_baudot(bytes)),
+            InputFormat::Hex => {
+                let mut s = String::new();
+                write_hex(bytes, &mut s);
+                Some(s)
+            }
+            InputFormat::Base64 => {
+                let mut s = String::new();
+                write_base64(bytes, &mut s);
+                Some(s)
+            }
+        }
+    }
+}
+
+/// The bytes `text` stands for in hex, ignoring whitespace.
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            c.to_digit(16)
+                .map(|d| d as u8)
+                .ok_or_else(|| format!("{c:?} isn't a hex digit."))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    if digits.len() % 2 == 1 {
+        return Err("An odd number of hex digits doesn't make whole bytes.".to_string());
+    }
+    Ok(digits.chunks(2).map(|d| d[0] << 4 | d[1]).collect())
+}
+
+/// The bytes `text` stands for in padded or unpadded Base64, ignoring whitespace.
+pub fn parse_base64(text: &str) -> Result<Vec<u8>, String> {
+    let chars: Vec<u8> = text.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+    let padding = chars.iter().rev().take_while(|&&c| c == b'=').count();
+    let data = &chars[..chars.len() - padding];
+    if padding > 2 || data.len() % 4 == 1 || (padding > 0 && data.len() % 4 + padding != 4) {
+        return Err("That isn't a whole number of Base64 groups.".to_string());
+    }
+    let values = data
+        .iter()
+        .map(|&c| {
+            BASE64
+                .iter()
+                .position(|&b| b == c)
+                .map(|v| v as u32)
+                .ok_or_else(|| format!("{:?} isn't a Base64 character.", c as char))
+        })
+        .collect::<Result<Vec<u32>, String>>()?;
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for group in values.chunks(4) {
+        let bits = group
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, v)| acc | v << (18 - 6 * i));
+        // Two characters make one byte, three make two, and four make three.
+        for i in 0..group.len() - 1 {
+            bytes.push((bits >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// The bytes `text` stands for in binary, most significant bit first, ignoring whitespace. A
+/// last partial byte is filled out with zeros.
+pub fn parse_binary(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for (i, c) in text.chars().filter(|c| !c.is_whitespace()).enumerate() {
+        let bit = match c {
+            '0' => 0,
+            '1' => 1,
+            _ => return Err(format!("{c:?} isn't a binary digit.")),
+        };
+        if i % 8 == 0 {
+            bytes.push(0);
+        }
+        *bytes.last_mut().unwrap() |= bit << (7 - i % 8);
+    }
+    Ok(bytes)
+}
+
+/// The bytes `text` stands for as decimal numbers separated by whitespace.
+pub fn parse_decimal(text: &str) -> Result<Vec<u8>, String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.parse()
+                .map_err(|_| format!("{word:?} isn't a byte from 0 to 255."))
+        })
+        .collect()
+}
+
+/// Appends `bytes` to `s` in hex, `HEX_LINE_BYTES` to a line, so that every byte takes three
+/// characters with the space or newline after it.
+pub fn write_hex(bytes: &[u8], s: &mut String) {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    s.reserve(bytes.len() * 3);
+    for (i, b) in bytes.iter().enumerate() {
+        s.push(DIGITS[usize::from(b >> 4)] as char);
+        s.push(DIGITS[usize::from(b & 0xf)] as char);
+        s.push(if (i + 1) % HEX_LINE_BYTES == 0 {
+            '\n'
+        } else {
+            ' '
+        });
+    }
+}
+
+/// Appends `bytes` to `s` in decimal, `DECIMAL_LINE_BYTES` to a line, each padded to three
+/// digits so that every byte takes four characters with the space or newline after it.
+pub fn write_decimal(bytes: &[u8], s: &mut String) {
+    s.reserve(bytes.len() * 4);
+    for (i, b) in bytes.iter().enumerate() {
+        // Writing to a `String` can't fail.
+        let _ = write!(s, "{b:>3}");
+        s.push(if (i + 1) % DECIMAL_LINE_BYTES == 0 {
+            '\n'
+        } else {
+            ' '
+        });
+    }
+}
+
+/// Appends `bytes` to `s` in padded Base64, `BASE64_LINE_BYTES` to a line.
+pub fn write_base64(bytes: &[u8], s: &mut String) {
+    s.reserve(bytes.len() * 4 / 3 + 4);
+    for (i, group) in bytes.chunks(3).enumerate() {
+        let bits = group
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for j in 0..4 {
+            if j <= group.len() {
+                s.push(BASE64[(bits >> (18 - 6 * j) & 0x3f) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+        if (i + 1) % (BASE64_LINE_BYTES / 3) == 0 {
+            s.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut s = String::new();
+        write_hex(&bytes, &mut s);
+        assert_eq!(parse_hex(&s).unwrap(), bytes);
+        assert_eq!(parse_hex(" 0A ff\n10 ").unwrap(), [0x0a, 0xff, 0x10]);
+    }
+
+    #[test]
+    fn malformed_hex() {
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("0g").is_err());
+        assert!(parse_hex("0x10").is_err());
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        for len in 0..100 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            let mut s = String::new();
+            write_base64(&bytes, &mut s);
+            assert_eq!(parse_base64(&s).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn base64_padding() {
+        let mut s = String::new();
+        write_base64(b"Ma", &mut s);
+        assert_eq!(s, "TWE=");
+        assert_eq!(parse_base64("TWE=").unwrap(), b"Ma");
+        assert_eq!(parse_base64("TWE").unwrap(), b"Ma");
+        assert_eq!(parse_base64("TQ==").unwrap(), b"M");
+        assert_eq!(parse_base64("TWFu").unwrap(), b"Man");
+    }
+
+    #[test]
+    fn malformed_base64() {
+        assert!(parse_base64("T").is_err());
+        assert!(parse_base64("TWFuT").is_err());
+        assert!(parse_base64("TQ=").is_err());
+        assert!(parse_base64("T===").is_err());
+        assert!(parse_base64("TW!u").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+/// Primitive polynomials for GF(2^m), with bit `i` the coefficient of `x^i`.
+const PRIMITIVE_POLYNOMIALS: [u32; 9] = [
+    0,
+    0,
+    0b111,
+    0b1011,
+    0b10011,
+    0b100101,
+    0b1000011,
+    0b10001001,
+    0b100011101,
+];
+
+pub const MIN_M: u32 = 2;
+pub const MAX_M: u32 = 8;
+
+/// GF(2^m), with elements as polynomials over GF(2) in the bits of a `u32`, and `α = x` as the
+/// primitive element.
+#[derive(Clone, Debug)]
+pub struct Field {
+    m: u32,
+    poly: u32,
+    exp: Vec<u32>,
+    log: Vec<u32>,
+}
+
+impl Field {
+    pub fn new(m: u32) -> Self {
+        assert!((MIN_M..=MAX_M).contains(&m), "GF(2^{m}) is not supported");
+        let poly = PRIMITIVE_POLYNOMIALS[m as usize];
+        let size = 1usize << m;
+        let mut exp = vec![0; 2 * size];
+        let mut log = vec![0; size];
+        let mut x = 1;
+        for (i, e) in exp.iter_mut().enumerate().take(size - 1) {
+            *e = x;
+            log[x as usize] = i as u32;
+            x <<= 1;
+            if x & size as u32 != 0 {
+                x ^= poly;
+            }
+        }
+        // Doubling the table avoids reducing exponents modulo 2^m - 1 when multiplying.
+        let (head, tail) = exp.split_at_mut(size - 1);
+        for (j, e) in tail.iter_mut().enumerate() {
+            *e = head[j % head.len()];
+        }
+        Self { m, poly, exp, log }
+    }
+
+    pub fn m(&self) -> u32 {
+        self.m
+    }
+
+    pub fn poly(&self) -> u32 {
+        self.poly
+    }
+
+    /// The number of elements, `2^m`.
+    pub fn size(&self) -> usize {
+        1 << self.m
+    }
+
+    /// The order of `α`, `2^m - 1`.
+    pub fn order(&self) -> usize {
+        self.size() - 1
+    }
+
+    pub fn add(&self, a: u32, b: u32) -> u32 {
+        a ^ b
+    }
+
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            self.exp[(self.log[a as usize] + self.log[b as usize]) as usize]
+        }
+    }
+
+    pub fn div(&self, a: u32, b: u32) -> u32 {
+        assert!(b != 0, "division by zero in GF(2^{})", self.m);
+        match self.log(a) {
+            None => 0,
+            Some(l) => self.alpha_pow(i64::from(l) - i64::from(self.log[b as usize])),
+        }
+    }
+
+    /// `α^i`, for any `i`.
+    pub fn alpha_pow(&self, i: i64) -> u32 {
+        self.exp[i.rem_euclid(self.order() as i64) as usize]
+    }
+
+    /// The `i` for which `α^i = a`, or `None` for zero.
+    pub fn log(&self, a: u32) -> Option<u32> {
+        (a != 0).then(|| self.log[a as usize])
+    }
+
+    /// Writes `a` as a power of `α`, e.g. `α^4`.
+    pub fn format_power(&self, a: u32) -> String {
+        match self.log(a) {
+            None => "0".to_string(),
+            Some(0) => "1".to_string(),
+            Some(1) => "α".to_string(),
+            Some(i) => format!("α^{i}"),
+        }
+    }
+}
+
+/// Writes a polynomial over GF(2), held in the bits of `p`, as e.g. `x^3 + x + 1`.
+pub fn format_poly(p: u32) -> String {
+    if p == 0 {
+        return "0".to_string();
+    }
+    format_terms((0..32).rev().filter(|i| (p >> i) & 1 == 1))
+}
+
+/// Like [`format_poly`], for polynomials too long for a `u32`, with `p[i]` the coefficient of
+/// `x^i`.
+pub fn format_poly_bits(p: &[bool]) -> String {
+    if !p.contains(&true) {
+        return "0".to_string();
+    }
+    format_terms((0..p.len()).rev().filter(|&i| p[i]))
+}
+
+fn format_terms(degrees: impl Iterator<Item = usize>) -> String {
+    let terms: Vec<String> = degrees
+        .map(|i| match i {
+            0 => "1".to_string(),
+            1 => "x".to_string(),
+            i => format!("x^{i}"),
+        })
+        .collect();
+    terms.join(" + ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `a * b` by shift and add, reduced by `poly` as it goes: the schoolbook product the tables
+    /// stand in for.
+    fn slow_mul(m: u32, poly: u32, mut a: u32, mut b: u32) -> u32 {
+        let mut p = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                p ^= a;
+            }
+            b >>= 1;
+            a <<= 1;
+            if a >> m & 1 == 1 {
+                a ^= poly;
+            }
+        }
+        p
+    }
+
+    #[test]
+    fn mul_matches_schoolbook() {
+        for m in MIN_M..=MAX_M {
+            let f = Field::new(m);
+            for a in 0..f.size() as u32 {
+                for b in 0..f.size() as u32 {
+                    assert_eq!(
+                        f.mul(a, b),
+                        slow_mul(m, f.poly(), a, b),
+                        "GF(2^{m}): {a} * {b}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_element_has_an_inverse() {
+        for m in MIN_M..=MAX_M {
+            let f = Field::new(m);
+            for a in 1..f.size() as u32 {
+                let inverse = f.div(1, a);
+                assert_eq!(f.mul(a, inverse), 1, "GF(2^{m}): {a}");
+                for b in 0..f.size() as u32 {
+                    assert_eq!(f.mul(f.div(b, a), a), b, "GF(2^{m}): {b} / {a}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn alpha_is_primitive() {
+        for m in MIN_M..=MAX_M {
+            let f = Field::new(m);
+            let mut seen = vec![false; f.size()];
+            let mut x = 1;
+            for i in 0..f.order() {
+                assert_eq!(f.alpha_pow(i as i64), x, "GF(2^{m}): α^{i}");
+                assert_eq!(f.log(x), Some(i as u32));
+                assert!(!seen[x as usize], "GF(2^{m}): α^{i} repeats");
+                seen[x as usize] = true;
+                x = slow_mul(m, f.poly(), x, 2);
+            }
+            // α^(2^m - 1) = 1, and negative powers wrap around.
+            assert_eq!(x, 1);
+            assert_eq!(f.alpha_pow(-1), f.div(1, 2));
+            assert_eq!(f.log(0), None);
+        }
+    }
+}
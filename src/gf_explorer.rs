@@ -0,0 +1,234 @@
+use crate::{
+    export,
+    gf::{self, Field},
+};
+use egui::{Color32, DragValue, Grid, RichText};
+
+/// The largest `m` whose addition and multiplication tables are drawn in full.
+const MAX_TABLE_M: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Table {
+    Powers,
+    Addition,
+    Multiplication,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Notation {
+    Decimal,
+    Binary,
+    Power,
+}
+
+/// Addition and multiplication tables and the powers of the primitive element of GF(2^m).
+pub struct GfExplorer {
+    pub open: bool,
+    field: Field,
+    table: Table,
+    notation: Notation,
+    selected: Option<(u32, u32)>,
+    export_status: Option<Result<String, String>>,
+}
+
+impl GfExplorer {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            field: Field::new(3),
+            table: Table::Powers,
+            notation: Notation::Decimal,
+            selected: None,
+            export_status: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Galois Field Explorer")
+            .open(&mut open)
+            .default_width(500.0)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn format(&self, a: u32) -> String {
+        match self.notation {
+            Notation::Decimal => a.to_string(),
+            Notation::Binary => format!("{a:0width$b}", width = self.field.m() as usize),
+            Notation::Power => self.field.format_power(a),
+        }
+    }
+
+    fn op(&self, a: u32, b: u32) -> u32 {
+        match self.table {
+            Table::Multiplication => self.field.mul(a, b),
+            _ => self.field.add(a, b),
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("GF(2^m), m =");
+            let mut m = self.field.m();
+            if ui
+                .add(DragValue::new(&mut m).clamp_range(gf::MIN_M..=gf::MAX_M))
+                .changed()
+            {
+                self.field = Field::new(m);
+                self.selected = None;
+            }
+            ui.label(format!(
+                "primitive polynomial {}",
+                gf::format_poly(self.field.poly())
+            ));
+        });
+        ui.horizontal(|ui| {
+            for (table, name) in [
+                (Table::Powers, "Powers of α"),
+                (Table::Addition, "Addition"),
+                (Table::Multiplication, "Multiplication"),
+            ] {
+                if ui.selectable_value(&mut self.table, table, name).changed() {
+                    self.selected = None;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Elements as");
+            ui.radio_value(&mut self.notation, Notation::Decimal, "decimal");
+            ui.radio_value(&mut self.notation, Notation::Binary, "binary");
+            ui.radio_value(&mut self.notation, Notation::Power, "powers of α");
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Export CSV").clicked() {
+                let name = format!("gf2_{}_{:?}.csv", self.field.m(), self.table).to_lowercase();
+                self.export_status = match export::save_text(&name, "CSV", "csv", &self.to_csv()) {
+                    Ok(Some(path)) => Some(Ok(format!("Saved {}", path.display()))),
+                    Ok(None) => self.export_status.take(),
+                    Err(e) => Some(Err(e)),
+                };
+            }
+            match &self.export_status {
+                Some(Ok(msg)) => {
+                    ui.label(msg.as_str());
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, e.as_str());
+                }
+                None => {}
+            }
+        });
+        ui.separator();
+
+        if let Some((a, b)) = self.selected {
+            let text = match self.table {
+                Table::Powers => format!("α^{a} = {} = {}", gf::format_poly(b), self.format(b)),
+                Table::Addition => format!(
+                    "{} + {} = {}",
+                    self.format(a),
+                    self.format(b),
+                    self.format(self.op(a, b))
+                ),
+                Table::Multiplication => format!(
+                    "{} · {} = {}",
+                    self.format(a),
+                    self.format(b),
+                    self.format(self.op(a, b))
+                ),
+            };
+            ui.label(RichText::new(text).strong());
+        }
+
+        egui::ScrollArea::both().show(ui, |ui| match self.table {
+            Table::Powers => self.powers_ui(ui),
+            _ if self.field.m() > MAX_TABLE_M => {
+                ui.label(format!(
+                    "Tables are only drawn for m up to {MAX_TABLE_M}; export them instead."
+                ));
+            }
+            _ => self.table_ui(ui),
+        });
+    }
+
+    fn powers_ui(&mut self, ui: &mut egui::Ui) {
+        Grid::new("gf_powers").striped(true).show(ui, |ui| {
+            ui.strong("Power");
+            ui.strong("Element");
+            ui.strong("Polynomial");
+            ui.end_row();
+            for i in 0..self.field.order() as u32 {
+                let a = self.field.alpha_pow(i64::from(i));
+                let selected = self.selected == Some((i, a));
+                if ui.selectable_label(selected, format!("α^{i}")).clicked() {
+                    self.selected = Some((i, a));
+                }
+                ui.label(RichText::new(self.format(a)).monospace());
+                ui.label(gf::format_poly(a));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn table_ui(&mut self, ui: &mut egui::Ui) {
+        let size = self.field.size() as u32;
+        let symbol = if self.table == Table::Multiplication {
+            "·"
+        } else {
+            "+"
+        };
+        Grid::new("gf_table").striped(true).show(ui, |ui| {
+            ui.strong(symbol);
+            for b in 0..size {
+                ui.strong(RichText::new(self.format(b)).monospace());
+            }
+            ui.end_row();
+            for a in 0..size {
+                ui.strong(RichText::new(self.format(a)).monospace());
+                for b in 0..size {
+                    let text = RichText::new(self.format(self.op(a, b))).monospace();
+                    let selected = self.selected == Some((a, b));
+                    if ui.selectable_label(selected, text).clicked() {
+                        self.selected = Some((a, b));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    fn to_csv(&self) -> String {
+        let mut s = String::new();
+        match self.table {
+            Table::Powers => {
+                s.push_str("power,element,polynomial\n");
+                for i in 0..self.field.order() as u32 {
+                    let a = self.field.alpha_pow(i64::from(i));
+                    s.push_str(&format!("{i},{},{}\n", self.format(a), gf::format_poly(a)));
+                }
+            }
+            _ => {
+                let size = self.field.size() as u32;
+                s.push_str(if self.table == Table::Multiplication {
+                    "*"
+                } else {
+                    "+"
+                });
+                for b in 0..size {
+                    s.push(',');
+                    s.push_str(&self.format(b));
+                }
+                s.push('\n');
+                for a in 0..size {
+                    s.push_str(&self.format(a));
+                    for b in 0..size {
+                        s.push(',');
+                        s.push_str(&self.format(self.op(a, b)));
+                    }
+                    s.push('\n');
+                }
+            }
+        }
+        s
+    }
+}
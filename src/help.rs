@@ -0,0 +1,143 @@
+use crate::platform::APP_NAME;
+
+/// The keyboard shortcuts, and what they do.
+const SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl+F", "Find in a panel"),
+    ("Enter", "Next match, in Find"),
+    ("Shift+Enter", "Previous match, in Find"),
+    ("Escape", "Close Find"),
+    ("Page Down", "Next scene of a demo script"),
+    ("Page Up", "Previous scene of a demo script"),
+    ("1 to 8", "Focus mode on a panel"),
+    ("0", "Focus mode off"),
+];
+
+/// The steps of a first look around, in order.
+const GETTING_STARTED: &[&str] = &[
+    "Type a message into the Original panel. It is encoded as you type.",
+    "Set an error probability and press Randomize Error to flip bits of the encoded stream.",
+    "Watch the Decoded panel: one flipped bit per codeword is corrected, and an extended code \
+     notices a second.",
+    "Choose Highlight parity in the Encoded panel, then hover a bit to see the bits it checks \
+     or is checked by.",
+    "Try Tools > Exercises to test what you've learned.",
+];
+
+/// The optional parts of the app, and whether this build has them.
+const FEATURES: &[(&str, bool)] = &[
+    ("alloc-stats", cfg!(feature = "alloc-stats")),
+    ("audio", cfg!(feature = "audio")),
+    ("bundle", cfg!(feature = "bundle")),
+    ("eframe", cfg!(feature = "eframe")),
+    ("explorers", cfg!(feature = "explorers")),
+    ("miniquad", cfg!(feature = "miniquad")),
+    ("network", cfg!(feature = "network")),
+    ("simulation", cfg!(feature = "simulation")),
+];
+
+/// What the app is drawn with, as reported by the window backend.
+pub struct Graphics {
+    pub backend: &'static str,
+    /// The driver's own description of itself, such as the OpenGL vendor, renderer, and version.
+    pub driver: Option<String>,
+}
+
+/// Everything in the About window that helps pin down a problem on someone else's machine,
+/// as `(name, value)` rows.
+fn diagnostics(graphics: Option<&Graphics>) -> Vec<(&'static str, String)> {
+    let enabled: Vec<&str> = FEATURES
+        .iter()
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| *name)
+        .collect();
+    let unknown = || "unknown".to_string();
+    vec![
+        ("Version", env!("CARGO_PKG_VERSION").to_string()),
+        (
+            "Commit",
+            option_env!("GIT_HASH").map_or_else(unknown, str::to_string),
+        ),
+        ("Features", enabled.join(", ")),
+        (
+            "Platform",
+            format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        ),
+        (
+            "Backend",
+            graphics.map_or_else(unknown, |g| g.backend.to_string()),
+        ),
+        (
+            "Graphics",
+            graphics
+                .and_then(|g| g.driver.clone())
+                .unwrap_or_else(unknown),
+        ),
+    ]
+}
+
+/// The windows under the Help menu.
+pub struct Help {
+    pub getting_started: bool,
+    pub shortcuts: bool,
+    pub about: bool,
+    /// `None` until the window backend reports it.
+    pub graphics: Option<Graphics>,
+}
+
+impl Help {
+    pub fn new() -> Self {
+        Self {
+            getting_started: false,
+            shortcuts: false,
+            about: false,
+            graphics: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Getting Started")
+            .open(&mut self.getting_started)
+            .show(ctx, |ui| {
+                for (i, step) in GETTING_STARTED.iter().enumerate() {
+                    ui.label(format!("{}. {step}", i + 1));
+                }
+            });
+        egui::Window::new("Keyboard Shortcuts")
+            .open(&mut self.shortcuts)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts").show(ui, |ui| {
+                    for (keys, action) in SHORTCUTS {
+                        ui.monospace(*keys);
+                        ui.label(*action);
+                        ui.end_row();
+                    }
+                });
+            });
+        let graphics = self.graphics.as_ref();
+        egui::Window::new(format!("About {APP_NAME}"))
+            .open(&mut self.about)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let diagnostics = diagnostics(graphics);
+                ui.heading(APP_NAME);
+                ui.label("See how Hamming codes encode a message, and how they correct errors.");
+                ui.separator();
+                egui::Grid::new("diagnostics").show(ui, |ui| {
+                    for (name, value) in &diagnostics {
+                        ui.label(*name);
+                        ui.monospace(value);
+                        ui.end_row();
+                    }
+                });
+                // Pasted into bug reports, which are mostly about drawing on one platform or
+                // another.
+                if ui.button("Copy diagnostics").clicked() {
+                    let mut text = format!("{APP_NAME}\n");
+                    for (name, value) in &diagnostics {
+                        text.push_str(&format!("{name}: {value}\n"));
+                    }
+                    ui.output().copied_text = text;
+                }
+            });
+    }
+}
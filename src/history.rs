@@ -0,0 +1,133 @@
+use crate::{analysis, codes::CodeInfo, tolerance};
+use egui::{
+    plot::{Legend, Line, Plot, PlotPoints},
+    Grid,
+};
+
+/// What a run of trials was for: the code, the message, and the error probability. A trial of
+/// anything else starts a new run.
+#[derive(PartialEq)]
+struct Conditions {
+    code: &'static str,
+    message: Vec<u8>,
+    probability: f64,
+}
+
+/// Each press of Randomize Error taken as a trial of whether the message decodes whole, with the
+/// running share of trials that did. Pressing it over and over builds up an estimate of the
+/// success rate.
+pub struct History {
+    pub open: bool,
+    conditions: Option<Conditions>,
+    /// Whether each trial decoded whole, in order.
+    trials: Vec<bool>,
+    /// The chance of a trial decoding whole, worked out from the code.
+    expected: f64,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            conditions: None,
+            trials: Vec::new(),
+            expected: 0.0,
+        }
+    }
+
+    /// Records a trial of `message`, encoded as `encoded`, at `probability`.
+    pub fn record(
+        &mut self,
+        info: &CodeInfo,
+        message: &[u8],
+        encoded: &[u8],
+        probability: f64,
+        success: bool,
+    ) {
+        let conditions = Conditions {
+            code: info.name,
+            message: message.to_vec(),
+            probability,
+        };
+        if self.conditions.as_ref() != Some(&conditions) {
+            self.conditions = Some(conditions);
+            self.trials.clear();
+            let codewords = analysis::codeword_count(info, encoded);
+            self.expected = tolerance::codeword_success(info, probability).powf(codewords as f64);
+        }
+        self.trials.push(success);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Randomization History")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let conditions = match &self.conditions {
+            Some(c) if !self.trials.is_empty() => c,
+            _ => {
+                ui.label(
+                    "Each press of Randomize Error is a trial. Press it a few times to build up \
+                     how often the message decodes whole.",
+                );
+                return;
+            }
+        };
+        let successes = self.trials.iter().filter(|&&s| s).count();
+        let share = successes as f64 / self.trials.len() as f64;
+        Grid::new("history").show(ui, |ui| {
+            ui.label("Code");
+            ui.label(conditions.code);
+            ui.end_row();
+            ui.label("Error probability");
+            ui.label(conditions.probability.to_string());
+            ui.end_row();
+            ui.label("Decoded whole");
+            ui.strong(format!(
+                "{successes} of {} ({:.1}%)",
+                self.trials.len(),
+                share * 100.0
+            ));
+            ui.end_row();
+            ui.label("Expected");
+            ui.label(format!("{:.1}%", self.expected * 100.0));
+            ui.end_row();
+        });
+        if ui.button("Clear").clicked() {
+            self.trials.clear();
+            return;
+        }
+
+        let mut successes = 0;
+        let running: Vec<[f64; 2]> = self
+            .trials
+            .iter()
+            .enumerate()
+            .map(|(i, &success)| {
+                successes += usize::from(success);
+                [(i + 1) as f64, successes as f64 / (i + 1) as f64]
+            })
+            .collect();
+        let end = self.trials.len() as f64;
+        Plot::new("history_plot")
+            .height(200.0)
+            .legend(Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .show(ui, |plot| {
+                plot.line(Line::new(PlotPoints::new(running)).name("Running share"));
+                plot.line(
+                    Line::new(PlotPoints::new(vec![
+                        [1.0, self.expected],
+                        [end.max(2.0), self.expected],
+                    ]))
+                    .name("Expected"),
+                );
+            });
+        ui.small("x: trials, y: share decoded whole");
+    }
+}
@@ -0,0 +1,456 @@
+use crate::{
+    analysis,
+    anim::AnimationSettings,
+    bmp::{self, Bitmap},
+    channel::Transmission,
+    codes::CodeInfo,
+    export,
+    unequal::UnequalProtection,
+};
+use egui::{vec2, Color32, ColorImage, DragValue, TextureFilter, TextureHandle};
+use hamming::Code;
+use instant::Instant;
+use std::fs;
+
+/// The longest side an image is sent at, in pixels. Larger images are shrunk to fit, to keep
+/// encoding quick.
+const MAX_SIDE: usize = 256;
+
+/// The longest side each image is shown at, in points.
+const SHOWN_SIDE: f32 = 256.0;
+
+/// The color of pixels that haven't arrived yet.
+const NOT_ARRIVED: Color32 = Color32::from_gray(40);
+
+/// The first `arrived` pixels of `rgb`, with the rest not arrived yet.
+fn color_image(width: usize, height: usize, rgb: &[u8], arrived: usize) -> ColorImage {
+    let mut pixels: Vec<Color32> = rgb
+        .chunks_exact(3)
+        .take(arrived)
+        .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+        .collect();
+    pixels.resize(width * height, NOT_ARRIVED);
+    ColorImage {
+        size: [width, height],
+        pixels,
+    }
+}
+
+fn texture(
+    ctx: &egui::Context,
+    name: &str,
+    width: usize,
+    height: usize,
+    rgb: &[u8],
+) -> TextureHandle {
+    let image = color_image(width, height, rgb, usize::MAX);
+    // Nearest, so that each damaged pixel stays sharp when scaled up.
+    ctx.load_texture(name, image, TextureFilter::Nearest)
+}
+
+/// The protected image as shown: each codeword's data as decoded where that came out right, and
+/// as received, uncorrected, where it didn't, so that the blocks the code failed on look
+/// glitched.
+fn glitched(info: &CodeInfo, original: &[u8], sent: &Transmission) -> Vec<u8> {
+    let mut shown = sent.protected.clone();
+    shown.resize(original.len(), 0);
+    let bits = original.len() * 8;
+    for start in (0..bits).step_by(info.k) {
+        let block = start..(start + info.k).min(bits);
+        if block
+            .clone()
+            .any(|b| analysis::get_bit(original, b) != analysis::get_bit(&shown, b))
+        {
+            for b in block {
+                analysis::set_bit(&mut shown, b, analysis::get_bit(&sent.protected_raw, b));
+            }
+        }
+    }
+    shown
+}
+
+/// The mean difference between each color value of `original` and `out`, out of 255.
+fn mean_error(original: &[u8], out: &[u8]) -> f64 {
+    let total: u64 = Iterator::zip(original.iter(), out.iter())
+        .map(|(&a, &b)| u64::from(a.abs_diff(b)))
+        .sum();
+    total as f64 / original.len().max(1) as f64
+}
+
+/// An image as loaded.
+struct Picture {
+    name: String,
+    bitmap: Bitmap,
+    /// The size of the file's image, if it was shrunk.
+    shrunk_from: Option<(usize, usize)>,
+    texture: TextureHandle,
+}
+
+/// The image after going through the channel, sent as it is and sent protected by a code.
+struct Results {
+    code: &'static str,
+    /// The code's codeword and data lengths, which set how fast the protected image arrives.
+    n: usize,
+    k: usize,
+    probability: f64,
+    width: usize,
+    height: usize,
+    unprotected_rgb: Vec<u8>,
+    protected_rgb: Vec<u8>,
+    unprotected: TextureHandle,
+    protected: TextureHandle,
+    /// Bits flipped by the channel in each.
+    unprotected_flips: usize,
+    protected_flips: usize,
+    /// Pixels that came out different from the original in each.
+    unprotected_damaged: usize,
+    protected_damaged: usize,
+    /// The mean difference from the original of each color value in each.
+    unprotected_error: f64,
+    protected_error: f64,
+    unequal: Option<Unequal>,
+}
+
+/// The image after going through the channel with unequal protection.
+struct Unequal {
+    name: String,
+    /// Bits sent for each bit of the image, which sets how fast it arrives.
+    overhead: f64,
+    rgb: Vec<u8>,
+    texture: TextureHandle,
+    flips: usize,
+    damaged: usize,
+    error: f64,
+}
+
+impl Results {
+    /// Sends the pixels of `picture` through the channel twice, as they are and protected by
+    /// `code`, and a third time with `unequal` protection if it is enabled.
+    fn new(
+        ctx: &egui::Context,
+        code: &Code,
+        info: &CodeInfo,
+        picture: &Picture,
+        probability: f64,
+        unequal: &UnequalProtection,
+    ) -> Self {
+        let bitmap = &picture.bitmap;
+        let sent = Transmission::new(code, info, &bitmap.rgb, probability);
+        let damaged = |out: &[u8]| {
+            Iterator::zip(bitmap.rgb.chunks(3), out.chunks(3))
+                .filter(|(a, b)| a != b)
+                .count()
+        };
+        let (width, height) = (bitmap.width, bitmap.height);
+        let protected_rgb = glitched(info, &bitmap.rgb, &sent);
+        let unequal = unequal.enabled.then(|| {
+            let arrival = unequal.send(&bitmap.rgb, 8, probability);
+            Unequal {
+                name: unequal.name(),
+                overhead: unequal.overhead(8),
+                texture: texture(ctx, "image_unequal", width, height, &arrival.bytes),
+                flips: arrival.flips,
+                damaged: damaged(&arrival.bytes),
+                error: mean_error(&bitmap.rgb, &arrival.bytes),
+                rgb: arrival.bytes,
+            }
+        });
+        Self {
+            code: info.name,
+            n: info.n,
+            k: info.k,
+            probability,
+            width,
+            height,
+            unprotected: texture(ctx, "image_unprotected", width, height, &sent.unprotected),
+            protected: texture(ctx, "image_protected", width, height, &protected_rgb),
+            unprotected_flips: sent.unprotected_flips,
+            protected_flips: sent.protected_flips,
+            unprotected_damaged: damaged(&sent.unprotected),
+            protected_damaged: damaged(&sent.protected),
+            unprotected_error: mean_error(&bitmap.rgb, &sent.unprotected),
+            protected_error: mean_error(&bitmap.rgb, &sent.protected),
+            unequal,
+            unprotected_rgb: sent.unprotected,
+            protected_rgb,
+        }
+    }
+
+    /// Shows as much of each image as `bits` bits of the channel carry: the unprotected pixels
+    /// one after another, and the protected ones a whole codeword at a time, which takes `n / k`
+    /// as long, and the unequally protected ones as fast as their overhead allows. Returns whether
+    /// all have arrived in full.
+    fn arrive(&mut self, bits: f64) -> bool {
+        let total = self.width * self.height;
+        let bits = bits.min(usize::MAX as f64) as usize;
+        let unprotected = bits / 24;
+        let protected = bits / self.n * self.k / 24;
+        let (width, height) = (self.width, self.height);
+        self.unprotected.set(
+            color_image(width, height, &self.unprotected_rgb, unprotected),
+            TextureFilter::Nearest,
+        );
+        self.protected.set(
+            color_image(width, height, &self.protected_rgb, protected),
+            TextureFilter::Nearest,
+        );
+        let unequal = match &mut self.unequal {
+            Some(unequal) => {
+                let arrived = (bits as f64 / unequal.overhead) as usize / 24;
+                unequal.texture.set(
+                    color_image(width, height, &unequal.rgb, arrived),
+                    TextureFilter::Nearest,
+                );
+                arrived
+            }
+            None => total,
+        };
+        unprotected >= total && protected >= total && unequal >= total
+    }
+}
+
+/// Makes the effect of a code visible at a glance: a small image is sent through the channel
+/// with and without the code's protection, and what arrives is shown side by side.
+pub struct ImageDemo {
+    pub open: bool,
+    picture: Option<Picture>,
+    probability: f64,
+    results: Option<Results>,
+    /// Why the last image couldn't be opened.
+    status: Option<String>,
+    /// Whether the images arrive bit by bit, like a download over a modem, rather than at once.
+    animate: bool,
+    /// The speed of the channel when animating, in bits per second.
+    rate: f64,
+    /// When the images started arriving, while they are.
+    arrival: Option<Instant>,
+    unequal: UnequalProtection,
+}
+
+impl ImageDemo {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            picture: None,
+            probability: 0.001,
+            results: None,
+            status: None,
+            animate: true,
+            rate: 28_800.0,
+            arrival: None,
+            unequal: UnequalProtection::new(),
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        code: &Code,
+        info: &CodeInfo,
+        animation: &AnimationSettings,
+    ) {
+        if let (Some(start), Some(results)) = (self.arrival, &mut self.results) {
+            let bits = if animation.reduce_motion {
+                f64::INFINITY
+            } else {
+                start.elapsed().as_secs_f64() * self.rate * f64::from(animation.speed)
+            };
+            if results.arrive(bits) {
+                self.arrival = None;
+            } else {
+                ctx.request_repaint();
+            }
+        }
+        let mut open = self.open;
+        egui::Window::new("Image")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, code, info));
+        self.open = open;
+    }
+
+    /// Starts the images arriving from nothing.
+    fn replay(&mut self) {
+        if let Some(results) = &mut self.results {
+            results.arrive(0.0);
+            self.arrival = Some(Instant::now());
+        }
+    }
+
+    fn open_picture(&mut self, ctx: &egui::Context) {
+        let path = match export::open_path("BMP image", &["bmp"]) {
+            Some(path) => path,
+            None => return,
+        };
+        let opened = fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| bmp::parse(&bytes));
+        match opened {
+            Ok(bitmap) => {
+                let size = (bitmap.width, bitmap.height);
+                let bitmap = bitmap.fit(MAX_SIDE);
+                let shrunk_from = (bitmap.width, bitmap.height) != size;
+                self.picture = Some(Picture {
+                    name: path
+                        .file_name()
+                        .map_or_else(String::new, |n| n.to_string_lossy().into_owned()),
+                    texture: texture(
+                        ctx,
+                        "image_original",
+                        bitmap.width,
+                        bitmap.height,
+                        &bitmap.rgb,
+                    ),
+                    bitmap,
+                    shrunk_from: shrunk_from.then_some(size),
+                });
+                self.results = None;
+                self.arrival = None;
+                self.status = None;
+            }
+            Err(e) => self.status = Some(e),
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, code: &Code, info: &CodeInfo) {
+        ui.horizontal(|ui| {
+            if ui.button("Open BMP...").clicked() {
+                self.open_picture(ui.ctx());
+            }
+            if let Some(picture) = &self.picture {
+                ui.label(format!(
+                    "{}: {} x {}",
+                    picture.name, picture.bitmap.width, picture.bitmap.height
+                ));
+            }
+        });
+        if let Some((width, height)) = self.picture.as_ref().and_then(|p| p.shrunk_from) {
+            ui.small(format!(
+                "Shrunk from {width} x {height} to send at most {MAX_SIDE} pixels a side."
+            ));
+        }
+        if let Some(e) = &self.status {
+            ui.colored_label(Color32::RED, e.as_str());
+        }
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.0001)
+                    .max_decimals(5),
+            );
+            if ui
+                .add_enabled(
+                    self.picture.is_some(),
+                    egui::Button::new(format!("Send with and without {}", info.name)),
+                )
+                .clicked()
+            {
+                if let Some(picture) = &self.picture {
+                    self.results = Some(Results::new(
+                        ui.ctx(),
+                        code,
+                        info,
+                        picture,
+                        self.probability,
+                        &self.unequal,
+                    ));
+                    if self.animate {
+                        self.replay();
+                    }
+                }
+            }
+        });
+        self.unequal.ui(ui, "byte", 8);
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.animate, "Arrive at");
+            ui.add_enabled(
+                self.animate,
+                DragValue::new(&mut self.rate)
+                    .clamp_range(300.0..=10_000_000.0)
+                    .speed(100.0)
+                    .suffix(" bits/s"),
+            );
+            if ui
+                .add_enabled(
+                    self.animate && self.results.is_some(),
+                    egui::Button::new("Replay"),
+                )
+                .clicked()
+            {
+                self.replay();
+            }
+        });
+        let picture = match &self.picture {
+            Some(picture) => picture,
+            None => {
+                ui.label("Open a small BMP image to see what the channel does to it.");
+                return;
+            }
+        };
+        ui.small("Each pixel is sent as three bytes: red, green and blue.");
+        ui.separator();
+
+        let bitmap = &picture.bitmap;
+        let scale = SHOWN_SIDE / bitmap.width.max(bitmap.height) as f32;
+        let size = vec2(bitmap.width as f32, bitmap.height as f32) * scale;
+        let total = bitmap.width * bitmap.height;
+        let damaged = |damaged: usize| {
+            format!(
+                "{damaged} of {total} pixels damaged ({:.2}%)",
+                damaged as f64 / total.max(1) as f64 * 100.0
+            )
+        };
+        let error = |error: f64| format!("Off by {error:.2} of 255 on average");
+        ui.horizontal_top(|ui| {
+            ui.vertical(|ui| {
+                ui.strong("Original");
+                ui.image(picture.texture.id(), size);
+            });
+            if let Some(results) = &self.results {
+                ui.vertical(|ui| {
+                    ui.strong("Unprotected");
+                    ui.image(results.unprotected.id(), size);
+                    ui.label(format!("{} bits flipped", results.unprotected_flips));
+                    ui.label(damaged(results.unprotected_damaged));
+                    ui.label(error(results.unprotected_error));
+                });
+                ui.vertical(|ui| {
+                    ui.strong(results.code);
+                    ui.image(results.protected.id(), size);
+                    ui.label(format!("{} bits flipped", results.protected_flips));
+                    ui.label(damaged(results.protected_damaged));
+                    ui.label(error(results.protected_error));
+                });
+                if let Some(unequal) = &results.unequal {
+                    ui.vertical(|ui| {
+                        ui.strong(&unequal.name);
+                        ui.image(unequal.texture.id(), size);
+                        ui.label(format!("{} bits flipped", unequal.flips));
+                        ui.label(damaged(unequal.damaged));
+                        ui.label(error(unequal.error));
+                    });
+                }
+            }
+        });
+        if let Some(results) = &self.results {
+            ui.small(format!(
+                "Error probability {} for both. Blocks of {} with more errors than it corrects \
+                 are shown as they were received, uncorrected. With its parity bits the \
+                 protected image takes {:.2} times as long to arrive.",
+                results.probability,
+                results.code,
+                results.n as f64 / results.k as f64
+            ));
+            if let Some(unequal) = &results.unequal {
+                ui.small(format!(
+                    "With unequal protection the high bits of each color value, whose errors \
+                     show most, are sent through the strong code and the low bits weakly or not \
+                     at all: more pixels come out a little off, but fewer glaringly so, and it \
+                     takes {:.2} times as long to arrive.",
+                    unequal.overhead
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{codes::CodeInfo, export};
+use egui::{Color32, Event, ProgressBar};
+use hamming::{encode, Code};
+use instant::Instant;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// Pastes longer than this, in bytes, are confirmed before they go anywhere.
+const CONFIRM_BYTES: usize = 16 * 1024;
+
+/// How much of the frame the chunked pipeline may take.
+const FRAME_BUDGET: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy)]
+enum Choice {
+    File,
+    Truncate,
+    Cancel,
+}
+
+/// Encoded a chunk at a time, so that a huge paste is written out over many frames instead of
+/// freezing one.
+struct Job {
+    text: String,
+    done: usize,
+    chunk: usize,
+    out: BufWriter<File>,
+    path: PathBuf,
+}
+
+impl Job {
+    /// Encodes chunks until the frame budget runs out. Returns `true` once finished.
+    fn step(&mut self, code: &Code) -> Result<bool, String> {
+        let start = Instant::now();
+        while self.done < self.text.len() && start.elapsed() < FRAME_BUDGET {
+            let end = (self.done + self.chunk).min(self.text.len());
+            let encoded = encode(&self.text.as_bytes()[self.done..end], code)
+                .map_err(|e| format!("{e:?}"))?;
+            self.out.write_all(&encoded).map_err(|e| e.to_string())?;
+            self.done = end;
+        }
+        if self.done == self.text.len() {
+            self.out.flush().map_err(|e| e.to_string())?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+/// Catches large pastes into the Original panel, and offers to encode them straight to a file.
+pub struct Ingest {
+    pending: Option<String>,
+    job: Option<Job>,
+    status: Option<Result<String, String>>,
+}
+
+impl Ingest {
+    pub fn new() -> Self {
+        Self {
+            pending: None,
+            job: None,
+            status: None,
+        }
+    }
+
+    /// How far through the chunked job is, if one is running.
+    pub fn progress(&self) -> Option<f32> {
+        self.job
+            .as_ref()
+            .map(|job| job.done as f32 / job.text.len() as f32)
+    }
+
+    /// Takes any oversized paste out of this frame's input before a text field can act on it.
+    pub fn intercept(&mut self, ctx: &egui::Context) {
+        let mut input = ctx.input_mut();
+        let events = std::mem::take(&mut input.events);
+        for event in events {
+            match event {
+                Event::Paste(text) if text.len() > CONFIRM_BYTES => self.pending = Some(text),
+                event => input.events.push(event),
+            }
+        }
+    }
+
+    /// Shows the paste confirmation and the progress of any chunked job. Returns text the user
+    /// chose to paste into the message after all, cut to `keep` bytes.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        code: &Code,
+        info: &CodeInfo,
+        keep: usize,
+    ) -> Option<String> {
+        let mut pasted = None;
+        if let Some(len) = self.pending.as_ref().map(String::len) {
+            let mut choice = None;
+            egui::Window::new("Large Paste")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "The clipboard holds {} KiB. Editing that much in the Original panel \
+                         would freeze the window.",
+                        len / 1024
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button("Encode to a file in chunks").clicked() {
+                            choice = Some(Choice::File);
+                        }
+                        if ui
+                            .button(format!("Paste the first {} KiB", keep / 1024))
+                            .clicked()
+                        {
+                            choice = Some(Choice::Truncate);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            choice = Some(Choice::Cancel);
+                        }
+                    });
+                });
+            match choice {
+                Some(Choice::File) => self.start(info),
+                Some(Choice::Truncate) => {
+                    let mut text = self.pending.take().unwrap();
+                    let mut end = keep.min(text.len());
+                    while !text.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    text.truncate(end);
+                    pasted = Some(text);
+                }
+                Some(Choice::Cancel) => self.pending = None,
+                None => {}
+            }
+        }
+
+        if let Some(job) = &mut self.job {
+            match job.step(code) {
+                Ok(true) => {
+                    self.status = Some(Ok(format!(
+                        "Encoded {} KiB to {}",
+                        job.text.len() / 1024,
+                        job.path.display()
+                    )));
+                    self.job = None;
+                }
+                Ok(false) => ctx.request_repaint(),
+                Err(e) => {
+                    self.status = Some(Err(e));
+                    self.job = None;
+                }
+            }
+        }
+        if self.job.is_none() && self.status.is_none() {
+            return pasted;
+        }
+        let progress = self.progress();
+        let mut open = true;
+        let mut stop = false;
+        egui::Window::new("Chunked Encoding")
+            .open(&mut open)
+            .show(ctx, |ui| match (&self.job, &self.status) {
+                (Some(_), _) => {
+                    ui.add(ProgressBar::new(progress.unwrap_or(0.0)).show_percentage());
+                    stop = ui.button("Stop").clicked();
+                }
+                (None, Some(Ok(msg))) => {
+                    ui.label(msg.as_str());
+                }
+                (None, Some(Err(e))) => {
+                    ui.colored_label(Color32::RED, e.as_str());
+                }
+                (None, None) => {}
+            });
+        if stop {
+            if let Some(job) = self.job.take() {
+                self.status = Some(Err(format!(
+                    "Stopped; {} is incomplete.",
+                    job.path.display()
+                )));
+            }
+        }
+        if !open {
+            self.job = None;
+            self.status = None;
+        }
+        pasted
+    }
+
+    /// Asks where to write the encoded paste and starts encoding it.
+    fn start(&mut self, info: &CodeInfo) {
+        let path = match export::save_path("encoded.bin", "Binary", "bin") {
+            Some(path) => path,
+            None => return,
+        };
+        let text = self.pending.take().unwrap();
+        self.status = None;
+        match File::create(&path) {
+            Ok(file) => {
+                self.job = Some(Job {
+                    text,
+                    done: 0,
+                    // A whole number of codewords per chunk, so chunks encode back to back.
+                    chunk: info.k * 1024,
+                    out: BufWriter::new(file),
+                    path,
+                })
+            }
+            Err(e) => self.status = Some(Err(e.to_string())),
+        }
+    }
+}
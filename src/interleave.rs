@@ -0,0 +1,77 @@
+use crate::{analysis, codes::CodeInfo};
+
+/// The deepest interleaving offered, in codewords.
+pub const MAX_DEPTH: usize = 64;
+
+/// A block interleaver: each block of `depth` codewords is written in as rows and read out by
+/// columns, so that bits next to each other on the channel come from different codewords, and a
+/// burst of up to `depth` flips puts at most one in each. The last block may have fewer rows.
+/// Bits after the last whole codeword are sent as they are.
+#[derive(Clone, Copy, Debug)]
+pub struct Interleaver {
+    n: usize,
+    codewords: usize,
+    depth: usize,
+}
+
+impl Interleaver {
+    /// An interleaver for a stream of `len` bytes of `info` codewords. A depth of 1 leaves the
+    /// stream as it is.
+    pub fn new(info: &CodeInfo, len: usize, depth: usize) -> Self {
+        Self {
+            n: info.n,
+            codewords: len * 8 / info.n,
+            depth: depth.max(1),
+        }
+    }
+
+    /// The rows of the block starting at codeword `first`.
+    fn rows(&self, first: usize) -> usize {
+        self.depth.min(self.codewords - first)
+    }
+
+    /// Where bit `i` of the stream, counted in codeword order, is sent on the channel.
+    pub fn sent_at(&self, i: usize) -> usize {
+        if i >= self.codewords * self.n {
+            return i;
+        }
+        let (j, c) = (i / self.n, i % self.n);
+        let first = j - j % self.depth;
+        first * self.n + c * self.rows(first) + j - first
+    }
+
+    /// The bit of the stream, counted in codeword order, sent at bit `t` of the channel.
+    pub fn source(&self, t: usize) -> usize {
+        if t >= self.codewords * self.n {
+            return t;
+        }
+        let first = t / (self.depth * self.n) * self.depth;
+        let offset = t - first * self.n;
+        let rows = self.rows(first);
+        (first + offset % rows) * self.n + offset / rows
+    }
+
+    /// `bytes` in the order they are sent on the channel.
+    pub fn interleave(&self, bytes: &[u8]) -> Vec<u8> {
+        self.permute(bytes, |i| self.sent_at(i))
+    }
+
+    /// `bytes` as sent on the channel, back in codeword order.
+    pub fn deinterleave(&self, bytes: &[u8]) -> Vec<u8> {
+        self.permute(bytes, |t| self.source(t))
+    }
+
+    /// Moves each bit `i` of `bytes` to `to(i)`.
+    fn permute(&self, bytes: &[u8], to: impl Fn(usize) -> usize) -> Vec<u8> {
+        if self.depth == 1 {
+            return bytes.to_vec();
+        }
+        let mut out = vec![0; bytes.len()];
+        for i in 0..bytes.len() * 8 {
+            if analysis::get_bit(bytes, i) {
+                analysis::set_bit(&mut out, to(i), true);
+            }
+        }
+        out
+    }
+}
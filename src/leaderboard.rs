@@ -0,0 +1,213 @@
+use crate::{platform, storage};
+use egui::{Color32, DragValue, Grid, TextEdit};
+use serde::{Deserialize, Serialize};
+
+const FILE: &str = "leaderboard.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub mode: String,
+    pub score: u32,
+    pub seconds: f64,
+    /// Seconds since the Unix epoch.
+    pub recorded: u64,
+}
+
+/// `secs` since the Unix epoch as a UTC date and time, such as "2022-09-14 13:05".
+fn date(secs: u64) -> String {
+    let (days, rem) = (secs / 86400, secs % 86400);
+    // Days to a civil date, after Howard Hinnant's `civil_from_days`, with eras of 400 years
+    // starting on the 1st of March.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        rem / 3600,
+        rem % 3600 / 60
+    )
+}
+
+/// A score typed in by the instructor, for a round played away from the app.
+struct Manual {
+    name: String,
+    mode: String,
+    score: u32,
+    seconds: f64,
+}
+
+/// Scores from the quiz, challenge, and game modes, kept across runs.
+pub struct Leaderboard {
+    pub open: bool,
+    entries: Vec<Entry>,
+    mode: Option<String>,
+    instructor: bool,
+    confirm_reset: bool,
+    manual: Manual,
+    error: Option<String>,
+}
+
+impl Leaderboard {
+    pub fn load() -> Self {
+        Self {
+            open: false,
+            entries: storage::load(FILE).unwrap_or_default(),
+            mode: None,
+            instructor: false,
+            confirm_reset: false,
+            manual: Manual {
+                name: String::new(),
+                mode: "Quiz".to_string(),
+                score: 0,
+                seconds: 0.0,
+            },
+            error: None,
+        }
+    }
+
+    pub fn record(&mut self, name: &str, mode: &str, score: u32, seconds: f64) {
+        let recorded = platform::unix_time();
+        self.entries.push(Entry {
+            name: name.to_string(),
+            mode: mode.to_string(),
+            score,
+            seconds,
+            recorded,
+        });
+        self.save();
+    }
+
+    fn save(&mut self) {
+        self.error = storage::save(FILE, &self.entries).err();
+    }
+
+    fn modes(&self) -> Vec<String> {
+        let mut modes: Vec<_> = self.entries.iter().map(|e| e.mode.clone()).collect();
+        modes.sort();
+        modes.dedup();
+        modes
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Leaderboard")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let modes = self.modes();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Mode")
+                .selected_text(self.mode.as_deref().unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.mode, None, "All");
+                    for mode in modes {
+                        ui.selectable_value(&mut self.mode, Some(mode.clone()), mode);
+                    }
+                });
+            ui.checkbox(&mut self.instructor, "Instructor view");
+        });
+
+        let mut ranked: Vec<(usize, &Entry)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.mode.as_ref().is_none_or(|m| *m == e.mode))
+            .collect();
+        ranked
+            .sort_by(|(_, a), (_, b)| b.score.cmp(&a.score).then(a.seconds.total_cmp(&b.seconds)));
+
+        let mut remove = None;
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                Grid::new("leaderboard").striped(true).show(ui, |ui| {
+                    ui.strong("#");
+                    ui.strong("Name");
+                    ui.strong("Mode");
+                    ui.strong("Score");
+                    ui.strong("Time");
+                    ui.strong("Recorded (UTC)");
+                    ui.end_row();
+                    for (rank, (i, entry)) in ranked.iter().enumerate() {
+                        ui.label(format!("{}", rank + 1));
+                        ui.label(entry.name.as_str());
+                        ui.label(entry.mode.as_str());
+                        ui.label(format!("{}", entry.score));
+                        ui.label(format!("{:.1} s", entry.seconds));
+                        ui.label(date(entry.recorded));
+                        if self.instructor && ui.small_button("Remove").clicked() {
+                            remove = Some(*i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        if let Some(i) = remove {
+            self.entries.remove(i);
+            self.save();
+        }
+
+        if self.instructor {
+            ui.separator();
+            ui.horizontal(|ui| {
+                let m = &mut self.manual;
+                ui.add(
+                    TextEdit::singleline(&mut m.name)
+                        .hint_text("Name")
+                        .desired_width(100.0),
+                );
+                ui.add(
+                    TextEdit::singleline(&mut m.mode)
+                        .hint_text("Mode")
+                        .desired_width(80.0),
+                );
+                ui.add(DragValue::new(&mut m.score).prefix("score "));
+                ui.add(
+                    DragValue::new(&mut m.seconds)
+                        .clamp_range(0.0..=f64::MAX)
+                        .suffix(" s"),
+                );
+                let ready = !m.name.trim().is_empty() && !m.mode.trim().is_empty();
+                if ui
+                    .add_enabled(ready, egui::Button::new("Add score"))
+                    .clicked()
+                {
+                    let (name, mode) = (m.name.trim().to_string(), m.mode.trim().to_string());
+                    let (score, seconds) = (m.score, m.seconds);
+                    m.name.clear();
+                    self.record(&name, &mode, score, seconds);
+                }
+            });
+            if self.confirm_reset {
+                ui.horizontal(|ui| {
+                    ui.label("Delete all entries?");
+                    if ui.button("Yes").clicked() {
+                        self.entries.clear();
+                        self.mode = None;
+                        self.confirm_reset = false;
+                        self.save();
+                    }
+                    if ui.button("No").clicked() {
+                        self.confirm_reset = false;
+                    }
+                });
+            } else if ui.button("Reset for new class").clicked() {
+                self.confirm_reset = true;
+            }
+        }
+
+        if let Some(e) = &self.error {
+            ui.colored_label(Color32::RED, e.as_str());
+        }
+    }
+}
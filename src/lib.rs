@@ -0,0 +1,81 @@
+//! The Hamming code demo: the app itself, and the encode, error, and decode pipeline as a widget
+//! that can be shown inside any egui app.
+
+mod adaptive;
+mod adversary;
+mod analysis;
+mod anim;
+mod app;
+mod audio;
+#[cfg(feature = "explorers")]
+mod bch;
+#[cfg(feature = "explorers")]
+mod bch_explorer;
+mod bit_grid;
+mod bmp;
+mod bookmarks;
+#[cfg(feature = "bundle")]
+mod bundle;
+mod burst;
+mod channel;
+mod charset;
+mod checker;
+mod checksums;
+pub mod cli;
+mod codes;
+mod coding_gain;
+mod corpus;
+mod corrections;
+mod decoders;
+mod differential;
+mod ensemble;
+mod examples;
+mod exercise;
+mod export;
+mod file_mode;
+mod format;
+#[cfg(feature = "explorers")]
+mod gf;
+#[cfg(feature = "explorers")]
+mod gf_explorer;
+mod help;
+mod history;
+mod image_demo;
+mod ingest;
+mod interleave;
+mod leaderboard;
+mod locale;
+mod manifest;
+pub mod memory;
+mod notes;
+mod overview;
+mod panel_model;
+mod panels;
+mod pipeline;
+pub mod platform;
+mod recent;
+mod reference;
+mod scratchpad;
+mod script;
+mod search;
+mod sensitivity;
+mod session;
+mod settings;
+mod share;
+#[cfg(feature = "simulation")]
+mod simulate;
+#[cfg(feature = "simulation")]
+mod soak;
+mod standard_array;
+mod storage;
+mod telegraph;
+mod tolerance;
+mod unequal;
+mod watch;
+mod wav;
+mod welcome;
+mod widget;
+
+pub use app::App;
+pub use help::Graphics;
+pub use widget::HammingDemoWidget;
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::{env, sync::OnceLock};
+
+/// Languages that write decimals with a comma.
+const COMMA_LANGUAGES: [&str; 24] = [
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv",
+    "nb", "nl", "pl", "pt", "ro", "ru", "sv", "tr",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecimalSeparator {
+    /// Follows the system locale, and accepts either separator when parsing.
+    #[default]
+    Auto,
+    Point,
+    Comma,
+}
+
+impl DecimalSeparator {
+    pub const ALL: [DecimalSeparator; 3] = [
+        DecimalSeparator::Auto,
+        DecimalSeparator::Point,
+        DecimalSeparator::Comma,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DecimalSeparator::Auto => "System locale",
+            DecimalSeparator::Point => "Point (0.5)",
+            DecimalSeparator::Comma => "Comma (0,5)",
+        }
+    }
+
+    /// The separator used when displaying numbers.
+    pub fn char(self) -> char {
+        match self {
+            DecimalSeparator::Auto => system_separator(),
+            DecimalSeparator::Point => '.',
+            DecimalSeparator::Comma => ',',
+        }
+    }
+
+    pub fn parse(self, s: &str) -> Option<f64> {
+        let s = s.trim();
+        let normalized = match self {
+            DecimalSeparator::Auto => s.replace(',', "."),
+            DecimalSeparator::Point if s.contains(',') => return None,
+            DecimalSeparator::Point => s.to_string(),
+            DecimalSeparator::Comma if s.contains('.') => return None,
+            DecimalSeparator::Comma => s.replace(',', "."),
+        };
+        normalized.parse().ok()
+    }
+
+    pub fn format(self, x: f64, precision: usize) -> String {
+        self.localize(format!("{x:.precision$}"))
+    }
+
+    /// `x` with as many digits as it takes to parse back to the same number, for filling in a
+    /// field with a value that didn't come from the user.
+    pub fn format_exact(self, x: f64) -> String {
+        self.localize(x.to_string())
+    }
+
+    /// `s`, written with a point, written with this separator instead.
+    fn localize(self, s: String) -> String {
+        match self.char() {
+            '.' => s,
+            c => s.replace('.', &c.to_string()),
+        }
+    }
+}
+
+/// The separator of the system locale, looked up once since numbers are formatted every frame.
+fn system_separator() -> char {
+    static SEPARATOR: OnceLock<char> = OnceLock::new();
+    *SEPARATOR.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(['_', '.', '-']).next().unwrap_or("");
+        if COMMA_LANGUAGES.contains(&language) {
+            ','
+        } else {
+            '.'
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_point() {
+        assert_eq!(DecimalSeparator::Point.parse("0.25"), Some(0.25));
+        assert_eq!(DecimalSeparator::Point.parse(" 1 "), Some(1.0));
+        assert_eq!(DecimalSeparator::Point.parse("0,25"), None);
+    }
+
+    #[test]
+    fn parse_comma() {
+        assert_eq!(DecimalSeparator::Comma.parse("0,25"), Some(0.25));
+        assert_eq!(DecimalSeparator::Comma.parse("1"), Some(1.0));
+        assert_eq!(DecimalSeparator::Comma.parse("0.25"), None);
+    }
+
+    #[test]
+    fn parse_auto() {
+        assert_eq!(DecimalSeparator::Auto.parse("0.25"), Some(0.25));
+        assert_eq!(DecimalSeparator::Auto.parse("0,25"), Some(0.25));
+        assert_eq!(DecimalSeparator::Auto.parse("a"), None);
+    }
+
+    #[test]
+    fn format_exact_parses_back() {
+        for separator in [DecimalSeparator::Point, DecimalSeparator::Comma] {
+            for x in [0.0, 0.01, 1e-7, 0.123456789] {
+                assert_eq!(separator.parse(&separator.format_exact(x)), Some(x));
+            }
+        }
+    }
+}
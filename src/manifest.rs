@@ -0,0 +1,159 @@
+use bitvec::{order::Lsb0, vec::BitVec};
+use rand::{distributions::Uniform, rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Everything that determines a run: the same manifest always gives the same error pattern and
+/// so the same decode, for settling grading disputes and reproducing bug reports.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The name of a pipeline code, such as `EH16_11`.
+    pub code: String,
+    /// Seeds the error pattern. At most `i64::MAX`, the largest integer TOML can hold.
+    pub seed: u64,
+    /// The chance that each bit of the encoded stream is flipped.
+    pub probability: f64,
+    /// The input file, relative to the manifest.
+    pub input: PathBuf,
+    /// `hash` of the input file, so a changed input is caught rather than replayed.
+    pub input_hash: String,
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let manifest: Manifest = toml::from_str(&s).map_err(|e| e.to_string())?;
+        if !(0.0..=1.0).contains(&manifest.probability) {
+            return Err("The probability must be between 0 and 1.".to_string());
+        }
+        Ok(manifest)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let s = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, s).map_err(|e| e.to_string())
+    }
+
+    /// Reads the input file of the manifest at `path`, checking it is the one hashed.
+    pub fn read_input(&self, path: &Path) -> Result<Vec<u8>, String> {
+        let input_path = path.parent().unwrap_or(Path::new("")).join(&self.input);
+        let input = fs::read(&input_path).map_err(|e| format!("{}: {e}", input_path.display()))?;
+        let found = hash(&input);
+        if found != self.input_hash {
+            return Err(format!(
+                "{} has hash {found}, but the manifest expects {}",
+                input_path.display(),
+                self.input_hash
+            ));
+        }
+        Ok(input)
+    }
+
+    /// The error pattern for an encoded stream of `len` bytes.
+    pub fn error_pattern(&self, len: usize) -> Vec<u8> {
+        error_pattern(self.seed, len, self.probability)
+    }
+}
+
+/// Flips each bit of a `len`-byte pattern with chance `probability`. `StdRng` only changes
+/// between major versions of `rand`, so a seed gives the same pattern for as long as this does.
+pub fn error_pattern(seed: u64, len: usize, probability: f64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut bits = BitVec::<u8, Lsb0>::from_vec(vec![0; len]);
+    let distr = Uniform::new(0.0, 1.0);
+    for mut bit in &mut bits {
+        bit.set(rng.sample(distr) < probability);
+    }
+    bits.into_vec()
+}
+
+/// How many bits a random error flipped, against how many it was expected to.
+#[derive(Clone, Copy, Debug)]
+pub struct FlipCount {
+    pub expected: f64,
+    pub observed: usize,
+    /// Where `observed` falls among the counts the same probability would give, from 0 to 100.
+    /// Half the chance of exactly `observed` is counted as below it, so that a typical count
+    /// sits near 50 whether the distribution is wide or narrow.
+    pub percentile: f64,
+}
+
+impl FlipCount {
+    pub fn new(error: &[u8], probability: f64) -> Self {
+        let bits = error.len() * 8;
+        let observed = error.iter().map(|b| b.count_ones() as usize).sum();
+        Self {
+            expected: bits as f64 * probability,
+            observed,
+            percentile: 100.0 * binomial_mid_cdf(bits, probability, observed),
+        }
+    }
+}
+
+/// `P(X < k) + P(X = k) / 2` for `X ~ Binomial(n, p)`. The probabilities are worked out relative
+/// to the most likely count and summed outwards until they are negligible, which neither
+/// underflows on long streams nor visits every count.
+fn binomial_mid_cdf(n: usize, p: f64, k: usize) -> f64 {
+    if p <= 0.0 || p >= 1.0 || n == 0 {
+        // Every run flips the same number of bits.
+        let certain = if p >= 1.0 { n } else { 0 };
+        return match k.cmp(&certain) {
+            Ordering::Less => 0.0,
+            Ordering::Equal => 0.5,
+            Ordering::Greater => 1.0,
+        };
+    }
+    let mode = (((n + 1) as f64 * p).floor() as usize).min(n);
+    let odds = p / (1.0 - p);
+    let weight = |i: usize| match i.cmp(&k) {
+        Ordering::Less => 1.0,
+        Ordering::Equal => 0.5,
+        Ordering::Greater => 0.0,
+    };
+    let (mut total, mut below) = (1.0, weight(mode));
+    let mut term = 1.0;
+    for i in (0..mode).rev() {
+        term *= (i + 1) as f64 / ((n - i) as f64 * odds);
+        total += term;
+        below += term * weight(i);
+        if term < total * f64::EPSILON {
+            break;
+        }
+    }
+    term = 1.0;
+    for i in mode + 1..=n {
+        term *= (n - i + 1) as f64 * odds / i as f64;
+        total += term;
+        below += term * weight(i);
+        if term < total * f64::EPSILON {
+            break;
+        }
+    }
+    below / total
+}
+
+/// A new seed that fits in a manifest.
+pub fn new_seed(rng: &mut impl Rng) -> u64 {
+    rng.gen_range(0..=i64::MAX as u64)
+}
+
+/// A seed as typed, if it is a whole number that fits in a manifest, so that a run drawn from
+/// it can always be saved and replayed.
+pub fn parse_seed(s: &str) -> Option<u64> {
+    s.parse::<u64>()
+        .ok()
+        .filter(|&seed| seed <= i64::MAX as u64)
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, in hex. Not cryptographic, but stable across platforms
+/// and releases, which is all that matters for spotting a changed input.
+pub fn hash(bytes: &[u8]) -> String {
+    let h = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{h:016x}")
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "alloc-stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting what passes through it.
+#[cfg(feature = "alloc-stats")]
+pub struct Tracking;
+
+#[cfg(feature = "alloc-stats")]
+fn grew(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for Tracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            grew(layout.size());
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            grew(layout.size());
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+            grew(new_size);
+        }
+        p
+    }
+}
+
+/// What the allocator has handed out.
+#[derive(Clone, Copy)]
+pub struct Stats {
+    /// Bytes allocated and not yet freed.
+    pub current: usize,
+    /// The most bytes ever allocated at once.
+    pub peak: usize,
+    /// Allocations made so far, reallocations included.
+    pub allocations: usize,
+}
+
+/// The allocator's counts, if this build tracks them.
+pub fn stats() -> Option<Stats> {
+    cfg!(feature = "alloc-stats").then(|| Stats {
+        current: CURRENT.load(Relaxed),
+        peak: PEAK.load(Relaxed),
+        allocations: ALLOCATIONS.load(Relaxed),
+    })
+}
@@ -0,0 +1,133 @@
+use egui_miniquad as egui_mq;
+use hamming_gui::{platform, App, Graphics};
+use miniquad as mq;
+use std::ffi::CStr;
+
+// miniquad's bindings don't all have these.
+const GL_VENDOR: u32 = 0x1F00;
+const GL_RENDERER: u32 = 0x1F01;
+const GL_VERSION: u32 = 0x1F02;
+
+/// The OpenGL vendor, renderer, and version, as the driver describes them.
+fn gl_driver() -> String {
+    let get = |name| {
+        // The strings belong to the driver and live as long as the context.
+        let s = unsafe { mq::gl::glGetString(name) };
+        if s.is_null() {
+            "unknown".to_string()
+        } else {
+            unsafe { CStr::from_ptr(s.cast()) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    };
+    format!(
+        "{} {}, OpenGL {}",
+        get(GL_VENDOR),
+        get(GL_RENDERER),
+        get(GL_VERSION)
+    )
+}
+
+struct Stage {
+    egui_mq: egui_mq::EguiMq,
+    app: App,
+}
+
+impl Stage {
+    fn new(ctx: &mut mq::Context, mut app: App) -> Self {
+        // miniquad makes its context current before it asks for the stage.
+        app.set_graphics(Graphics {
+            backend: "miniquad",
+            driver: Some(gl_driver()),
+        });
+        Self {
+            egui_mq: egui_mq::EguiMq::new(ctx),
+            app,
+        }
+    }
+}
+
+impl mq::EventHandler for Stage {
+    fn update(&mut self, _ctx: &mut mq::Context) {}
+
+    fn draw(&mut self, mq_ctx: &mut mq::Context) {
+        mq_ctx.clear(Some((1., 1., 1., 1.)), None, None);
+        mq_ctx.begin_default_pass(mq::PassAction::clear_color(0.2, 0.2, 0.2, 1.0));
+        mq_ctx.end_render_pass();
+
+        self.app.update();
+        self.egui_mq
+            .run(mq_ctx, |_mq_ctx, egui_ctx| self.app.ui(egui_ctx));
+
+        self.egui_mq.draw(mq_ctx);
+        mq_ctx.commit_frame();
+    }
+
+    fn mouse_motion_event(&mut self, _: &mut mq::Context, x: f32, y: f32) {
+        self.egui_mq.mouse_motion_event(x, y);
+    }
+
+    fn mouse_wheel_event(&mut self, _: &mut mq::Context, dx: f32, dy: f32) {
+        self.egui_mq.mouse_wheel_event(dx, dy);
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut mq::Context,
+        mb: mq::MouseButton,
+        x: f32,
+        y: f32,
+    ) {
+        self.egui_mq.mouse_button_down_event(ctx, mb, x, y);
+    }
+
+    fn mouse_button_up_event(
+        &mut self,
+        ctx: &mut mq::Context,
+        mb: mq::MouseButton,
+        x: f32,
+        y: f32,
+    ) {
+        self.egui_mq.mouse_button_up_event(ctx, mb, x, y);
+    }
+
+    fn char_event(
+        &mut self,
+        _ctx: &mut mq::Context,
+        character: char,
+        _keymods: mq::KeyMods,
+        _repeat: bool,
+    ) {
+        self.egui_mq.char_event(character);
+    }
+
+    fn key_down_event(
+        &mut self,
+        ctx: &mut mq::Context,
+        keycode: mq::KeyCode,
+        keymods: mq::KeyMods,
+        _repeat: bool,
+    ) {
+        self.egui_mq.key_down_event(ctx, keycode, keymods);
+    }
+
+    fn key_up_event(&mut self, _ctx: &mut mq::Context, keycode: mq::KeyCode, keymods: mq::KeyMods) {
+        self.egui_mq.key_up_event(keycode, keymods);
+    }
+}
+
+/// Runs the app with miniquad, which can't change the window title once the window is open.
+pub fn run(app: App) {
+    let conf = mq::conf::Conf {
+        window_title: app.window_title(),
+        high_dpi: true,
+        icon: Some(mq::conf::Icon {
+            small: platform::icon(16).try_into().unwrap(),
+            medium: platform::icon(32).try_into().unwrap(),
+            big: platform::icon(64).try_into().unwrap(),
+        }),
+        ..Default::default()
+    };
+    mq::start(conf, |ctx| Box::new(Stage::new(ctx, app)));
+}
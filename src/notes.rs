@@ -0,0 +1,119 @@
+use crate::session::Notes;
+use egui::{DragValue, Grid, TextEdit};
+use std::collections::BTreeMap;
+
+/// What a new note is attached to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target {
+    Block,
+    Bit,
+}
+
+impl Target {
+    const ALL: [Target; 2] = [Target::Block, Target::Bit];
+
+    fn name(self) -> &'static str {
+        match self {
+            Target::Block => "Codeword",
+            Target::Bit => "Bit",
+        }
+    }
+}
+
+/// Edits the notes saved with the session.
+pub struct NotesWindow {
+    pub open: bool,
+    target: Target,
+    index: usize,
+}
+
+impl NotesWindow {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            target: Target::Block,
+            index: 0,
+        }
+    }
+
+    /// Shows the editor. `blocks` and `bits` are the sizes of the encoded stream.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        notes: &mut Notes,
+        blocks: usize,
+        bits: usize,
+        read_only: bool,
+    ) {
+        let mut open = self.open;
+        egui::Window::new("Notes").open(&mut open).show(ctx, |ui| {
+            ui.add_enabled_ui(!read_only, |ui| self.ui(ui, notes, blocks, bits));
+        });
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, notes: &mut Notes, blocks: usize, bits: usize) {
+        ui.label("Session");
+        ui.add(
+            TextEdit::multiline(&mut notes.session)
+                .hint_text("Shown when hovering over Notes")
+                .desired_rows(3),
+        );
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            for t in Target::ALL {
+                ui.radio_value(&mut self.target, t, t.name());
+            }
+            let count = match self.target {
+                Target::Block => blocks,
+                Target::Bit => bits,
+            };
+            ui.add(DragValue::new(&mut self.index).clamp_range(0..=count.saturating_sub(1)));
+            let map = self.map(notes);
+            if ui
+                .add_enabled(
+                    count > 0 && !map.contains_key(&self.index),
+                    egui::Button::new("Add note"),
+                )
+                .clicked()
+            {
+                map.insert(self.index, String::new());
+            }
+        });
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (target, map) in [
+                    (Target::Block, &mut notes.blocks),
+                    (Target::Bit, &mut notes.bits),
+                ] {
+                    let mut remove = None;
+                    Grid::new(target.name()).num_columns(3).show(ui, |ui| {
+                        for (&i, note) in map.iter_mut() {
+                            ui.label(format!("{} {i}", target.name()));
+                            ui.add(TextEdit::singleline(note).hint_text("Note"));
+                            if ui.small_button("✕").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if let Some(i) = remove {
+                        map.remove(&i);
+                    }
+                }
+            });
+        if notes.blocks.is_empty() && notes.bits.is_empty() {
+            ui.label("No codeword or bit notes yet.");
+        }
+    }
+
+    fn map<'a>(&self, notes: &'a mut Notes) -> &'a mut BTreeMap<usize, String> {
+        match self.target {
+            Target::Block => &mut notes.blocks,
+            Target::Bit => &mut notes.bits,
+        }
+    }
+}
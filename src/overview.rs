@@ -0,0 +1,136 @@
+use crate::panels::PanelId;
+use egui::{vec2, Align2, Color32, FontId, Rect, Sense, Stroke};
+
+const BOX: egui::Vec2 = vec2(130.0, 64.0);
+const GAP: f32 = 96.0;
+
+/// What the overview shows on the box for one stage.
+pub struct StageSummary {
+    pub id: PanelId,
+    pub detail: String,
+    pub status: Option<(String, Color32)>,
+    /// How many bytes leave this stage for the next one.
+    pub output_bytes: usize,
+}
+
+/// A diagram of the whole pipeline, with a box per stage that jumps to its panel when clicked.
+pub struct Overview {
+    pub open: bool,
+    zoom: f32,
+}
+
+impl Overview {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            zoom: 1.0,
+        }
+    }
+
+    /// Returns the stage whose box was clicked.
+    pub fn show(&mut self, ctx: &egui::Context, stages: &[StageSummary]) -> Option<PanelId> {
+        let mut open = self.open;
+        let mut clicked = None;
+        egui::Window::new("Pipeline Overview")
+            .open(&mut open)
+            .default_width(4.0 * (BOX.x + GAP))
+            .show(ctx, |ui| clicked = self.ui(ui, stages));
+        self.open = open;
+        clicked
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &[StageSummary]) -> Option<PanelId> {
+        ui.add(egui::Slider::new(&mut self.zoom, 0.5..=2.5).text("Zoom"));
+
+        let mut clicked = None;
+        egui::ScrollArea::both().show(ui, |ui| {
+            let zoom = self.zoom;
+            let pitch = (BOX.x + GAP) * zoom;
+            let size =
+                vec2(pitch * stages.len() as f32 - GAP * zoom, BOX.y * zoom) + vec2(8.0, 8.0);
+            let (response, painter) = ui.allocate_painter(size, Sense::click());
+            if response.hovered() {
+                let zoom_delta = ui.input().zoom_delta();
+                self.zoom = (self.zoom * zoom_delta).clamp(0.5, 2.5);
+            }
+
+            let origin = response.rect.min + vec2(4.0, 4.0);
+            // Expansion ratios are relative to what enters the first stage.
+            let input_bytes = stages.first().map_or(0, |s| s.output_bytes);
+            let visuals = ui.visuals();
+            let title_font = FontId::proportional(14.0 * zoom);
+            let detail_font = FontId::proportional(11.0 * zoom);
+            for (i, stage) in stages.iter().enumerate() {
+                let rect = Rect::from_min_size(origin + vec2(i as f32 * pitch, 0.0), BOX * zoom);
+                let hovered = response.hover_pos().is_some_and(|pos| rect.contains(pos));
+                let fill = if hovered {
+                    visuals.widgets.hovered.bg_fill
+                } else {
+                    visuals.widgets.inactive.bg_fill
+                };
+                painter.rect(rect, 4.0 * zoom, fill, visuals.widgets.inactive.bg_stroke);
+
+                let mut y = rect.top() + 14.0 * zoom;
+                painter.text(
+                    egui::pos2(rect.center().x, y),
+                    Align2::CENTER_CENTER,
+                    stage.id.title(),
+                    title_font.clone(),
+                    visuals.strong_text_color(),
+                );
+                y += 18.0 * zoom;
+                painter.text(
+                    egui::pos2(rect.center().x, y),
+                    Align2::CENTER_CENTER,
+                    &stage.detail,
+                    detail_font.clone(),
+                    visuals.text_color(),
+                );
+                if let Some((status, color)) = &stage.status {
+                    y += 16.0 * zoom;
+                    painter.text(
+                        egui::pos2(rect.center().x, y),
+                        Align2::CENTER_CENTER,
+                        status,
+                        detail_font.clone(),
+                        *color,
+                    );
+                }
+
+                if i + 1 < stages.len() {
+                    let from = rect.right_center() + vec2(4.0 * zoom, 0.0);
+                    let length = GAP * zoom - 8.0 * zoom;
+                    painter.arrow(
+                        from,
+                        vec2(length, 0.0),
+                        Stroke::new(2.0, visuals.text_color()),
+                    );
+
+                    let bytes = stage.output_bytes;
+                    let label_pos = from + vec2(length / 2.0, 0.0);
+                    painter.text(
+                        label_pos - vec2(0.0, 4.0 * zoom),
+                        Align2::CENTER_BOTTOM,
+                        format!("{bytes} B / {} bits", bytes * 8),
+                        detail_font.clone(),
+                        visuals.text_color(),
+                    );
+                    if input_bytes > 0 {
+                        painter.text(
+                            label_pos + vec2(0.0, 4.0 * zoom),
+                            Align2::CENTER_TOP,
+                            format!("×{:.2}", bytes as f64 / input_bytes as f64),
+                            detail_font.clone(),
+                            visuals.weak_text_color(),
+                        );
+                    }
+                }
+
+                if hovered && response.clicked() {
+                    clicked = Some(stage.id);
+                }
+            }
+        });
+        clicked
+    }
+}
@@ -0,0 +1,493 @@
+use crate::{
+    analysis,
+    charset::Alphabet,
+    codes::CodeInfo,
+    corrections::Corrections,
+    decoders::{Algorithm, Outcome},
+    format::{self, InputFormat},
+    interleave::Interleaver,
+    overview::StageSummary,
+    panels::{self, BinaryText, ByteDisplay, ErrorText, PanelId, ParityOverlay},
+    search::Highlights,
+    session::Notes,
+};
+use egui::Color32;
+
+/// The parts of the app the panels show and edit, borrowed for a frame.
+pub struct Stages<'a> {
+    /// The message as typed, in `input_format`.
+    pub message_in: &'a mut String,
+    pub input_format: &'a mut InputFormat,
+    /// The bytes of the message.
+    pub message: &'a [u8],
+    /// Why the message as typed doesn't parse, if it doesn't.
+    pub parse_error: Option<&'a str>,
+    /// The length of an oversized message before it was cut short.
+    pub truncated_from: Option<usize>,
+    pub alphabet: &'a mut Alphabet,
+    pub encoded: &'a [u8],
+    /// Codewords per block of the interleaver, 1 for none.
+    pub interleave_depth: &'a mut usize,
+    pub error: &'a mut [u8],
+    pub with_error: &'a [u8],
+    pub message_out: &'a mut Option<String>,
+    pub algorithm: &'a mut Algorithm,
+    pub code_info: &'a CodeInfo,
+    pub outcomes: &'a [Outcome],
+    /// What the chosen decoder corrected.
+    pub corrections: &'a Corrections,
+    /// How the byte panels write their bytes.
+    pub byte_display: ByteDisplay,
+    pub parity_overlay: &'a mut ParityOverlay,
+    pub hovered_bit: &'a mut Option<usize>,
+    pub notes: &'a Notes,
+}
+
+/// How a panel is to be shown this frame.
+pub struct View<'a> {
+    pub highlights: Option<Highlights<'a>>,
+    /// A byte to bring into view.
+    pub scroll_to: Option<usize>,
+    pub read_only: bool,
+}
+
+/// A panel of the main window: the stage it shows, how that stage is summarized, and how it is
+/// drawn and edited. Panels keep whatever they need between frames themselves.
+pub trait PanelModel {
+    fn id(&self) -> PanelId;
+    /// The bytes this stage passes on, which are what Find searches.
+    fn output(&self, stages: &Stages) -> Vec<u8>;
+    /// How the stage is doing, for the overview. `None` for panels that aren't a stage of the
+    /// pipeline.
+    fn summary(&self, _stages: &Stages) -> Option<StageSummary> {
+        None
+    }
+    /// The panel's contents as copied to the clipboard: its output in the notation the byte
+    /// panels are written in.
+    fn copy_text(&self, stages: &Stages) -> String {
+        stages
+            .byte_display
+            .notation(*stages.input_format)
+            .text(&self.output(stages))
+    }
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View);
+}
+
+/// Every panel, in pipeline order.
+pub fn all() -> Vec<Box<dyn PanelModel>> {
+    vec![
+        Box::new(Original),
+        Box::new(Encoded::default()),
+        Box::new(Interleaved::default()),
+        Box::new(Error::default()),
+        Box::new(WithError::default()),
+        Box::new(Decoded::default()),
+        Box::new(CorrectionsPanel),
+        Box::new(Syndromes),
+    ]
+}
+
+fn byte_count(id: PanelId, bytes: &[u8]) -> StageSummary {
+    StageSummary {
+        id,
+        detail: format!("{} bytes", bytes.len()),
+        status: None,
+        output_bytes: bytes.len(),
+    }
+}
+
+struct Original;
+
+impl PanelModel for Original {
+    fn id(&self) -> PanelId {
+        PanelId::Original
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        stages.message.to_vec()
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        Some(byte_count(self.id(), stages.message))
+    }
+
+    /// The message as typed, in the format it is typed in.
+    fn copy_text(&self, stages: &Stages) -> String {
+        stages.message_in.clone()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        panels::input_format(
+            ui,
+            stages.message_in,
+            stages.input_format,
+            stages.message,
+            stages.parse_error,
+            view.read_only,
+        );
+        panels::original(
+            ui,
+            stages.message_in,
+            stages.input_format,
+            stages.alphabet,
+            stages.truncated_from,
+            view.highlights,
+            view.read_only,
+        );
+    }
+}
+
+#[derive(Default)]
+struct Encoded {
+    text: BinaryText,
+}
+
+impl PanelModel for Encoded {
+    fn id(&self) -> PanelId {
+        PanelId::Encoded
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        stages.encoded.to_vec()
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        Some(byte_count(self.id(), stages.encoded))
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        self.text
+            .set_notation(stages.byte_display.notation(*stages.input_format));
+        panels::encoded(ui, stages, view, &mut self.text);
+    }
+}
+
+/// The stream as sent on the channel, errors and all.
+#[derive(Default)]
+struct Interleaved {
+    sent: Vec<u8>,
+    /// The received stream and depth `sent` was worked out for.
+    sent_for: Option<(Vec<u8>, usize)>,
+}
+
+impl Interleaved {
+    fn update(&mut self, stages: &Stages) {
+        let depth = *stages.interleave_depth;
+        if !matches!(&self.sent_for, Some((w, d)) if w == stages.with_error && *d == depth) {
+            let interleaver = Interleaver::new(stages.code_info, stages.with_error.len(), depth);
+            self.sent = interleaver.interleave(stages.with_error);
+            self.sent_for = Some((stages.with_error.to_vec(), depth));
+        }
+    }
+}
+
+impl PanelModel for Interleaved {
+    fn id(&self) -> PanelId {
+        PanelId::Interleaved
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        Interleaver::new(
+            stages.code_info,
+            stages.with_error.len(),
+            *stages.interleave_depth,
+        )
+        .interleave(stages.with_error)
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        Some(StageSummary {
+            detail: format!(
+                "{} bytes, depth {}",
+                stages.with_error.len(),
+                stages.interleave_depth
+            ),
+            ..byte_count(self.id(), stages.with_error)
+        })
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        self.update(stages);
+        panels::interleaved(
+            ui,
+            &self.sent,
+            stages.error,
+            stages.code_info,
+            stages.interleave_depth,
+            view.scroll_to,
+            view.read_only,
+        );
+    }
+}
+
+#[derive(Default)]
+struct Error {
+    text: ErrorText,
+}
+
+impl PanelModel for Error {
+    fn id(&self) -> PanelId {
+        PanelId::Error
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        stages.error.to_vec()
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        let flipped: u32 = stages.error.iter().map(|b| b.count_ones()).sum();
+        Some(StageSummary {
+            id: self.id(),
+            detail: format!("{flipped} bits flipped"),
+            status: None,
+            output_bytes: stages.error.len(),
+        })
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        self.text
+            .set_notation(stages.byte_display.notation(*stages.input_format));
+        panels::error(
+            ui,
+            stages.error,
+            stages.code_info,
+            &mut self.text,
+            view.highlights,
+            view.scroll_to,
+            view.read_only,
+        );
+    }
+}
+
+struct WithError {
+    text: BinaryText,
+    /// Whether to show the bits as a grid marked with the decoder's corrections, rather than as
+    /// text.
+    show_corrections: bool,
+}
+
+impl Default for WithError {
+    fn default() -> Self {
+        Self {
+            text: BinaryText::default(),
+            show_corrections: true,
+        }
+    }
+}
+
+impl PanelModel for WithError {
+    fn id(&self) -> PanelId {
+        PanelId::WithError
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        stages.with_error.to_vec()
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        Some(byte_count(self.id(), stages.with_error))
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        ui.checkbox(&mut self.show_corrections, "Show corrections");
+        self.text
+            .set_notation(stages.byte_display.notation(*stages.input_format));
+        if self.show_corrections {
+            panels::with_error(
+                ui,
+                stages.with_error,
+                stages.corrections,
+                stages.code_info,
+                *stages.parity_overlay,
+                view.highlights,
+                view.scroll_to,
+            );
+        } else {
+            panels::bytes(
+                ui,
+                self.id(),
+                stages.with_error,
+                &mut self.text,
+                view.highlights,
+                view.scroll_to,
+            );
+        }
+    }
+}
+
+/// How decoded bytes that aren't valid UTF-8 are shown, when the message is typed as text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum RawView {
+    #[default]
+    Hex,
+    Lossy,
+}
+
+impl RawView {
+    const ALL: [RawView; 2] = [RawView::Hex, RawView::Lossy];
+
+    fn name(self) -> &'static str {
+        match self {
+            RawView::Hex => "Hex",
+            RawView::Lossy => "Text with \u{FFFD} for bad bytes",
+        }
+    }
+
+    fn write(self, bytes: &[u8]) -> String {
+        match self {
+            RawView::Hex => {
+                let mut s = String::new();
+                format::write_hex(bytes, &mut s);
+                s
+            }
+            RawView::Lossy => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Decoded {
+    raw: RawView,
+}
+
+impl Decoded {
+    /// The decoded bytes in the raw view, when they decoded but aren't valid UTF-8 to show as
+    /// text. Showing them anyway beats a blanket failure: a single flipped bit in a multibyte
+    /// character is enough to make the whole message invalid.
+    fn fallback(&self, stages: &Stages) -> Option<String> {
+        if stages.message_out.is_some() {
+            return None;
+        }
+        decoded(stages).map(|bytes| self.raw.write(bytes))
+    }
+}
+
+impl PanelModel for Decoded {
+    fn id(&self) -> PanelId {
+        PanelId::Decoded
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        decoded(stages).map_or_else(Vec::new, <[u8]>::to_vec)
+    }
+
+    fn summary(&self, stages: &Stages) -> Option<StageSummary> {
+        let decoded = decoded(stages);
+        let matches = decoded == Some(stages.message);
+        Some(StageSummary {
+            id: self.id(),
+            detail: match decoded {
+                Some(m) => format!("{} bytes", m.len()),
+                None => match stages
+                    .outcomes
+                    .iter()
+                    .find(|o| o.algorithm == *stages.algorithm)
+                    .and_then(|o| o.partial.as_ref())
+                {
+                    Some(p) => format!("{} of {} codewords failed", p.failed.len(), p.codewords),
+                    None => "undecodable".to_string(),
+                },
+            },
+            status: Some(if matches {
+                ("matches original".to_string(), Color32::GREEN)
+            } else {
+                ("differs from original".to_string(), Color32::RED)
+            }),
+            output_bytes: decoded.map_or(0, <[u8]>::len),
+        })
+    }
+
+    /// The decoded message, in the format the message is typed in, or as shown if it isn't
+    /// valid in it.
+    fn copy_text(&self, stages: &Stages) -> String {
+        stages
+            .message_out
+            .clone()
+            .or_else(|| self.fallback(stages))
+            .unwrap_or_default()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        if self.fallback(stages).is_some() {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::YELLOW, "Not valid UTF-8, so shown as");
+                for raw in RawView::ALL {
+                    ui.radio_value(&mut self.raw, raw, raw.name());
+                }
+            });
+        }
+        let mut fallback = self.fallback(stages);
+        // Matches are found in the decoded bytes, which only line up with the text when it is
+        // the text.
+        let highlights = view
+            .highlights
+            .filter(|_| *stages.input_format == InputFormat::Text && fallback.is_none());
+        let message_out = if fallback.is_some() {
+            &mut fallback
+        } else {
+            &mut *stages.message_out
+        };
+        panels::decoded(
+            ui,
+            message_out,
+            stages.algorithm,
+            stages.code_info,
+            stages.outcomes,
+            stages.message,
+            highlights,
+        );
+    }
+}
+
+/// What the chosen decoder decoded the message to, if it could.
+fn decoded<'a>(stages: &'a Stages) -> Option<&'a [u8]> {
+    stages
+        .outcomes
+        .iter()
+        .find(|o| o.algorithm == *stages.algorithm)
+        .and_then(|o| o.decoded.as_deref())
+}
+
+struct CorrectionsPanel;
+
+impl PanelModel for CorrectionsPanel {
+    fn id(&self) -> PanelId {
+        PanelId::Corrections
+    }
+
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        stages.corrections.flipped.clone()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        panels::corrections(
+            ui,
+            stages.error,
+            stages.corrections,
+            stages.code_info,
+            *stages.parity_overlay,
+            view.scroll_to,
+        );
+    }
+}
+
+struct Syndromes;
+
+impl PanelModel for Syndromes {
+    fn id(&self) -> PanelId {
+        PanelId::Syndromes
+    }
+
+    /// One byte per codeword, its syndrome.
+    fn output(&self, stages: &Stages) -> Vec<u8> {
+        let info = stages.code_info;
+        (0..analysis::codeword_count(info, stages.with_error))
+            .filter_map(|j| analysis::codeword(info, stages.with_error, j))
+            .map(|word| analysis::check(info, &word).syndrome as u8)
+            .collect()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, stages: &mut Stages, view: View) {
+        panels::syndromes(ui, stages.with_error, stages.code_info, view.scroll_to);
+    }
+}
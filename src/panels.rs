@@ -0,0 +1,1260 @@
+use crate::{
+    analysis,
+    bit_grid::{self, bit_grid},
+    charset::Alphabet,
+    codes::CodeInfo,
+    corrections::{self, Corrections},
+    decoders::{Algorithm, Outcome, Partial},
+    examples::EXAMPLES,
+    format::{self, InputFormat},
+    interleave::{self, Interleaver},
+    panel_model::{Stages, View},
+    search::Highlights,
+};
+use egui::{
+    text::LayoutJob, vec2, Align, Color32, DragValue, Grid, Label, Layout, Rect, RichText, Sense,
+    TextEdit, TextFormat, TextStyle,
+};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, ops::Range, time::Duration};
+
+/// The width of a full-length block decode time bar.
+const BAR_WIDTH: f32 = 100.0;
+
+/// How the pipeline stages are arranged in the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Arrangement {
+    /// One side panel per stage, left to right.
+    #[default]
+    Columns,
+    /// Stages stacked top to bottom with arrows between them.
+    Pipeline,
+}
+
+impl Arrangement {
+    pub const ALL: [Arrangement; 2] = [Arrangement::Columns, Arrangement::Pipeline];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Arrangement::Columns => "Columns",
+            Arrangement::Pipeline => "Vertical pipeline",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PanelId {
+    Original,
+    Encoded,
+    Interleaved,
+    Error,
+    WithError,
+    Decoded,
+    Corrections,
+    Syndromes,
+}
+
+impl PanelId {
+    pub const ALL: [PanelId; 8] = [
+        PanelId::Original,
+        PanelId::Encoded,
+        PanelId::Interleaved,
+        PanelId::Error,
+        PanelId::WithError,
+        PanelId::Decoded,
+        PanelId::Corrections,
+        PanelId::Syndromes,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            PanelId::Original => "original",
+            PanelId::Encoded => "encoded",
+            PanelId::Interleaved => "interleaved",
+            PanelId::Error => "error",
+            PanelId::WithError => "with_error",
+            PanelId::Decoded => "decoded",
+            PanelId::Corrections => "corrections",
+            PanelId::Syndromes => "syndromes",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            PanelId::Original => "Original",
+            PanelId::Encoded => "Encoded",
+            PanelId::Interleaved => "Interleaved",
+            PanelId::Error => "Error",
+            PanelId::WithError => "Encoded with Error",
+            PanelId::Decoded => "Decoded",
+            PanelId::Corrections => "Corrections",
+            PanelId::Syndromes => "Syndromes",
+        }
+    }
+}
+
+/// A selector for the format the message is typed in. Switching rewrites the message in the new
+/// format; text is only offered when the message's bytes are UTF-8.
+pub fn input_format(
+    ui: &mut egui::Ui,
+    message: &mut String,
+    format: &mut InputFormat,
+    bytes: &[u8],
+    parse_error: Option<&str>,
+    read_only: bool,
+) {
+    let mut selected = *format;
+    ui.add_enabled_ui(!read_only, |ui| {
+        egui::ComboBox::from_label("Input format")
+            .selected_text(format.name())
+            .show_ui(ui, |ui| {
+                for f in InputFormat::ALL {
+                    let convertible = f.write(bytes).is_some();
+                    ui.add_enabled_ui(convertible, |ui| {
+                        ui.selectable_value(&mut selected, f, f.name())
+                            .on_disabled_hover_text("The message isn't valid UTF-8.");
+                    });
+                }
+            });
+    });
+    if selected != *format {
+        if let Some(text) = selected.write(bytes) {
+            *message = text;
+            *format = selected;
+        }
+    }
+    if let Some(e) = parse_error {
+        ui.colored_label(Color32::RED, e);
+    }
+}
+
+/// Shows the message being sent. `truncated_from` is the length of an oversized message before
+/// it was cut short.
+pub fn original(
+    ui: &mut egui::Ui,
+    message: &mut String,
+    format: &mut InputFormat,
+    alphabet: &mut Alphabet,
+    truncated_from: Option<usize>,
+    highlights: Option<Highlights>,
+    read_only: bool,
+) {
+    egui::ComboBox::from_label("Alphabet")
+        .selected_text(alphabet.name())
+        .show_ui(ui, |ui| {
+            for a in Alphabet::ALL {
+                ui.selectable_value(alphabet, a, a.name());
+            }
+        });
+    ui.add_enabled_ui(!read_only, |ui| {
+        egui::ComboBox::from_label("Example")
+            .selected_text("Load...")
+            .show_ui(ui, |ui| {
+                for e in &EXAMPLES {
+                    if ui.selectable_label(false, e.name).clicked() {
+                        *message = e.text.to_string();
+                        *format = InputFormat::Text;
+                    }
+                }
+            });
+    });
+    if let Some(e) = EXAMPLES.iter().find(|e| e.text == message) {
+        ui.small(e.note);
+    }
+
+    let alphabet = *alphabet;
+    let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+        ui.fonts()
+            .layout_job(alphabet.highlight(ui, text, wrap_width))
+    };
+    let mut search_layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+        let h = highlights.as_ref().unwrap();
+        ui.fonts()
+            .layout_job(h.layout(ui, text, wrap_width, |i| i..i + 1))
+    };
+    // The alphabet only applies to the message when it is typed as text, and search matches
+    // only line up with it when each character is a byte.
+    let text = format.is_text();
+    let bytewise = *format == InputFormat::Text;
+    let mut m = TextEdit::multiline(message).interactive(!read_only);
+    // Characters the alphabet can't encode take priority over search matches.
+    if text && alphabet != Alphabet::Unrestricted {
+        m = m.layouter(&mut layouter);
+    } else if bytewise && highlights.is_some() {
+        m = m.layouter(&mut search_layouter);
+    }
+    ui.add(m);
+    if let Some(len) = truncated_from {
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "The message was {} KiB, so only its first {} KiB are kept.",
+                len / 1024,
+                message.len() / 1024
+            ),
+        );
+    }
+
+    if !text {
+        return;
+    }
+    let rejected = alphabet.rejected(message);
+    for (i, c, why) in rejected.iter().take(5) {
+        ui.colored_label(Color32::RED, format!("{c:?} at byte {i}: {why}"));
+    }
+    if rejected.len() > 5 {
+        ui.colored_label(Color32::RED, format!("...and {} more", rejected.len() - 5));
+    }
+}
+
+/// Which kind of codeword bit the Encoded panel draws attention to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParityOverlay {
+    Off,
+    Parity,
+    Data,
+    /// Every kind of bit in its own color, with a legend.
+    Structure,
+    /// Data bits shaded by their significance in the message byte they came from, through every
+    /// panel with a grid of bits.
+    Significance,
+}
+
+impl ParityOverlay {
+    pub const ALL: [ParityOverlay; 5] = [
+        ParityOverlay::Off,
+        ParityOverlay::Parity,
+        ParityOverlay::Data,
+        ParityOverlay::Structure,
+        ParityOverlay::Significance,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ParityOverlay::Off => "Plain",
+            ParityOverlay::Parity => "Highlight parity",
+            ParityOverlay::Data => "Highlight data",
+            ParityOverlay::Structure => "Color by role",
+            ParityOverlay::Significance => "Color by significance",
+        }
+    }
+}
+
+/// How the byte panels write their bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ByteDisplay {
+    /// In the format the message is typed in, with text meaning binary.
+    #[default]
+    Message,
+    Binary,
+    Hex,
+    Decimal,
+}
+
+impl ByteDisplay {
+    pub const ALL: [ByteDisplay; 4] = [
+        ByteDisplay::Message,
+        ByteDisplay::Binary,
+        ByteDisplay::Hex,
+        ByteDisplay::Decimal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ByteDisplay::Message => "Same as the message",
+            ByteDisplay::Binary => "Binary",
+            ByteDisplay::Hex => "Hex",
+            ByteDisplay::Decimal => "Decimal",
+        }
+    }
+
+    /// The notation bytes are written in, when the message is typed in `format`.
+    pub fn notation(self, format: InputFormat) -> Notation {
+        match (self, format) {
+            (
+                ByteDisplay::Message,
+                InputFormat::Text | InputFormat::Ascii7 | InputFormat::Baudot,
+            )
+            | (ByteDisplay::Binary, _) => Notation::Binary,
+            (ByteDisplay::Message, InputFormat::Hex) | (ByteDisplay::Hex, _) => Notation::Hex,
+            (ByteDisplay::Message, InputFormat::Base64) => Notation::Base64,
+            (ByteDisplay::Decimal, _) => Notation::Decimal,
+        }
+    }
+}
+
+/// The notation a byte panel is written in, once `ByteDisplay::Message` is settled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    #[default]
+    Binary,
+    Hex,
+    Decimal,
+    Base64,
+}
+
+impl Notation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Notation::Binary => "Binary",
+            Notation::Hex => "Hex",
+            Notation::Decimal => "Decimal",
+            Notation::Base64 => "Base64",
+        }
+    }
+
+    /// Appends `bytes` to `s`. Binary is one byte per line; its digits are pushed directly
+    /// rather than through `format!`, which on a long stream costs an allocation per byte.
+    fn write(self, bytes: &[u8], s: &mut String) {
+        match self {
+            Notation::Binary => {
+                s.reserve(bytes.len() * 9);
+                for b in bytes {
+                    for i in (0..8).rev() {
+                        s.push(if (b >> i) & 1 == 1 { '1' } else { '0' });
+                    }
+                    s.push('\n');
+                }
+            }
+            Notation::Hex => format::write_hex(bytes, s),
+            Notation::Decimal => format::write_decimal(bytes, s),
+            Notation::Base64 => format::write_base64(bytes, s),
+        }
+    }
+
+    /// `bytes` written out, as the byte panels show them.
+    pub fn text(self, bytes: &[u8]) -> String {
+        let mut s = String::new();
+        self.write(bytes, &mut s);
+        s
+    }
+
+    /// The bytes `text` stands for, written as `write` writes them.
+    fn parse(self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Notation::Binary => format::parse_binary(text),
+            Notation::Hex => format::parse_hex(text),
+            Notation::Decimal => format::parse_decimal(text),
+            Notation::Base64 => format::parse_base64(text),
+        }
+    }
+
+    /// The characters byte `i` is written with.
+    fn chars(self, i: usize) -> Range<usize> {
+        match self {
+            // Each byte takes eight digits and a newline.
+            Notation::Binary => 9 * i..9 * i + 8,
+            Notation::Hex => 3 * i..3 * i + 2,
+            Notation::Decimal => 4 * i..4 * i + 3,
+            // Each line holds 16 groups of four characters, for three bytes, and a newline.
+            Notation::Base64 => {
+                let group = i / 3;
+                let start = group / 16 * 65 + group % 16 * 4 + i % 3;
+                start..start + 2
+            }
+        }
+    }
+
+    /// The line byte `i` is on.
+    fn line(self, i: usize) -> usize {
+        match self {
+            Notation::Binary => i,
+            Notation::Hex => i / format::HEX_LINE_BYTES,
+            Notation::Decimal => i / format::DECIMAL_LINE_BYTES,
+            Notation::Base64 => i / format::BASE64_LINE_BYTES,
+        }
+    }
+}
+
+/// The colors of the Color by role overlay.
+const DATA_COLOR: Color32 = Color32::from_rgb(70, 110, 170);
+const PARITY_COLOR: Color32 = Color32::from_rgb(230, 160, 40);
+const OVERALL_PARITY_COLOR: Color32 = Color32::from_rgb(170, 90, 190);
+
+/// The color of a data bit of significance `s` under the Color by significance overlay, from
+/// deep red for the most significant bit to dark blue for the least.
+fn significance_color(s: usize) -> Color32 {
+    let s = s.min(7) as u8;
+    Color32::from_rgb(40 + 25 * s, 50, 190 - 20 * s)
+}
+
+/// The background of bit `bit` of the stream where a grid has nothing else to show: its
+/// significance under the Color by significance overlay, if it is a data bit, and `faint`
+/// otherwise.
+fn background(info: &CodeInfo, overlay: ParityOverlay, bit: usize, faint: Color32) -> Color32 {
+    match analysis::source_bit(info, bit) {
+        Some((_, s)) if overlay == ParityOverlay::Significance => significance_color(s),
+        _ => faint,
+    }
+}
+
+/// Where a data bit came from in the message, for the hover text of a grid, starting on a new
+/// line. Empty for parity bits.
+fn source_note(info: &CodeInfo, bit: usize) -> String {
+    match analysis::source_bit(info, bit) {
+        Some((byte, 7)) => format!("\nMost significant bit of message byte {byte}"),
+        Some((byte, 0)) => format!("\nLeast significant bit of message byte {byte}"),
+        Some((byte, s)) => format!("\nBit {s} of message byte {byte}"),
+        None => String::new(),
+    }
+}
+
+/// A row of color swatches and what they mean.
+fn legend(ui: &mut egui::Ui, entries: &[(Color32, &str)]) {
+    ui.horizontal_wrapped(|ui| {
+        for &(color, label) in entries {
+            let (rect, _) = ui.allocate_exact_size(vec2(12.0, 12.0), Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color);
+            ui.small(label);
+        }
+    });
+}
+
+/// A key to the Color by significance overlay, most significant bit first.
+fn significance_legend(ui: &mut egui::Ui) {
+    let labels = ["MSB", "6", "5", "4", "3", "2", "1", "LSB"];
+    let entries: Vec<(Color32, &str)> = Iterator::zip((0..8).rev(), labels)
+        .map(|(s, label)| (significance_color(s), label))
+        .collect();
+    legend(ui, &entries);
+}
+
+/// A key to the Color by role overlay. The overall parity bit only appears in extended codes.
+fn structure_legend(ui: &mut egui::Ui, info: &CodeInfo) {
+    let mut entries = vec![(DATA_COLOR, "Data"), (PARITY_COLOR, "Parity")];
+    if info.extended {
+        entries.push((OVERALL_PARITY_COLOR, "Overall parity"));
+    }
+    legend(ui, &entries);
+}
+
+/// Shows the encoded stream, with the error pattern marked on a minimap when the stream is long.
+/// The bit under the pointer is carried between frames in `stages.hovered_bit` so that the bits
+/// related to it can be highlighted, and any notes on it shown. `text` is as for `bytes`.
+pub fn encoded(ui: &mut egui::Ui, stages: &mut Stages, view: View, text: &mut BinaryText) {
+    let bytes = stages.encoded;
+    let error = &*stages.error;
+    let info = stages.code_info;
+    let overlay = &mut *stages.parity_overlay;
+    let hovered = &mut *stages.hovered_bit;
+    let notes = stages.notes;
+    let View {
+        highlights,
+        scroll_to,
+        ..
+    } = view;
+    ui.small(format!("{} (n = {}, k = {})", info.name, info.n, info.k));
+    ui.horizontal(|ui| {
+        for o in ParityOverlay::ALL {
+            ui.radio_value(overlay, o, o.name());
+        }
+    });
+    if *overlay == ParityOverlay::Off {
+        *hovered = None;
+        self::bytes(ui, PanelId::Encoded, bytes, text, highlights, scroll_to);
+        return;
+    }
+
+    let bright = PARITY_COLOR;
+    let dim = ui.visuals().faint_bg_color;
+    let hover_color = Color32::from_rgb(60, 140, 230);
+    let related_color = Color32::from_rgb(120, 200, 250);
+    let highlight_parity = *overlay == ParityOverlay::Parity;
+    let by_role = *overlay == ParityOverlay::Structure;
+    let by_significance = *overlay == ParityOverlay::Significance;
+    if by_role {
+        structure_legend(ui, info);
+    }
+    if by_significance {
+        significance_legend(ui);
+    }
+    let hover = hovered.map(|bit| info.locate(bit));
+    // A hovered parity bit relates to the bits it checks; a hovered data bit to the parity
+    // bits that check it.
+    let related = |codeword: usize, i: usize| match hover {
+        Some((c, h)) if c == codeword && h != i => {
+            if info.is_parity(h) {
+                info.covers(h, i)
+            } else {
+                info.is_parity(i) && info.covers(i, h)
+            }
+        }
+        _ => false,
+    };
+
+    // Long streams get a minimap of where the errors are, which scrolls the grid when clicked.
+    let rows = bytes.len();
+    // The vertical pipeline can offer unbounded height, so the grid is capped.
+    let height = ui.available_height().clamp(100.0, 1000.0);
+    let long = rows as f32 * bit_grid::CELL > height;
+    ui.horizontal_top(|ui| {
+        let map = long.then(|| {
+            let markers = error_markers(info, error);
+            bit_grid::minimap(ui, rows, height, &markers)
+        });
+        let mut scroll = egui::ScrollArea::vertical()
+            .id_source("encoded_grid")
+            .max_height(height);
+        if let Some(row) = map.as_ref().and_then(|m| m.clicked).or(scroll_to) {
+            let offset = row as f32 * bit_grid::CELL - height / 2.0;
+            scroll = scroll.vertical_scroll_offset(offset.max(0.0));
+        }
+        let output = scroll.show(ui, |ui| {
+            let grid = bit_grid(ui, bytes, |bit, _| {
+                let (codeword, i) = info.locate(bit);
+                if hover == Some((codeword, i)) {
+                    hover_color
+                } else if related(codeword, i) {
+                    related_color
+                } else if let Some(c) = highlights.as_ref().and_then(|h| h.color(bit / 8)) {
+                    c
+                } else if by_significance {
+                    background(info, *overlay, bit, dim)
+                } else if by_role {
+                    match info.position(i) {
+                        0 => OVERALL_PARITY_COLOR,
+                        _ if info.is_parity(i) => PARITY_COLOR,
+                        _ => DATA_COLOR,
+                    }
+                } else if info.is_parity(i) == highlight_parity {
+                    bright
+                } else {
+                    dim
+                }
+            });
+            *hovered = grid.hovered;
+            if let Some(bit) = grid.hovered {
+                let (codeword, i) = info.locate(bit);
+                let p = info.position(i);
+                let mut text = if info.is_parity(i) {
+                    let checked: Vec<String> = info
+                        .coverage(i)
+                        .into_iter()
+                        .map(|j| info.position(j).to_string())
+                        .collect();
+                    format!(
+                        "Codeword {codeword}, parity bit at position {p}\nChecks positions {}",
+                        checked.join(", ")
+                    )
+                } else {
+                    let checkers: Vec<String> = (0..info.n)
+                        .filter(|&j| info.is_parity(j) && info.covers(j, i))
+                        .map(|j| info.position(j).to_string())
+                        .collect();
+                    format!(
+                        "Codeword {codeword}, data bit at position {p}\nChecked by parity bits \
+                         {}{}",
+                        checkers.join(", "),
+                        source_note(info, bit)
+                    )
+                };
+                for note in notes.for_bit(codeword, bit) {
+                    text.push_str("\n\n");
+                    text.push_str(note);
+                }
+                grid.response.on_hover_text(text);
+            }
+        });
+        if let Some(map) = &map {
+            let first = output.state.offset.y / bit_grid::CELL;
+            let count = output.inner_rect.height() / bit_grid::CELL;
+            bit_grid::minimap_viewport(ui, map, rows, first, count);
+        }
+    });
+}
+
+/// The text of the Error panel, which can be edited to set the error. It is kept between frames
+/// so that a pattern still being typed isn't rewritten from the error, and only rebuilt when the
+/// error changes some other way.
+#[derive(Default)]
+pub struct ErrorText {
+    notation: Notation,
+    /// The error `text` was written from or last set it to.
+    bytes: Vec<u8>,
+    text: String,
+    /// Why the text doesn't parse, or how its length was made to fit.
+    problem: Option<Result<String, String>>,
+}
+
+impl ErrorText {
+    pub fn set_notation(&mut self, notation: Notation) {
+        if self.notation != notation {
+            self.notation = notation;
+            self.bytes.clear();
+            self.text.clear();
+        }
+    }
+
+    /// Rewrites the text if `error` has changed since it was last written or parsed.
+    fn update(&mut self, error: &[u8]) {
+        if self.bytes != error {
+            self.bytes.clear();
+            self.bytes.extend_from_slice(error);
+            self.text.clear();
+            self.notation.write(error, &mut self.text);
+            self.problem = None;
+        }
+    }
+
+    /// Sets `error` to the pattern typed, cut short or padded with zeros to its length.
+    fn parse_into(&mut self, error: &mut [u8]) {
+        match self.notation.parse(&self.text) {
+            Ok(typed) => {
+                let len = typed.len().min(error.len());
+                error[..len].copy_from_slice(&typed[..len]);
+                error[len..].fill(0);
+                self.bytes.clear();
+                self.bytes.extend_from_slice(error);
+                self.problem = match typed.len().cmp(&error.len()) {
+                    Ordering::Less => Some(Ok(format!(
+                        "{} of {} bytes given; the rest are left clear.",
+                        typed.len(),
+                        error.len()
+                    ))),
+                    Ordering::Greater => Some(Ok(format!(
+                        "{} bytes given; only the first {} are used.",
+                        typed.len(),
+                        error.len()
+                    ))),
+                    Ordering::Equal => None,
+                };
+            }
+            Err(e) => self.problem = Some(Err(e)),
+        }
+    }
+}
+
+/// Shows the error as a grid of bits that can be clicked to flip, for building single, double,
+/// and burst errors by hand, and as text that a pattern can be typed or pasted into. Flipped
+/// bits are green where their codeword can still be corrected and red where it can't.
+pub fn error(
+    ui: &mut egui::Ui,
+    error: &mut [u8],
+    info: &CodeInfo,
+    text: &mut ErrorText,
+    highlights: Option<Highlights>,
+    scroll_to: Option<usize>,
+    read_only: bool,
+) {
+    let flips = analysis::error_counts(info, error);
+    let faint = ui.visuals().faint_bg_color;
+    let height = ui.available_height().clamp(100.0, 1000.0);
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source("error_grid")
+        .max_height(height);
+    if let Some(row) = scroll_to {
+        let offset = row as f32 * bit_grid::CELL - height / 2.0;
+        scroll = scroll.vertical_scroll_offset(offset.max(0.0));
+    }
+    scroll.show(ui, |ui| {
+        let grid = bit_grid(ui, error, |bit, set| {
+            let (codeword, _) = info.locate(bit);
+            match flips.get(codeword) {
+                Some(1) if set => Color32::DARK_GREEN,
+                Some(_) if set => Color32::DARK_RED,
+                _ => highlights
+                    .as_ref()
+                    .and_then(|h| h.color(bit / 8))
+                    .unwrap_or(faint),
+            }
+        });
+        if let Some(bit) = grid.hovered {
+            let (codeword, i) = info.locate(bit);
+            let mut text = format!("Codeword {codeword}, position {}", info.position(i));
+            text.push_str(&source_note(info, bit));
+            if !read_only {
+                text.push_str("\nClick to flip");
+            }
+            grid.response.on_hover_text(text);
+        }
+        if let Some(bit) = grid.clicked.filter(|_| !read_only) {
+            error[bit / 8] ^= 1 << (bit % 8);
+        }
+    });
+    egui::CollapsingHeader::new(format!("As {}", text.notation.name())).show(ui, |ui| {
+        text.update(error);
+        let edit = egui::ScrollArea::vertical()
+            .id_source("error_text")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut text.text)
+                        .code_editor()
+                        .interactive(!read_only),
+                )
+            })
+            .inner;
+        if edit.changed() {
+            text.parse_into(error);
+        }
+        match &text.problem {
+            Some(Ok(note)) => {
+                ui.colored_label(Color32::YELLOW, note);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e);
+            }
+            None => {
+                ui.small("Type or paste a pattern to set the error.");
+            }
+        }
+    });
+}
+
+/// Shows the stream as sent on the channel, interleaved to `depth` codewords, with the bits the
+/// channel flipped colored as in the Error panel: green where their codeword can still be
+/// corrected and red where it can't. Bits can be clicked to flip, for building bursts in the order
+/// the channel sees them. `error` is in codeword order.
+pub fn interleaved(
+    ui: &mut egui::Ui,
+    sent: &[u8],
+    error: &mut [u8],
+    info: &CodeInfo,
+    depth: &mut usize,
+    scroll_to: Option<usize>,
+    read_only: bool,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Depth:");
+        ui.add_enabled(
+            !read_only,
+            DragValue::new(depth)
+                .clamp_range(1..=interleave::MAX_DEPTH)
+                .suffix(" codewords"),
+        );
+    });
+    if *depth == 1 {
+        ui.small("Off: the stream is sent in codeword order.");
+    } else {
+        ui.small(format!(
+            "Each block of {depth} codewords is sent a bit of each in turn, so a burst of up to \
+             {depth} flips puts at most one in any codeword."
+        ));
+    }
+    let interleaver = Interleaver::new(info, sent.len(), *depth);
+    let flips = analysis::error_counts(info, error);
+    let faint = ui.visuals().faint_bg_color;
+    let height = ui.available_height().clamp(100.0, 1000.0);
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source("interleaved_grid")
+        .max_height(height);
+    if let Some(row) = scroll_to {
+        let offset = row as f32 * bit_grid::CELL - height / 2.0;
+        scroll = scroll.vertical_scroll_offset(offset.max(0.0));
+    }
+    scroll.show(ui, |ui| {
+        let grid = bit_grid(ui, sent, |t, _| {
+            let bit = interleaver.source(t);
+            if !analysis::get_bit(error, bit) {
+                return faint;
+            }
+            let (codeword, _) = info.locate(bit);
+            match flips.get(codeword) {
+                Some(1) => Color32::DARK_GREEN,
+                _ => Color32::DARK_RED,
+            }
+        });
+        if let Some(t) = grid.hovered {
+            let bit = interleaver.source(t);
+            let (codeword, i) = info.locate(bit);
+            let mut text = format!("Codeword {codeword}, position {}", info.position(i));
+            text.push_str(&source_note(info, bit));
+            if !read_only {
+                text.push_str("\nClick to flip");
+            }
+            grid.response.on_hover_text(text);
+        }
+        if let Some(t) = grid.clicked.filter(|_| !read_only) {
+            let bit = interleaver.source(t);
+            error[bit / 8] ^= 1 << (bit % 8);
+        }
+    });
+}
+
+/// The colors of the Corrections panel.
+const FIXED_COLOR: Color32 = Color32::DARK_GREEN;
+const MISSED_COLOR: Color32 = Color32::from_rgb(200, 120, 0);
+const SPURIOUS_COLOR: Color32 = Color32::DARK_RED;
+const UNCORRECTABLE_COLOR: Color32 = Color32::from_rgb(70, 30, 30);
+
+/// Compares the injected error with the bits the decoder flipped back: which errors it fixed,
+/// which it missed, and which bits it flipped that were never in error. The grid shows the
+/// injected error.
+pub fn corrections(
+    ui: &mut egui::Ui,
+    error: &[u8],
+    corrections: &Corrections,
+    info: &CodeInfo,
+    overlay: ParityOverlay,
+    scroll_to: Option<usize>,
+) {
+    let comparison = corrections::compare(error, corrections);
+    Grid::new("corrections_counts").show(ui, |ui| {
+        for (label, count) in [
+            ("Fixed", comparison.fixed),
+            ("Missed", comparison.missed),
+            ("Spurious", comparison.spurious),
+            ("Uncorrectable codewords", corrections.uncorrectable.len()),
+        ] {
+            ui.label(label);
+            ui.label(count.to_string());
+            ui.end_row();
+        }
+    });
+    legend(
+        ui,
+        &[
+            (FIXED_COLOR, "Fixed"),
+            (MISSED_COLOR, "Missed"),
+            (SPURIOUS_COLOR, "Spurious"),
+            (UNCORRECTABLE_COLOR, "Uncorrectable codeword"),
+        ],
+    );
+
+    let faint = ui.visuals().faint_bg_color;
+    let height = ui.available_height().clamp(100.0, 1000.0);
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source("corrections_grid")
+        .max_height(height);
+    if let Some(row) = scroll_to {
+        let offset = row as f32 * bit_grid::CELL - height / 2.0;
+        scroll = scroll.vertical_scroll_offset(offset.max(0.0));
+    }
+    scroll.show(ui, |ui| {
+        let grid = bit_grid(ui, error, |bit, injected| {
+            match (injected, corrections.is_flipped(bit)) {
+                (true, true) => FIXED_COLOR,
+                (true, false) => MISSED_COLOR,
+                (false, true) => SPURIOUS_COLOR,
+                (false, false) => {
+                    let (codeword, _) = info.locate(bit);
+                    if corrections.uncorrectable.contains(&codeword) {
+                        UNCORRECTABLE_COLOR
+                    } else {
+                        background(info, overlay, bit, faint)
+                    }
+                }
+            }
+        });
+        if let Some(bit) = grid.hovered {
+            let (codeword, i) = info.locate(bit);
+            let what = match (analysis::get_bit(error, bit), corrections.is_flipped(bit)) {
+                (true, true) => "flipped, and fixed",
+                (true, false) => "flipped, and missed",
+                (false, true) => "not flipped, but \"corrected\"",
+                (false, false) => "not flipped",
+            };
+            grid.response.on_hover_text(format!(
+                "Codeword {codeword}, position {}: {what}{}",
+                info.position(i),
+                source_note(info, bit)
+            ));
+        }
+    });
+}
+
+/// Shows the received stream as a grid of bits, with the bits the decoder corrected in green and
+/// the codewords it found errors in but couldn't correct in red.
+pub fn with_error(
+    ui: &mut egui::Ui,
+    bytes: &[u8],
+    corrections: &Corrections,
+    info: &CodeInfo,
+    overlay: ParityOverlay,
+    highlights: Option<Highlights>,
+    scroll_to: Option<usize>,
+) {
+    legend(
+        ui,
+        &[
+            (Color32::DARK_GREEN, "Corrected"),
+            (Color32::DARK_RED, "Uncorrectable codeword"),
+        ],
+    );
+    let faint = ui.visuals().faint_bg_color;
+    let height = ui.available_height().clamp(100.0, 1000.0);
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source("with_error_grid")
+        .max_height(height);
+    if let Some(row) = scroll_to {
+        let offset = row as f32 * bit_grid::CELL - height / 2.0;
+        scroll = scroll.vertical_scroll_offset(offset.max(0.0));
+    }
+    scroll.show(ui, |ui| {
+        let uncorrectable = |bit| {
+            let (codeword, _) = info.locate(bit);
+            corrections.uncorrectable.contains(&codeword)
+        };
+        let grid = bit_grid(ui, bytes, |bit, _| {
+            if corrections.is_flipped(bit) {
+                Color32::DARK_GREEN
+            } else if uncorrectable(bit) {
+                Color32::DARK_RED
+            } else {
+                highlights
+                    .as_ref()
+                    .and_then(|h| h.color(bit / 8))
+                    .unwrap_or_else(|| background(info, overlay, bit, faint))
+            }
+        });
+        if let Some(bit) = grid.hovered {
+            let (codeword, i) = info.locate(bit);
+            let mut text = format!("Codeword {codeword}, position {}", info.position(i));
+            text.push_str(&source_note(info, bit));
+            if corrections.is_flipped(bit) {
+                text.push_str("\nCorrected by the decoder");
+            } else if uncorrectable(bit) {
+                text.push_str("\nErrors detected in this codeword, but not corrected");
+            }
+            grid.response.on_hover_text(text);
+        }
+    });
+}
+
+/// Shows the check of each received codeword: its syndrome, the position the syndrome points to,
+/// the overall parity for extended codes, and what the decoder makes of them. `scroll_to` is a
+/// byte of the stream, as for the other panels, and brings the codeword starting there into
+/// view.
+pub fn syndromes(ui: &mut egui::Ui, received: &[u8], info: &CodeInfo, scroll_to: Option<usize>) {
+    ui.small(
+        "The syndrome is the XOR of the positions of the set bits: zero when every parity check \
+         passes, and otherwise the position of a single flipped bit.",
+    );
+    let count = analysis::codeword_count(info, received);
+    if count == 0 {
+        ui.label("No whole codewords were received.");
+        return;
+    }
+    // The overall parity bit isn't part of the syndrome.
+    let width = (0..info.n)
+        .filter(|&i| info.is_parity(i) && info.position(i) != 0)
+        .count();
+    ui.monospace(format!(
+        "{:>8}  {:>w$}  {:>9}  {:>7}  Result",
+        "Codeword",
+        "Syndrome",
+        "Points to",
+        "Overall",
+        w = width.max(8)
+    ));
+
+    let row_height = ui.text_style_height(&TextStyle::Monospace);
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source("syndromes")
+        .max_height(ui.available_height().clamp(100.0, 1000.0));
+    if let Some(row) = scroll_to {
+        let j = (0..count)
+            .find(|&j| analysis::codeword_row(info, j) >= row)
+            .unwrap_or(count - 1);
+        scroll = scroll.vertical_scroll_offset(j as f32 * row_height);
+    }
+    scroll.show_rows(ui, row_height, count, |ui, rows| {
+        for j in rows {
+            let check = match analysis::codeword(info, received, j) {
+                Some(word) => analysis::check(info, &word),
+                None => continue,
+            };
+            let points_to = match check.syndrome {
+                0 => "-".to_string(),
+                s if (0..info.n).any(|i| info.position(i) == s) => s.to_string(),
+                _ => "none".to_string(),
+            };
+            let overall = match check.overall_even {
+                None => "-",
+                Some(true) => "even",
+                Some(false) => "odd",
+            };
+            let (result, color) = match (check.distance, check.flip) {
+                (0, _) => ("valid".to_string(), ui.visuals().text_color()),
+                (1, Some(i)) => (
+                    format!("flip position {}", info.position(i)),
+                    Color32::GREEN,
+                ),
+                _ => ("uncorrectable".to_string(), Color32::RED),
+            };
+            ui.horizontal(|ui| {
+                ui.monospace(format!(
+                    "{j:>8}  {:>w$}  {points_to:>9}  {overall:>7}",
+                    format!("{:0width$b}", check.syndrome),
+                    w = width.max(8)
+                ));
+                ui.label(RichText::new(result).monospace().color(color));
+            });
+        }
+    });
+}
+
+/// Minimap markers for the codewords with flipped bits: a single flip is corrected, anything
+/// more is not.
+fn error_markers(info: &CodeInfo, error: &[u8]) -> Vec<(usize, usize, Color32)> {
+    analysis::error_counts(info, error)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(j, flips)| {
+            let color = match flips {
+                0 => return None,
+                1 => Color32::GREEN,
+                _ => Color32::RED,
+            };
+            let first = analysis::codeword_row(info, j);
+            let end = ((j + 1) * info.n).div_ceil(8);
+            Some((first, end - first, color))
+        })
+        .collect()
+}
+
+/// The text of a byte panel, kept between frames and only rebuilt when the bytes or the
+/// notation change.
+#[derive(Default)]
+pub struct BinaryText {
+    bytes: Vec<u8>,
+    notation: Notation,
+    text: String,
+}
+
+impl BinaryText {
+    pub fn set_notation(&mut self, notation: Notation) {
+        if self.notation != notation {
+            self.notation = notation;
+            self.bytes.clear();
+            self.text.clear();
+        }
+    }
+
+    /// The text for `bytes`.
+    fn update(&mut self, bytes: &[u8]) -> &mut String {
+        if self.bytes != bytes {
+            self.bytes.clear();
+            self.bytes.extend_from_slice(bytes);
+            self.text.clear();
+            self.notation.write(bytes, &mut self.text);
+        }
+        &mut self.text
+    }
+}
+
+/// Shows `bytes` in the notation `text` is set to, with any search matches highlighted,
+/// scrolling to byte `scroll_to` if given. `text` holds the panel's text from the last frame.
+pub fn bytes(
+    ui: &mut egui::Ui,
+    id: PanelId,
+    bytes: &[u8],
+    text: &mut BinaryText,
+    highlights: Option<Highlights>,
+    scroll_to: Option<usize>,
+) {
+    let notation = text.notation;
+    let s = text.update(bytes);
+
+    let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+        let h = highlights.as_ref().unwrap();
+        ui.fonts()
+            .layout_job(h.layout(ui, text, wrap_width, |i| notation.chars(i)))
+    };
+    let mut m = TextEdit::multiline(s).interactive(false);
+    if highlights.is_some() {
+        m = m.layouter(&mut layouter);
+    }
+    let mut scroll = egui::ScrollArea::vertical()
+        .id_source(id.id())
+        .max_height(1000.0);
+    if let Some(row) = scroll_to.map(|i| notation.line(i)) {
+        scroll = scroll.vertical_scroll_offset(row as f32 * ui.text_style_height(&TextStyle::Body));
+    }
+    scroll.show(ui, |ui| ui.add(m));
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.3} ms", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Shows what was recovered of a message that didn't decode as a whole, with the bytes of the
+/// codewords that couldn't be corrected marked.
+fn partial(ui: &mut egui::Ui, partial: &Partial) {
+    let failed: Vec<String> = partial.failed.iter().map(usize::to_string).collect();
+    ui.colored_label(
+        Color32::RED,
+        format!(
+            "{} of {} codewords couldn't be corrected: {}",
+            partial.failed.len(),
+            partial.codewords,
+            failed.join(", ")
+        ),
+    );
+    let font_id = TextStyle::Body.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let mut job = LayoutJob::default();
+    for (text, recovered) in &partial.runs {
+        let format = if *recovered {
+            TextFormat {
+                font_id: font_id.clone(),
+                color,
+                ..Default::default()
+            }
+        } else {
+            TextFormat {
+                font_id: font_id.clone(),
+                color: Color32::WHITE,
+                background: Color32::from_rgb(200, 40, 40),
+                ..Default::default()
+            }
+        };
+        job.append(text, 0.0, format);
+    }
+    ui.label(job);
+}
+
+/// Shows the decoded message, with a choice of decoder and how each decoder fared on the same
+/// stream.
+pub fn decoded(
+    ui: &mut egui::Ui,
+    message_out: &mut Option<String>,
+    algorithm: &mut Algorithm,
+    info: &CodeInfo,
+    outcomes: &[Outcome],
+    original: &[u8],
+    highlights: Option<Highlights>,
+) {
+    egui::ComboBox::from_label("Decoder")
+        .selected_text(algorithm.name())
+        .show_ui(ui, |ui| {
+            for a in Algorithm::available(info) {
+                ui.selectable_value(algorithm, a, a.name());
+            }
+        });
+    if let Some(o) = outcomes.iter().find(|o| o.algorithm == *algorithm) {
+        ui.small(format!(
+            "Decoded by {} in {}",
+            o.algorithm.name(),
+            format_elapsed(o.elapsed)
+        ));
+    }
+
+    match message_out {
+        Some(message_out) => {
+            let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+                let h = highlights.as_ref().unwrap();
+                ui.fonts()
+                    .layout_job(h.layout(ui, text, wrap_width, |i| i..i + 1))
+            };
+            let mut m = TextEdit::multiline(message_out).interactive(false);
+            if highlights.is_some() {
+                m = m.layouter(&mut layouter);
+            }
+            ui.add(m);
+        }
+        None => match outcomes
+            .iter()
+            .find(|o| o.algorithm == *algorithm)
+            .and_then(|o| o.partial.as_ref())
+        {
+            Some(partial) => self::partial(ui, partial),
+            None => {
+                let l = Label::new(RichText::new("Unable to decode message.").color(Color32::RED));
+                ui.add(l);
+            }
+        },
+    };
+
+    egui::CollapsingHeader::new("Compare decoders").show(ui, |ui| {
+        Grid::new("decoder_comparison")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Decoder");
+                ui.strong("Result");
+                ui.strong("Time");
+                ui.end_row();
+                for o in outcomes {
+                    ui.label(o.algorithm.name());
+                    match &o.decoded {
+                        Some(d) if d == original => {
+                            ui.colored_label(Color32::GREEN, "matches original")
+                        }
+                        Some(_) => ui.colored_label(Color32::RED, "differs from original"),
+                        None => ui.colored_label(Color32::RED, "gave up"),
+                    };
+                    ui.label(format_elapsed(o.elapsed));
+                    ui.end_row();
+                }
+            });
+    });
+    if let Some(o) = outcomes.iter().find(|o| o.algorithm == *algorithm) {
+        egui::CollapsingHeader::new("Block decode times").show(ui, |ui| block_times(ui, o));
+    }
+}
+
+/// Percentiles of the per-block decode times, then a bar per block scaled to the slowest.
+fn block_times(ui: &mut egui::Ui, outcome: &Outcome) {
+    if outcome.algorithm == Algorithm::Library {
+        ui.label(format!(
+            "{} decodes the whole stream at once, so it can't be timed per block.",
+            outcome.algorithm.name()
+        ));
+        return;
+    }
+    if outcome.block_times.is_empty() {
+        ui.label("No whole codewords were received.");
+        return;
+    }
+    let percentiles: Vec<String> = [50.0, 90.0, 99.0, 100.0]
+        .into_iter()
+        .filter_map(|p| {
+            let t = outcome.percentile(p)?;
+            Some(match p as u32 {
+                100 => format!("max {}", format_elapsed(t)),
+                p => format!("p{p} {}", format_elapsed(t)),
+            })
+        })
+        .collect();
+    ui.small(percentiles.join(", "));
+
+    let slowest = outcome.percentile(100.0).unwrap_or_default().as_secs_f32();
+    let row_height = ui.text_style_height(&egui::TextStyle::Small);
+    egui::ScrollArea::vertical()
+        .id_source("block_times")
+        .max_height(200.0)
+        .show_rows(ui, row_height, outcome.block_times.len(), |ui, rows| {
+            for j in rows {
+                let t = outcome.block_times[j];
+                ui.horizontal(|ui| {
+                    ui.small(format!("{j:>4}"));
+                    let frac = if slowest > 0.0 {
+                        t.as_secs_f32() / slowest
+                    } else {
+                        0.0
+                    };
+                    let (rect, response) =
+                        ui.allocate_exact_size(vec2(BAR_WIDTH, row_height), Sense::hover());
+                    let bar = Rect::from_min_size(
+                        rect.left_center() - vec2(0.0, 2.0),
+                        vec2(BAR_WIDTH * frac, 4.0),
+                    );
+                    ui.painter()
+                        .rect_filled(bar, 1.0, ui.visuals().selection.bg_fill);
+                    response.on_hover_text(format_elapsed(t));
+                });
+            }
+        });
+}
+
+/// A panel's title, with a button that puts `contents` on the clipboard. Text that can't be
+/// edited is awkward to select, and a whole long stream tedious to.
+pub fn header(ui: &mut egui::Ui, id: PanelId, contents: impl FnOnce() -> String) {
+    ui.horizontal(|ui| {
+        ui.label(id.title());
+        if ui
+            .small_button("📋")
+            .on_hover_text(format!("Copy {} as shown", id.title()))
+            .clicked()
+        {
+            ui.output().copied_text = contents();
+        }
+    });
+}
+
+/// The arrow between two stages of the vertical pipeline.
+pub fn arrow(ui: &mut egui::Ui) {
+    ui.with_layout(Layout::top_down(Align::Center), |ui| {
+        ui.label(RichText::new("⬇").size(24.0));
+    });
+}
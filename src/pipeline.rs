@@ -0,0 +1,153 @@
+use crate::{
+    codes::CodeInfo,
+    decoders::{self, Outcome},
+};
+use hamming::{encode, Code};
+use instant::Instant;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread,
+};
+
+/// What the worker is asked to work out.
+struct Job {
+    code: &'static Code,
+    info: CodeInfo,
+    /// The message to encode, or `None` to keep the last encoding.
+    message: Option<Vec<u8>>,
+    error: Vec<u8>,
+}
+
+/// The stages the worker worked out for a job.
+pub struct Results {
+    /// The code the job was for.
+    pub info: CodeInfo,
+    /// The message and its encoding, if it was encoded again.
+    pub encoded: Option<(Vec<u8>, Vec<u8>)>,
+    /// The error the stream was received with, resized to the encoding.
+    pub error: Vec<u8>,
+    pub with_error: Vec<u8>,
+    pub outcomes: Vec<Outcome>,
+}
+
+/// Works out the stages for `job`, given the stream `encoded` last, which is kept for the next.
+fn work(encoded: &mut Vec<u8>, job: Job) -> Results {
+    let encoded_again = job.message.map(|message| {
+        *encoded = encode(&message, job.code).unwrap();
+        (message, encoded.clone())
+    });
+    let mut error = job.error;
+    error.resize(encoded.len(), 0);
+    let with_error: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+        .map(|(b, e)| b ^ e)
+        .collect();
+    let outcomes = decoders::run_all(job.code, &job.info, &with_error);
+    Results {
+        info: job.info,
+        encoded: encoded_again,
+        error,
+        with_error,
+        outcomes,
+    }
+}
+
+/// Encodes and decodes on a thread of its own, so that a large message doesn't freeze the window
+/// while it is worked on. One job runs at a time; the app sends the next once the results are in.
+/// The web has no threads, so there each job is worked on as it is sent.
+pub struct Pipeline {
+    #[cfg(not(target_arch = "wasm32"))]
+    jobs: Sender<Job>,
+    #[cfg(not(target_arch = "wasm32"))]
+    results: Receiver<Results>,
+    /// The stream last encoded, on the web.
+    #[cfg(target_arch = "wasm32")]
+    encoded: Vec<u8>,
+    /// The results of the job sent last, on the web, until they are polled.
+    #[cfg(target_arch = "wasm32")]
+    results: Option<Results>,
+    /// When the job in progress was sent, if there is one.
+    sent: Option<Instant>,
+}
+
+impl Pipeline {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let (result_tx, results) = mpsc::channel();
+        thread::spawn(move || {
+            let mut encoded = Vec::new();
+            for job in job_rx {
+                if result_tx.send(work(&mut encoded, job)).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            jobs,
+            results,
+            sent: None,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn new() -> Self {
+        Self {
+            encoded: Vec::new(),
+            results: None,
+            sent: None,
+        }
+    }
+
+    /// How long the job in progress has been running, if there is one.
+    pub fn pending_for(&self) -> Option<Duration> {
+        self.sent.map(|sent| sent.elapsed())
+    }
+
+    /// Starts a job, encoding `message` if given, then receiving the encoding with `error` and
+    /// decoding it. Any job already in progress should be finished first.
+    pub fn send(
+        &mut self,
+        code: &'static Code,
+        info: CodeInfo,
+        message: Option<Vec<u8>>,
+        error: Vec<u8>,
+    ) {
+        let job = Job {
+            code,
+            info,
+            message,
+            error,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.jobs.send(job).is_err() {
+            panic!("The pipeline worker stopped.");
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.results = Some(work(&mut self.encoded, job));
+        }
+        self.sent = Some(Instant::now());
+    }
+
+    /// The results of the job in progress, once it is finished.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self) -> Option<Results> {
+        match self.results.try_recv() {
+            Ok(results) => {
+                self.sent = None;
+                Some(results)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => panic!("The pipeline worker stopped."),
+        }
+    }
+
+    /// The results of the job in progress, once it is finished.
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self) -> Option<Results> {
+        self.sent = None;
+        self.results.take()
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fs, path::Path, time::Duration};
+
+/// The name shown in the window title.
+pub const APP_NAME: &str = "Hamming";
+
+/// The bits drawn on the icon, one byte per row, most significant bit first.
+const ICON_BITS: [u8; 8] = [
+    0b1110_0001,
+    0b0101_0110,
+    0b1001_1010,
+    0b0011_1100,
+    0b1100_0011,
+    0b0110_1001,
+    0b1010_0101,
+    0b0001_1110,
+];
+
+/// The window icon, `size` by `size` RGBA pixels: an 8 by 8 grid of bits, coloured as in the
+/// Encoded panel.
+pub fn icon(size: usize) -> Vec<u8> {
+    let cell = size / 8;
+    // Small icons are too cramped for gaps between the cells.
+    let gap = usize::from(cell >= 4);
+    let mut rgba = vec![0; size * size * 4];
+    for y in 0..size {
+        for x in 0..size {
+            let (row, col) = (y / cell, x / cell);
+            if row >= 8 || col >= 8 || y % cell < gap || x % cell < gap {
+                continue;
+            }
+            let set = (ICON_BITS[row] >> (7 - col)) & 1 == 1;
+            let color = if set {
+                [230, 160, 40, 255]
+            } else {
+                [60, 60, 60, 255]
+            };
+            let i = (y * size + x) * 4;
+            rgba[i..i + 4].copy_from_slice(&color);
+        }
+    }
+    rgba
+}
+
+/// The window title: the file being worked on, and the progress of any long operation. Few
+/// platforms let an app put progress on its taskbar entry, but all of them show its title there.
+pub fn title(file: Option<&Path>, progress: Option<f32>) -> String {
+    let mut title = String::new();
+    if let Some(p) = progress {
+        title.push_str(&format!("[{:.0}%] ", p * 100.0));
+    }
+    if let Some(name) = file.and_then(Path::file_name) {
+        title.push_str(&format!("{} - ", name.to_string_lossy()));
+    }
+    title.push_str(APP_NAME);
+    title
+}
+
+/// The memory the process holds, in bytes, where the platform reports it.
+pub fn resident_memory() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kib: u64 = status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// The processor time the process has used, where the platform reports it.
+pub fn cpu_time() -> Option<Duration> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    // The command name, in parentheses, may hold spaces, so count fields from after it.
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let ticks: u64 = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+    // Linux reports these in USER_HZ, which is 100 everywhere that matters.
+    Some(Duration::from_millis(ticks * 10))
+}
+
+/// The seconds since the Unix epoch, or zero if the clock is before it. The web has no system
+/// clock in `std`, so there it is read from JavaScript.
+pub fn unix_time() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    }
+}
@@ -0,0 +1,115 @@
+use crate::storage;
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const FILE: &str = "recent.json";
+
+/// The most unpinned files remembered.
+const MAX_RECENT: usize = 10;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Entry {
+    path: PathBuf,
+    /// Pinned files stay at the top of the list, however long ago they were opened.
+    pinned: bool,
+}
+
+/// Files and sessions recently opened or saved, for the File > Recent menu.
+pub struct Recent {
+    /// Pinned entries first, then the rest, most recent first.
+    entries: Vec<Entry>,
+    error: Option<String>,
+}
+
+impl Recent {
+    pub fn load() -> Self {
+        Self {
+            entries: storage::load(FILE).unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    /// Moves `path` to the top of its part of the list.
+    pub fn add(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let pinned = matches!(self.remove(&path), Some(Entry { pinned: true, .. }));
+        self.insert(Entry { path, pinned });
+        let mut unpinned = 0;
+        self.entries.retain(|e| {
+            unpinned += usize::from(!e.pinned);
+            e.pinned || unpinned <= MAX_RECENT
+        });
+        self.save();
+    }
+
+    fn remove(&mut self, path: &Path) -> Option<Entry> {
+        let i = self.entries.iter().position(|e| e.path == path)?;
+        Some(self.entries.remove(i))
+    }
+
+    /// Puts `entry` first among the pinned or the unpinned entries.
+    fn insert(&mut self, entry: Entry) {
+        let at = if entry.pinned {
+            0
+        } else {
+            self.entries.iter().take_while(|e| e.pinned).count()
+        };
+        self.entries.insert(at, entry);
+    }
+
+    fn save(&mut self) {
+        self.error = storage::save(FILE, &self.entries).err();
+    }
+
+    /// The contents of the Recent menu. Returns a file to open, if one was picked.
+    pub fn menu(&mut self, ui: &mut egui::Ui, enabled: bool) -> Option<PathBuf> {
+        if self.entries.is_empty() {
+            ui.label("No recent files");
+            return None;
+        }
+        let mut picked = None;
+        let mut toggled = None;
+        for entry in &self.entries {
+            ui.horizontal(|ui| {
+                let pin = if entry.pinned { "Unpin" } else { "Pin" };
+                if ui
+                    .selectable_label(entry.pinned, "📌")
+                    .on_hover_text(pin)
+                    .clicked()
+                {
+                    toggled = Some(entry.path.clone());
+                }
+                let name = entry.path.file_name().map_or_else(
+                    || entry.path.display().to_string(),
+                    |n| n.to_string_lossy().into(),
+                );
+                let button = ui
+                    .add_enabled(enabled, egui::Button::new(name))
+                    .on_hover_text(entry.path.display().to_string());
+                if button.clicked() {
+                    picked = Some(entry.path.clone());
+                }
+            });
+        }
+        if let Some(path) = toggled {
+            if let Some(mut entry) = self.remove(&path) {
+                entry.pinned = !entry.pinned;
+                self.insert(entry);
+                self.save();
+            }
+        }
+        ui.separator();
+        if ui.button("Clear unpinned").clicked() {
+            self.entries.retain(|e| e.pinned);
+            self.save();
+        }
+        if let Some(e) = &self.error {
+            ui.colored_label(Color32::RED, e.as_str());
+        }
+        if picked.is_some() {
+            ui.close_menu();
+        }
+        picked
+    }
+}
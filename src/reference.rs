@@ -0,0 +1,92 @@
+/// An independent, matrix-based model of a Hamming code, to check the `hamming` crate against.
+/// Words are bitmasks with bit `i` for bit `i` of the codeword, and every matrix is one such
+/// mask per row.
+pub struct Reference {
+    /// The parity-check matrix: one row per bit of the position number, plus the overall parity
+    /// row of an extended code.
+    h: Vec<u32>,
+    /// The generator matrix, one row per data bit.
+    g: Vec<u32>,
+    /// The codeword bit holding each data bit.
+    data: Vec<usize>,
+}
+
+impl Reference {
+    /// The `n`-bit code, laid out as Hamming did: bits numbered from 1, with a parity bit at
+    /// each power of two checking every position that has that bit set. An extended code puts
+    /// its overall parity bit in front, at position 0, and numbers the rest from there.
+    pub fn new(n: usize, extended: bool) -> Self {
+        let position = |i: usize| if extended { i } else { i + 1 };
+        let is_parity = |p: usize| p == 0 || p.is_power_of_two();
+        let last = position(n - 1);
+        let rows = usize::BITS - last.leading_zeros();
+        // Column `i` of H is the position number of bit `i`, written in binary.
+        let mut h: Vec<u32> = (0..rows)
+            .map(|r| {
+                (0..n)
+                    .filter(|&i| (position(i) >> r) & 1 == 1)
+                    .fold(0, |row, i| row | 1 << i)
+            })
+            .collect();
+        if extended {
+            h.push((1 << n) - 1);
+        }
+
+        let data: Vec<usize> = (0..n).filter(|&i| !is_parity(position(i))).collect();
+        // Each data bit, with the parity bits set so that every row of H is satisfied.
+        let g = data
+            .iter()
+            .map(|&d| {
+                let mut word: u32 = 1 << d;
+                for r in 0..rows {
+                    let p = 1 << r;
+                    if position(d) & p != 0 {
+                        word |= 1 << (0..n).find(|&i| position(i) == p).unwrap();
+                    }
+                }
+                if extended && word.count_ones() % 2 == 1 {
+                    word |= 1;
+                }
+                word
+            })
+            .collect();
+        Self { h, g, data }
+    }
+
+    /// H times `word`: bit `r` is the parity of `word` under row `r`.
+    pub fn syndrome(&self, word: u32) -> u32 {
+        self.h
+            .iter()
+            .enumerate()
+            .fold(0, |s, (r, row)| s | ((row & word).count_ones() & 1) << r)
+    }
+
+    /// `data` (bit `b` for data bit `b`) times G.
+    pub fn encode(&self, data: u32) -> u32 {
+        self.g
+            .iter()
+            .enumerate()
+            .filter(|(b, _)| (data >> b) & 1 == 1)
+            .fold(0, |word, (_, row)| word ^ row)
+    }
+
+    /// The data bits of `word`.
+    pub fn data(&self, word: u32) -> u32 {
+        self.data
+            .iter()
+            .enumerate()
+            .fold(0, |data, (b, &i)| data | ((word >> i) & 1) << b)
+    }
+
+    /// Corrects a single flipped bit by finding the column of H that matches the syndrome.
+    /// Returns `None` if no single flip explains it.
+    pub fn correct(&self, word: u32) -> Option<u32> {
+        let s = self.syndrome(word);
+        if s == 0 {
+            return Some(word);
+        }
+        (0..u32::BITS)
+            .find(|&i| self.syndrome(1 << i) == s)
+            .map(|i| word ^ 1 << i)
+    }
+}
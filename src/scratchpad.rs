@@ -0,0 +1,102 @@
+use egui::{Color32, Grid, RichText, TextEdit};
+
+/// Parses a string of `0`s and `1`s, ignoring whitespace and `_` separators.
+fn parse_bits(s: &str) -> Result<Vec<bool>, char> {
+    s.chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .map(|c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            c => Err(c),
+        })
+        .collect()
+}
+
+fn format_bits(bits: &[bool]) -> String {
+    bits.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
+
+/// Left-pads the shorter of `a` and `b` with zeros, as when writing numbers right-aligned.
+fn align(a: &[bool], b: &[bool]) -> (Vec<bool>, Vec<bool>) {
+    let len = a.len().max(b.len());
+    let pad = |x: &[bool]| {
+        let mut v = vec![false; len - x.len()];
+        v.extend_from_slice(x);
+        v
+    };
+    (pad(a), pad(b))
+}
+
+fn parity(bits: &[bool]) -> String {
+    let ones = bits.iter().filter(|&&b| b).count();
+    format!(
+        "{ones} ones, {} parity",
+        if ones % 2 == 0 { "even" } else { "odd" }
+    )
+}
+
+/// Two binary strings combined bitwise, for checking the decoder's work by hand.
+pub struct Scratchpad {
+    pub open: bool,
+    a: String,
+    b: String,
+}
+
+impl Scratchpad {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            a: String::new(),
+            b: String::new(),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Binary Scratchpad")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        Grid::new("scratchpad_inputs").show(ui, |ui| {
+            ui.label("A");
+            ui.add(TextEdit::singleline(&mut self.a).font(egui::TextStyle::Monospace));
+            ui.end_row();
+            ui.label("B");
+            ui.add(TextEdit::singleline(&mut self.b).font(egui::TextStyle::Monospace));
+            ui.end_row();
+        });
+        ui.separator();
+
+        let (a, b) = match (parse_bits(&self.a), parse_bits(&self.b)) {
+            (Ok(a), Ok(b)) => (a, b),
+            (Err(c), _) | (_, Err(c)) => {
+                ui.colored_label(Color32::RED, format!("{c:?} is not a binary digit."));
+                return;
+            }
+        };
+        let (a, b) = align(&a, &b);
+        let combine = |f: fn(bool, bool) -> bool| -> Vec<bool> {
+            a.iter().zip(&b).map(|(&x, &y)| f(x, y)).collect()
+        };
+
+        Grid::new("scratchpad_results")
+            .striped(true)
+            .show(ui, |ui| {
+                for (name, bits) in [
+                    ("A", a.clone()),
+                    ("B", b.clone()),
+                    ("A XOR B", combine(|x, y| x ^ y)),
+                    ("A AND B", combine(|x, y| x & y)),
+                    ("A OR B", combine(|x, y| x | y)),
+                ] {
+                    ui.label(name);
+                    ui.label(RichText::new(format_bits(&bits)).monospace());
+                    ui.label(parity(&bits));
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{export, format::InputFormat, panels::PanelId};
+use egui::{Align2, Color32, Key, RichText, TextEdit};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The error a scene sets up.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SceneError {
+    /// The error bits themselves.
+    Pattern(Vec<u8>),
+    /// Each bit flipped with chance `probability`, drawn from `seed`.
+    Seeded { seed: u64, probability: f64 },
+}
+
+/// One step of a demo script: the state the app is put in, and what to say about it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Scene {
+    pub name: String,
+    /// The name of the pipeline code.
+    pub code: String,
+    pub message_in: String,
+    #[serde(default)]
+    pub format: InputFormat,
+    pub error: SceneError,
+    /// The panel to draw attention to, if any.
+    #[serde(default)]
+    pub panel: Option<PanelId>,
+    /// Whether to dim every panel but `panel`.
+    #[serde(default)]
+    pub dim_others: bool,
+    /// Shown to the audience with the scene.
+    #[serde(default)]
+    pub note: String,
+    /// Shown only in the presenter notes window, for the person giving the lecture.
+    #[serde(default)]
+    pub presenter_notes: String,
+}
+
+fn read(path: &Path) -> Result<Vec<Scene>, String> {
+    let s = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&s).map_err(|e| e.to_string())
+}
+
+fn write(path: &Path, scenes: &[Scene]) -> Result<(), String> {
+    let s = serde_json::to_string_pretty(scenes).map_err(|e| e.to_string())?;
+    fs::write(path, s).map_err(|e| e.to_string())
+}
+
+/// What the app is asked to do by the demo script.
+pub enum Request {
+    /// Put the app in this scene.
+    Go(Scene),
+    /// Add the app as it is now to the script, as a new scene.
+    Capture,
+}
+
+/// An ordered list of scenes for a lecture, stepped through with Page Down and Page Up, and
+/// kept in a file between lectures.
+pub struct DemoScript {
+    pub open: bool,
+    /// Whether the presenter notes window is open. It is a window of its own so that it can be
+    /// moved out of the way of, or closed before, anything shared with the audience.
+    pub notes_open: bool,
+    scenes: Vec<Scene>,
+    /// The scene last gone to.
+    current: Option<usize>,
+    /// The outcome of the last open or save.
+    status: Option<Result<String, String>>,
+}
+
+impl DemoScript {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            notes_open: false,
+            scenes: Vec::new(),
+            current: None,
+            status: None,
+        }
+    }
+
+    pub fn scene_count(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// Adds `scene` after the current one, or at the end, and makes it current.
+    pub fn add(&mut self, scene: Scene) {
+        let i = self.current.map_or(self.scenes.len(), |i| i + 1);
+        self.scenes.insert(i, scene);
+        self.current = Some(i);
+    }
+
+    /// Steps through the scenes on Page Down and Page Up, and shows the script if it is open,
+    /// or else the current scene's note. Returns what the app should do, if anything.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Request> {
+        let mut request = None;
+        if !self.scenes.is_empty() && !ctx.wants_keyboard_input() {
+            let (next, previous) = {
+                let input = ctx.input();
+                (
+                    input.key_pressed(Key::PageDown),
+                    input.key_pressed(Key::PageUp),
+                )
+            };
+            let target = match self.current {
+                None if next || previous => Some(0),
+                Some(i) if next => (i + 1 < self.scenes.len()).then(|| i + 1),
+                Some(i) if previous => i.checked_sub(1),
+                _ => None,
+            };
+            if let Some(i) = target {
+                request = self.go(i);
+            }
+        }
+
+        if self.open {
+            let mut open = self.open;
+            egui::Window::new("Demo Script")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if let Some(r) = self.ui(ui) {
+                        request = Some(r);
+                    }
+                });
+            self.open = open;
+        } else if let Some(scene) = self.current.and_then(|i| self.scenes.get(i)) {
+            if !scene.note.is_empty() {
+                egui::Area::new("scene_note")
+                    .anchor(Align2::RIGHT_BOTTOM, [-10.0, -10.0])
+                    .show(ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.strong(&scene.name);
+                            ui.label(&scene.note);
+                        });
+                    });
+            }
+        }
+        if self.notes_open {
+            self.show_notes(ctx);
+        }
+        request
+    }
+
+    /// The current scene's presenter notes, and what comes next.
+    fn show_notes(&mut self, ctx: &egui::Context) {
+        let mut open = self.notes_open;
+        egui::Window::new("Presenter Notes")
+            .open(&mut open)
+            .default_width(240.0)
+            .show(ctx, |ui| {
+                let i = match self.current {
+                    Some(i) if i < self.scenes.len() => i,
+                    _ => {
+                        ui.label("Go to a scene to see its notes.");
+                        return;
+                    }
+                };
+                let scene = &self.scenes[i];
+                ui.strong(format!(
+                    "{} of {}: {}",
+                    i + 1,
+                    self.scenes.len(),
+                    scene.name
+                ));
+                if scene.presenter_notes.is_empty() {
+                    ui.weak("No notes for this scene.");
+                } else {
+                    ui.label(&scene.presenter_notes);
+                }
+                if let Some(next) = self.scenes.get(i + 1) {
+                    ui.separator();
+                    ui.small(format!("Next: {}", next.name));
+                }
+            });
+        self.notes_open = open;
+    }
+
+    fn go(&mut self, i: usize) -> Option<Request> {
+        self.current = Some(i);
+        self.scenes.get(i).cloned().map(Request::Go)
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) -> Option<Request> {
+        let mut request = None;
+        ui.horizontal(|ui| {
+            if ui.button("Open script...").clicked() {
+                if let Some(path) = export::open_path("Demo script", &["json"]) {
+                    let opened = read(&path).map(|scenes| {
+                        self.scenes = scenes;
+                        self.current = None;
+                    });
+                    self.status = Some(opened.map(|()| format!("Opened {}", path.display())));
+                }
+            }
+            if ui
+                .add_enabled(!self.scenes.is_empty(), egui::Button::new("Save script..."))
+                .clicked()
+            {
+                if let Some(path) = export::save_path("script.json", "Demo script", "json") {
+                    let saved = write(&path, &self.scenes);
+                    self.status = Some(saved.map(|()| format!("Saved to {}", path.display())));
+                }
+            }
+            if ui
+                .button("Add scene")
+                .on_hover_text("Adds the message, code and error as they are now.")
+                .clicked()
+            {
+                request = Some(Request::Capture);
+            }
+        });
+        match &self.status {
+            Some(Ok(s)) => {
+                ui.label(s.as_str());
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e.as_str());
+            }
+            None => {}
+        }
+        ui.small("Page Down and Page Up step through the scenes, with this window open or not.");
+        ui.separator();
+
+        if self.scenes.is_empty() {
+            ui.label("No scenes yet.");
+            return request;
+        }
+        let mut go = None;
+        let mut swap = None;
+        let mut remove = None;
+        let last = self.scenes.len() - 1;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for (i, scene) in self.scenes.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let mut title = RichText::new(format!("{}. {}", i + 1, scene.name));
+                    if self.current == Some(i) {
+                        title = title.strong();
+                    }
+                    if ui
+                        .selectable_label(self.current == Some(i), title)
+                        .clicked()
+                    {
+                        go = Some(i);
+                    }
+                    if ui
+                        .add_enabled(i > 0, egui::Button::new("⏶").small())
+                        .clicked()
+                    {
+                        swap = Some(i - 1);
+                    }
+                    if ui
+                        .add_enabled(i < last, egui::Button::new("⏷").small())
+                        .clicked()
+                    {
+                        swap = Some(i);
+                    }
+                    if ui.small_button("✕").clicked() {
+                        remove = Some(i);
+                    }
+                });
+                egui::CollapsingHeader::new("Edit")
+                    .id_source(("scene", i))
+                    .show(ui, |ui| {
+                        ui.add(TextEdit::singleline(&mut scene.name).hint_text("Name"));
+                        egui::ComboBox::from_id_source(("scene_panel", i))
+                            .selected_text(scene.panel.map_or("No panel", PanelId::title))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut scene.panel, None, "No panel");
+                                for id in PanelId::ALL {
+                                    ui.selectable_value(&mut scene.panel, Some(id), id.title());
+                                }
+                            });
+                        ui.add_enabled(
+                            scene.panel.is_some(),
+                            egui::Checkbox::new(&mut scene.dim_others, "Dim the other panels"),
+                        );
+                        ui.add(TextEdit::multiline(&mut scene.note).hint_text("Note"));
+                        ui.add(
+                            TextEdit::multiline(&mut scene.presenter_notes)
+                                .hint_text("Presenter notes"),
+                        );
+                    });
+            }
+        });
+        if let Some(i) = go {
+            request = self.go(i);
+        }
+        if let Some(i) = swap {
+            self.scenes.swap(i, i + 1);
+            self.current = self.current.map(|c| match c {
+                c if c == i => i + 1,
+                c if c == i + 1 => i,
+                c => c,
+            });
+        }
+        if let Some(i) = remove {
+            self.scenes.remove(i);
+            self.current = match self.current {
+                Some(c) if c == i => None,
+                Some(c) if c > i => Some(c - 1),
+                c => c,
+            };
+        }
+        request
+    }
+}
@@ -0,0 +1,271 @@
+use crate::panels::PanelId;
+use egui::{text::LayoutJob, Color32, Key, TextEdit, TextFormat, TextStyle};
+use std::ops::Range;
+
+/// How the query is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Text,
+    /// Pairs of hex digits, optionally separated by spaces or prefixed with `0x`.
+    Hex,
+    /// A single byte, in decimal.
+    Byte,
+}
+
+impl Mode {
+    const ALL: [Mode; 3] = [Mode::Text, Mode::Hex, Mode::Byte];
+
+    fn name(self) -> &'static str {
+        match self {
+            Mode::Text => "Text",
+            Mode::Hex => "Hex bytes",
+            Mode::Byte => "Byte value",
+        }
+    }
+
+    /// The bytes to look for, or why `query` doesn't give any.
+    fn pattern(self, query: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Mode::Text => Ok(query.as_bytes().to_vec()),
+            Mode::Hex => {
+                let digits: String = query
+                    .split_whitespace()
+                    .map(|w| w.trim_start_matches("0x"))
+                    .collect();
+                if !digits.is_ascii() {
+                    return Err("Hex digits are 0 to 9 and a to f.".to_string());
+                }
+                if !digits.len().is_multiple_of(2) {
+                    return Err("Hex needs two digits per byte.".to_string());
+                }
+                (0..digits.len())
+                    .step_by(2)
+                    .map(|i| {
+                        u8::from_str_radix(&digits[i..i + 2], 16)
+                            .map_err(|_| format!("{:?} isn't a hex byte.", &digits[i..i + 2]))
+                    })
+                    .collect()
+            }
+            Mode::Byte => query
+                .trim()
+                .parse::<u8>()
+                .map(|b| vec![b])
+                .map_err(|_| "Enter a byte value from 0 to 255.".to_string()),
+        }
+    }
+}
+
+/// Every place `pattern` occurs in `haystack`, overlapping or not.
+fn find_all(haystack: &[u8], pattern: &[u8]) -> Vec<Range<usize>> {
+    if pattern.is_empty() || pattern.len() > haystack.len() {
+        return Vec::new();
+    }
+    haystack
+        .windows(pattern.len())
+        .enumerate()
+        .filter(|(_, w)| *w == pattern)
+        .map(|(i, _)| i..i + pattern.len())
+        .collect()
+}
+
+/// The matches to highlight in one panel, and which of them is the current one.
+pub struct Highlights<'a> {
+    pub matches: &'a [Range<usize>],
+    pub current: usize,
+}
+
+impl Highlights<'_> {
+    fn background(&self, m: usize) -> Color32 {
+        if m == self.current {
+            Color32::from_rgb(230, 160, 40)
+        } else {
+            Color32::from_rgb(110, 90, 40)
+        }
+    }
+
+    /// The background for byte `i`, if it is part of a match.
+    pub fn color(&self, i: usize) -> Option<Color32> {
+        // Matches are in order of where they start.
+        let m = self
+            .matches
+            .partition_point(|m| m.start <= i)
+            .checked_sub(1)?;
+        self.matches[m].contains(&i).then(|| self.background(m))
+    }
+
+    /// Lays out `text` with the matched bytes highlighted, where byte `i` is drawn by the
+    /// characters in `text_range(i)`.
+    pub fn layout(
+        &self,
+        ui: &egui::Ui,
+        text: &str,
+        wrap_width: f32,
+        text_range: impl Fn(usize) -> Range<usize>,
+    ) -> LayoutJob {
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let mut job = LayoutJob::simple(String::new(), font_id.clone(), color, wrap_width);
+        let format = |background| TextFormat {
+            font_id: font_id.clone(),
+            color,
+            background,
+            ..Default::default()
+        };
+
+        let mut start = 0;
+        for (m, bytes) in self.matches.iter().enumerate() {
+            let from = floor_char_boundary(text, text_range(bytes.start).start);
+            let to = floor_char_boundary(text, text_range(bytes.end - 1).end);
+            // Overlapping matches are drawn as far as the first of them.
+            if from < start || from >= to {
+                continue;
+            }
+            job.append(&text[start..from], 0.0, format(Color32::TRANSPARENT));
+            job.append(&text[from..to], 0.0, format(self.background(m)));
+            start = to;
+        }
+        job.append(&text[start..], 0.0, format(Color32::TRANSPARENT));
+        job
+    }
+}
+
+fn floor_char_boundary(text: &str, mut i: usize) -> usize {
+    i = i.min(text.len());
+    while !text.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+/// Ctrl+F search for bytes or text within one of the panels.
+pub struct Search {
+    pub open: bool,
+    query: String,
+    mode: Mode,
+    scope: PanelId,
+    matches: Vec<Range<usize>>,
+    current: usize,
+    focus: bool,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            mode: Mode::Text,
+            scope: PanelId::Decoded,
+            matches: Vec::new(),
+            current: 0,
+            focus: false,
+        }
+    }
+
+    /// The matches in panel `id`, if that is the panel being searched.
+    pub fn highlights(&self, id: PanelId) -> Option<Highlights<'_>> {
+        (self.open && self.scope == id && !self.matches.is_empty()).then(|| Highlights {
+            matches: &self.matches,
+            current: self.current,
+        })
+    }
+
+    /// Shows the search bar along the bottom of the window. `contents` gives the bytes of each
+    /// panel.
+    pub fn show(&mut self, ctx: &egui::Context, contents: impl Fn(PanelId) -> Vec<u8>) {
+        {
+            let input = ctx.input();
+            if input.modifiers.command && input.key_pressed(Key::F) {
+                self.open = true;
+                self.focus = true;
+            }
+        }
+        if !self.open {
+            return;
+        }
+
+        let pattern = self.mode.pattern(&self.query);
+        let previous = self.matches.get(self.current).map(|m| m.start);
+        self.matches = match &pattern {
+            Ok(p) => find_all(&contents(self.scope), p),
+            Err(_) => Vec::new(),
+        };
+        // Stay on the same match while the contents change around it.
+        self.current = previous
+            .and_then(|start| self.matches.iter().position(|m| m.start >= start))
+            .unwrap_or(0);
+
+        egui::TopBottomPanel::bottom("search").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Find");
+                let response = ui.add(TextEdit::singleline(&mut self.query).desired_width(200.0));
+                if std::mem::take(&mut self.focus) {
+                    response.request_focus();
+                }
+                let (enter, shift, escape) = {
+                    let input = ui.input();
+                    (
+                        input.key_pressed(Key::Enter),
+                        input.modifiers.shift,
+                        input.key_pressed(Key::Escape),
+                    )
+                };
+                egui::ComboBox::from_id_source("search_mode")
+                    .selected_text(self.mode.name())
+                    .show_ui(ui, |ui| {
+                        for m in Mode::ALL {
+                            ui.selectable_value(&mut self.mode, m, m.name());
+                        }
+                    });
+                ui.label("in");
+                egui::ComboBox::from_id_source("search_scope")
+                    .selected_text(self.scope.title())
+                    .show_ui(ui, |ui| {
+                        for id in PanelId::ALL {
+                            ui.selectable_value(&mut self.scope, id, id.title());
+                        }
+                    });
+
+                let count = self.matches.len();
+                let prev = ui
+                    .add_enabled(count > 0, egui::Button::new("Previous"))
+                    .clicked()
+                    || (response.lost_focus() && enter && shift);
+                let next = ui
+                    .add_enabled(count > 0, egui::Button::new("Next"))
+                    .clicked()
+                    || (response.lost_focus() && enter && !shift);
+                if count > 0 {
+                    if prev {
+                        self.current = (self.current + count - 1) % count;
+                    }
+                    if next {
+                        self.current = (self.current + 1) % count;
+                    }
+                    if prev || next {
+                        self.focus = true;
+                    }
+                }
+
+                match &pattern {
+                    Err(e) if !self.query.is_empty() => {
+                        ui.colored_label(Color32::RED, e.as_str());
+                    }
+                    _ if self.query.is_empty() => {}
+                    _ if count == 0 => {
+                        ui.label("No matches.");
+                    }
+                    _ => {
+                        ui.label(format!(
+                            "Match {} of {count}, at byte {}",
+                            self.current + 1,
+                            self.matches[self.current].start
+                        ));
+                    }
+                }
+                if ui.button("Close").clicked() || escape {
+                    self.open = false;
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{analysis, codes::CodeInfo};
+use egui::{Color32, DragValue, Grid};
+
+/// What the decoder makes of a block with some bits flipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    /// The flips cancelled out.
+    Clean,
+    /// The decoder flipped the right bit back.
+    Corrected,
+    /// The decoder flagged the block as uncorrectable.
+    Detected,
+    /// The decoder flipped the wrong bit, making matters worse.
+    Miscorrected,
+    /// The flips made another codeword, which passes every check.
+    Undetected,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 5] = [
+        Outcome::Clean,
+        Outcome::Corrected,
+        Outcome::Detected,
+        Outcome::Miscorrected,
+        Outcome::Undetected,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Clean => "Clean",
+            Outcome::Corrected => "Corrected",
+            Outcome::Detected => "Detected",
+            Outcome::Miscorrected => "Miscorrected",
+            Outcome::Undetected => "Undetected",
+        }
+    }
+
+    fn color(self) -> Option<Color32> {
+        match self {
+            Outcome::Clean => None,
+            Outcome::Corrected => Some(Color32::GREEN),
+            Outcome::Detected => Some(Color32::YELLOW),
+            Outcome::Miscorrected | Outcome::Undetected => Some(Color32::RED),
+        }
+    }
+}
+
+/// One row of the table: what the decoder does with `error` in a block.
+struct Row {
+    syndrome: usize,
+    /// The bit the decoder flips, if it flips one.
+    flip: Option<usize>,
+    outcome: Outcome,
+    /// Data bits still wrong after decoding, when the decoder doesn't flag the block.
+    wrong: usize,
+}
+
+impl Row {
+    /// The code is linear, so what the decoder does depends only on the error, not on which
+    /// codeword it was added to.
+    fn new(info: &CodeInfo, error: &[bool]) -> Self {
+        let check = analysis::check(info, error);
+        let mut after = error.to_vec();
+        let outcome = match (check.distance, check.flip) {
+            (0, _) if !error.contains(&true) => Outcome::Clean,
+            (0, _) => Outcome::Undetected,
+            (1, Some(i)) => {
+                after[i] = !after[i];
+                if after.contains(&true) {
+                    Outcome::Miscorrected
+                } else {
+                    Outcome::Corrected
+                }
+            }
+            _ => Outcome::Detected,
+        };
+        let wrong = match outcome {
+            Outcome::Detected => 0,
+            _ => analysis::data_bits(info, &after)
+                .into_iter()
+                .filter(|&b| b)
+                .count(),
+        };
+        Self {
+            syndrome: check.syndrome,
+            flip: check.flip.filter(|_| check.distance == 1),
+            outcome,
+            wrong,
+        }
+    }
+}
+
+/// Picks one bit of a block and tries every second flip alongside it, showing in a small truth
+/// table what the decoder makes of each: the neighbourhood of one error, and where it crosses
+/// into another codeword's decoding region.
+pub struct Sensitivity {
+    pub open: bool,
+    block: usize,
+    /// The bit picked, counted from 0 within the block.
+    bit: usize,
+    /// Whether the flips go on top of the block's current error rather than a clean block.
+    on_error: bool,
+}
+
+impl Sensitivity {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            block: 0,
+            bit: 0,
+            on_error: false,
+        }
+    }
+
+    /// `error` is the current error pattern, which the flips can be added to.
+    pub fn show(&mut self, ctx: &egui::Context, info: &CodeInfo, error: &[u8]) {
+        let mut open = self.open;
+        egui::Window::new("Single-Bit Sensitivity")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info, error));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo, error: &[u8]) {
+        self.bit = self.bit.min(info.n - 1);
+        let blocks = analysis::codeword_count(info, error);
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Bit")
+                .selected_text(format!("Position {}", info.position(self.bit)))
+                .show_ui(ui, |ui| {
+                    for i in 0..info.n {
+                        let kind = if info.is_parity(i) { "parity" } else { "data" };
+                        let text = format!("Position {} ({kind})", info.position(i));
+                        ui.selectable_value(&mut self.bit, i, text);
+                    }
+                });
+            ui.add_enabled_ui(blocks > 0, |ui| {
+                ui.checkbox(&mut self.on_error, "On the error in block");
+                ui.add_enabled(
+                    self.on_error,
+                    DragValue::new(&mut self.block).clamp_range(0..=blocks.saturating_sub(1)),
+                );
+            });
+        });
+        let mut base = match analysis::codeword(info, error, self.block) {
+            Some(bits) if self.on_error => bits,
+            _ => vec![false; info.n],
+        };
+        base[self.bit] = !base[self.bit];
+        ui.small(format!(
+            "The bit at position {} is flipped{}, then each other bit of the block in turn. \
+             Every codeword decodes alike, so this holds for any data.",
+            info.position(self.bit),
+            if self.on_error && blocks > 0 {
+                " on top of the block's error"
+            } else {
+                ""
+            },
+        ));
+        ui.separator();
+
+        let rows: Vec<(Option<usize>, Row)> = std::iter::once(None)
+            .chain((0..info.n).filter(|&i| i != self.bit).map(Some))
+            .map(|also| {
+                let mut error = base.clone();
+                if let Some(i) = also {
+                    error[i] = !error[i];
+                }
+                (also, Row::new(info, &error))
+            })
+            .collect();
+        // The overall parity bit isn't part of the syndrome.
+        let width = (0..info.n)
+            .filter(|&i| info.is_parity(i) && info.position(i) != 0)
+            .count();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            Grid::new("sensitivity").striped(true).show(ui, |ui| {
+                ui.strong("Also flip");
+                ui.strong("Syndrome");
+                ui.strong("Decoder flips");
+                ui.strong("Outcome");
+                ui.strong("Data bits wrong");
+                ui.end_row();
+                for (also, row) in &rows {
+                    ui.label(also.map_or_else(
+                        || "Nothing".to_string(),
+                        |i| format!("Position {}", info.position(i)),
+                    ));
+                    ui.monospace(format!("{:0width$b}", row.syndrome));
+                    ui.label(row.flip.map_or_else(
+                        || "-".to_string(),
+                        |i| format!("Position {}", info.position(i)),
+                    ));
+                    match row.outcome.color() {
+                        Some(color) => ui.colored_label(color, row.outcome.name()),
+                        None => ui.label(row.outcome.name()),
+                    };
+                    ui.label(row.wrong.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+        ui.separator();
+        let second = &rows[1..];
+        let tally: Vec<String> = Outcome::ALL
+            .into_iter()
+            .map(|o| (o, second.iter().filter(|(_, r)| r.outcome == o).count()))
+            .filter(|&(_, count)| count > 0)
+            .map(|(o, count)| format!("{count} {}", o.name().to_lowercase()))
+            .collect();
+        ui.label(format!(
+            "Of the {} second flips: {}.",
+            second.len(),
+            tally.join(", ")
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::format::InputFormat;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// The part of the app state that is shared between instances, and saved to session files.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// The name of the pipeline code. Empty in sessions saved before the code could be chosen,
+    /// which leaves the code as it is.
+    #[serde(default)]
+    pub code: String,
+    pub message_in: String,
+    /// Missing from sessions saved before messages could be given in hex or Base64.
+    #[serde(default)]
+    pub format: InputFormat,
+    pub error: Vec<u8>,
+    pub prob_str: String,
+    /// Missing from sessions saved before the error could be randomized with a given seed.
+    #[serde(default)]
+    pub seed_str: String,
+    /// Codewords per block of the interleaver. Missing from sessions saved before interleaving,
+    /// and so 0, which is taken as 1: no interleaving.
+    #[serde(default)]
+    pub interleave_depth: usize,
+    /// Missing from sessions saved before notes existed.
+    #[serde(default)]
+    pub notes: Notes,
+    /// The seed and probability `error` was drawn from, if it hasn't been edited since, so that
+    /// the run can be repeated. The seed is only in `seed_str` if it was typed in. Missing from
+    /// sessions saved before it was kept.
+    #[serde(default)]
+    pub randomized: Option<(u64, f64)>,
+}
+
+/// Free-text notes for explaining a prepared demo, shown as tooltips.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Notes {
+    pub session: String,
+    /// Notes on codewords, by codeword index.
+    pub blocks: BTreeMap<usize, String>,
+    /// Notes on bits of the encoded stream, by bit index.
+    pub bits: BTreeMap<usize, String>,
+}
+
+impl Notes {
+    /// The notes that apply to `bit`, which is in `codeword`, most specific first.
+    pub fn for_bit(&self, codeword: usize, bit: usize) -> Vec<&str> {
+        [self.bits.get(&bit), self.blocks.get(&codeword)]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|n| !n.is_empty())
+            .collect()
+    }
+}
+
+pub fn parse(s: &str) -> Result<Session, String> {
+    serde_json::from_str(s).map_err(|e| e.to_string())
+}
+
+pub fn read(path: &Path) -> Result<Session, String> {
+    parse(&fs::read_to_string(path).map_err(|e| e.to_string())?)
+}
+
+pub fn write(path: &Path, session: &Session) -> Result<(), String> {
+    // Write then rename, so anyone watching the file never reads it half-written.
+    let tmp = path.with_extension("tmp");
+    let s = serde_json::to_string(session).map_err(|e| e.to_string())?;
+    fs::write(&tmp, s).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
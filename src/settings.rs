@@ -0,0 +1,191 @@
+use crate::{
+    anim::{AnimationSettings, Easing},
+    locale::DecimalSeparator,
+    panels::{Arrangement, ByteDisplay, PanelId},
+    storage,
+};
+use egui::{Color32, ComboBox, Sense, Slider, Stroke, Visuals};
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+
+const FILE: &str = "settings.json";
+
+/// egui's own default for `Style::animation_time`.
+const EGUI_ANIMATION_TIME: f32 = 1.0 / 12.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub animation: AnimationSettings,
+    pub decimal_separator: DecimalSeparator,
+    pub arrangement: Arrangement,
+    pub theme: Theme,
+    /// How the Encoded, Error and With Error panels write their bytes.
+    pub byte_display: ByteDisplay,
+    /// Panels left out of the main window.
+    pub hidden_panels: Vec<PanelId>,
+    /// Whether the bar of error statistics along the bottom of the window is hidden.
+    pub hide_statistics: bool,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        storage::load(FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(FILE, self)
+    }
+
+    /// Applies the settings that egui itself is responsible for.
+    pub fn apply(&self, ctx: &egui::Context) {
+        let animation_time = self.animation.duration(EGUI_ANIMATION_TIME);
+        if ctx.style().animation_time != animation_time {
+            let mut style = (*ctx.style()).clone();
+            style.animation_time = animation_time;
+            ctx.set_style(style);
+        }
+        let dark = self.theme == Theme::Dark;
+        if ctx.style().visuals.dark_mode != dark {
+            ctx.set_visuals(if dark {
+                Visuals::dark()
+            } else {
+                Visuals::light()
+            });
+        }
+    }
+}
+
+pub struct SettingsWindow {
+    pub open: bool,
+    preview: Option<Instant>,
+    error: Option<String>,
+}
+
+impl SettingsWindow {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            preview: None,
+            error: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &mut Settings) {
+        let mut open = self.open;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, settings));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        let before = settings.clone();
+
+        ui.heading("Layout");
+        let arrangement = &mut settings.arrangement;
+        ComboBox::from_label("Stages")
+            .selected_text(arrangement.name())
+            .show_ui(ui, |ui| {
+                for a in Arrangement::ALL {
+                    ui.selectable_value(arrangement, a, a.name());
+                }
+            });
+        let theme = &mut settings.theme;
+        ComboBox::from_label("Theme")
+            .selected_text(theme.name())
+            .show_ui(ui, |ui| {
+                for t in Theme::ALL {
+                    ui.selectable_value(theme, t, t.name());
+                }
+            });
+        let byte_display = &mut settings.byte_display;
+        ComboBox::from_label("Bytes")
+            .selected_text(byte_display.name())
+            .show_ui(ui, |ui| {
+                for d in ByteDisplay::ALL {
+                    ui.selectable_value(byte_display, d, d.name());
+                }
+            });
+
+        ui.separator();
+        ui.heading("Animation");
+        let animation = &mut settings.animation;
+        ui.checkbox(&mut animation.reduce_motion, "Reduce motion");
+        ui.add_enabled_ui(!animation.reduce_motion, |ui| {
+            ui.add(
+                Slider::new(&mut animation.speed, 0.25..=4.0)
+                    .logarithmic(true)
+                    .text("Speed"),
+            );
+            ComboBox::from_label("Easing")
+                .selected_text(animation.easing.name())
+                .show_ui(ui, |ui| {
+                    for easing in Easing::ALL {
+                        ui.selectable_value(&mut animation.easing, easing, easing.name());
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("Preview").clicked() {
+                self.preview = Some(Instant::now());
+            }
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(160.0, 16.0), Sense::hover());
+            let t = match self.preview {
+                Some(start) => animation.progress(start.elapsed().as_secs_f32(), 1.0),
+                None => 0.0,
+            };
+            if t >= 1.0 {
+                self.preview = None;
+            }
+            let painter = ui.painter();
+            painter.line_segment(
+                [rect.left_center(), rect.right_center()],
+                Stroke::new(1.0, Color32::GRAY),
+            );
+            let x = rect.left() + 6.0 + t * (rect.width() - 12.0);
+            painter.circle_filled(egui::pos2(x, rect.center().y), 6.0, Color32::LIGHT_BLUE);
+        });
+
+        ui.separator();
+        ui.heading("Numbers");
+        let separator = &mut settings.decimal_separator;
+        ComboBox::from_label("Decimal separator")
+            .selected_text(separator.name())
+            .show_ui(ui, |ui| {
+                for s in DecimalSeparator::ALL {
+                    ui.selectable_value(separator, s, s.name());
+                }
+            });
+        ui.label(format!(
+            "Numbers are shown as {}",
+            separator.format(0.25, 2)
+        ));
+
+        if *settings != before {
+            self.error = settings.save().err();
+        }
+        if let Some(e) = &self.error {
+            ui.colored_label(Color32::RED, e.as_str());
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use crate::session::{self, Session};
+use egui::{Color32, ComboBox, TextEdit};
+use std::{collections::BTreeMap, fs, path::PathBuf, time::SystemTime};
+
+#[cfg(feature = "network")]
+use crate::bit_grid::bit_grid;
+#[cfg(feature = "network")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "network")]
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+#[cfg(feature = "network")]
+const USER_COLORS: [Color32; 8] = [
+    Color32::from_rgb(230, 80, 80),
+    Color32::from_rgb(80, 160, 230),
+    Color32::from_rgb(90, 190, 90),
+    Color32::from_rgb(230, 170, 50),
+    Color32::from_rgb(170, 100, 220),
+    Color32::from_rgb(60, 190, 180),
+    Color32::from_rgb(220, 110, 170),
+    Color32::from_rgb(150, 150, 90),
+];
+
+/// The color used for bits flipped by `user`. The leader is always user 0.
+#[cfg(feature = "network")]
+pub fn user_color(user: usize) -> Color32 {
+    USER_COLORS[user % USER_COLORS.len()]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Off,
+    Lead,
+    Follow,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Role::Off => "Off",
+            Role::Lead => "Lead",
+            Role::Follow => "Follow",
+        }
+    }
+}
+
+/// Where the shared state lives: `tcp://host:port` or a file path.
+enum Target {
+    File(PathBuf),
+    #[cfg(feature = "network")]
+    Tcp(String),
+}
+
+impl Target {
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            Err("No file or tcp:// address given".to_string())
+        } else if s.starts_with("tcp://") && !cfg!(feature = "network") {
+            Err("This build can only share through a file".to_string())
+        } else {
+            #[cfg(feature = "network")]
+            if let Some(addr) = s.strip_prefix("tcp://") {
+                return Ok(Target::Tcp(addr.to_string()));
+            }
+            Ok(Target::File(PathBuf::from(s)))
+        }
+    }
+}
+
+/// The line-delimited JSON protocol spoken over TCP.
+#[cfg(feature = "network")]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Message {
+    Welcome {
+        user: usize,
+    },
+    State {
+        session: Box<Session>,
+        /// `(bit, user)` for every set error bit whose flipper is known.
+        flips: Vec<(usize, usize)>,
+    },
+    Toggle {
+        bit: usize,
+    },
+}
+
+#[cfg(feature = "network")]
+impl Message {
+    fn to_line(&self) -> Result<String, String> {
+        serde_json::to_string(self)
+            .map(|s| s + "\n")
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "network")]
+enum FollowEvent {
+    Connected(TcpStream),
+    Message(Message),
+    Error(String),
+}
+
+enum Link {
+    FileLead {
+        path: PathBuf,
+    },
+    FileFollow {
+        path: PathBuf,
+        modified: Option<SystemTime>,
+    },
+    #[cfg(feature = "network")]
+    TcpLead {
+        tx: Sender<String>,
+        toggles: Receiver<(usize, usize)>,
+    },
+    #[cfg(feature = "network")]
+    TcpFollow {
+        rx: Receiver<FollowEvent>,
+        tx: Sender<String>,
+        stream: Option<TcpStream>,
+    },
+}
+
+/// Mirrors the live state of a leading instance into following instances. Followers are
+/// read-only, except that over TCP they may flip error bits on the leader's transmission.
+pub struct Share {
+    role: Role,
+    target: String,
+    link: Option<Link>,
+    status: Option<Result<String, String>>,
+    published: Option<(Session, Vec<(usize, usize)>)>,
+    #[cfg(feature = "network")]
+    user: Option<usize>,
+    owners: BTreeMap<usize, usize>,
+    #[cfg(feature = "network")]
+    allow_edits: bool,
+}
+
+impl Share {
+    pub fn new() -> Self {
+        Self {
+            role: Role::Off,
+            target: String::new(),
+            link: None,
+            status: None,
+            published: None,
+            #[cfg(feature = "network")]
+            user: None,
+            owners: BTreeMap::new(),
+            #[cfg(feature = "network")]
+            allow_edits: true,
+        }
+    }
+
+    pub fn is_leading(&self) -> bool {
+        match self.link {
+            Some(Link::FileLead { .. }) => true,
+            #[cfg(feature = "network")]
+            Some(Link::TcpLead { .. }) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_following(&self) -> bool {
+        match self.link {
+            Some(Link::FileFollow { .. }) => true,
+            #[cfg(feature = "network")]
+            Some(Link::TcpFollow { .. }) => true,
+            _ => false,
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn is_collaborative(&self) -> bool {
+        matches!(
+            self.link,
+            Some(Link::TcpLead { .. } | Link::TcpFollow { .. })
+        )
+    }
+
+    /// Whether the current link has been sent a state yet.
+    pub fn has_published(&self) -> bool {
+        self.published.is_some()
+    }
+
+    /// Returns the newest state from the leader, if one arrived since the last call.
+    pub fn poll(&mut self) -> Option<Session> {
+        let mut latest = None;
+        let mut status = None;
+        match &mut self.link {
+            Some(Link::FileFollow { path, modified }) => {
+                let current = fs::metadata(&*path).and_then(|m| m.modified()).ok();
+                if current.is_some() && current != *modified {
+                    *modified = current;
+                    match session::read(path) {
+                        Ok(session) => latest = Some(session),
+                        Err(e) => status = Some(Err(e)),
+                    }
+                }
+            }
+            #[cfg(feature = "network")]
+            Some(Link::TcpFollow { rx, stream, .. }) => loop {
+                match rx.try_recv() {
+                    Ok(FollowEvent::Connected(s)) => *stream = Some(s),
+                    Ok(FollowEvent::Message(Message::Welcome { user })) => self.user = Some(user),
+                    Ok(FollowEvent::Message(Message::State { session, flips })) => {
+                        self.owners = flips.into_iter().collect();
+                        latest = Some(*session);
+                    }
+                    Ok(FollowEvent::Message(Message::Toggle { .. })) => {}
+                    Ok(FollowEvent::Error(e)) => status = Some(Err(e)),
+                    Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+                }
+            },
+            _ => {}
+        }
+        if latest.is_some() {
+            status = Some(Ok("Following".to_string()));
+        }
+        if status.is_some() {
+            self.status = status;
+        }
+        latest
+    }
+
+    /// Applies the bit flips requested by followers since the last call.
+    #[cfg(feature = "network")]
+    pub fn apply_toggles(&mut self, error: &mut [u8]) {
+        let mut requested = Vec::new();
+        if let Some(Link::TcpLead { toggles, .. }) = &self.link {
+            requested.extend(toggles.try_iter());
+        }
+        if !self.allow_edits {
+            return;
+        }
+        for (user, bit) in requested {
+            self.flip(bit, user, error);
+        }
+    }
+
+    /// Flips `bit` on behalf of this instance: directly when leading, by request when following.
+    #[cfg(feature = "network")]
+    pub fn toggle(&mut self, bit: usize, error: &mut [u8]) {
+        match &self.link {
+            Some(Link::TcpLead { .. }) => self.flip(bit, 0, error),
+            Some(Link::TcpFollow { tx, .. }) => {
+                if let Ok(line) = (Message::Toggle { bit }).to_line() {
+                    let _ = tx.send(line);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[cfg(feature = "network")]
+    fn flip(&mut self, bit: usize, user: usize, error: &mut [u8]) {
+        if let Some(byte) = error.get_mut(bit / 8) {
+            *byte ^= 1 << (bit % 8);
+            self.owners.insert(bit, user);
+        }
+    }
+
+    /// Sends the current state to followers if it changed since it was last sent.
+    pub fn publish(&mut self, session: &Session) {
+        let error = &session.error;
+        self.owners.retain(|&bit, _| {
+            error
+                .get(bit / 8)
+                .is_some_and(|&b| (b >> (bit % 8)) & 1 == 1)
+        });
+        let flips: Vec<_> = self
+            .owners
+            .iter()
+            .map(|(&bit, &user)| (bit, user))
+            .collect();
+        if self
+            .published
+            .as_ref()
+            .is_some_and(|(s, f)| s == session && *f == flips)
+        {
+            return;
+        }
+        let result = match &self.link {
+            Some(Link::FileLead { path }) => session::write(path, session),
+            #[cfg(feature = "network")]
+            Some(Link::TcpLead { tx, .. }) => Message::State {
+                session: Box::new(session.clone()),
+                flips: flips.clone(),
+            }
+            .to_line()
+            .and_then(|line| {
+                tx.send(line)
+                    .map_err(|_| "Sharing thread stopped".to_string())
+            }),
+            _ => return,
+        };
+        match result {
+            Ok(()) => {
+                self.published = Some((session.clone(), flips));
+                self.status = Some(Ok("Leading".to_string()));
+            }
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+
+    fn start(&mut self) {
+        self.stop();
+        let target = match Target::parse(&self.target) {
+            Ok(target) => target,
+            Err(e) => {
+                self.status = Some(Err(e));
+                return;
+            }
+        };
+        let link = match (self.role, target) {
+            (Role::Off, _) => return,
+            (Role::Lead, Target::File(path)) => Ok(Link::FileLead { path }),
+            (Role::Follow, Target::File(path)) => Ok(Link::FileFollow {
+                path,
+                modified: None,
+            }),
+            #[cfg(feature = "network")]
+            (Role::Lead, Target::Tcp(addr)) => {
+                spawn_tcp_lead(&addr).map(|(tx, toggles)| Link::TcpLead { tx, toggles })
+            }
+            #[cfg(feature = "network")]
+            (Role::Follow, Target::Tcp(addr)) => {
+                let (rx, tx) = spawn_tcp_follow(addr);
+                Ok(Link::TcpFollow {
+                    rx,
+                    tx,
+                    stream: None,
+                })
+            }
+        };
+        match link {
+            Ok(link) => {
+                #[cfg(feature = "network")]
+                if matches!(link, Link::TcpLead { .. }) {
+                    self.user = Some(0);
+                }
+                self.link = Some(link);
+                self.status = Some(Ok("Started".to_string()));
+            }
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+
+    fn stop(&mut self) {
+        #[cfg(feature = "network")]
+        if let Some(Link::TcpFollow {
+            stream: Some(stream),
+            ..
+        }) = &self.link
+        {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        self.link = None;
+        self.published = None;
+        self.status = None;
+        #[cfg(feature = "network")]
+        {
+            self.user = None;
+        }
+        self.owners.clear();
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Share:");
+            let active = self.link.is_some();
+            ui.add_enabled_ui(!active, |ui| {
+                ComboBox::from_id_source("share_role")
+                    .selected_text(self.role.name())
+                    .show_ui(ui, |ui| {
+                        for role in [Role::Off, Role::Lead, Role::Follow] {
+                            ui.selectable_value(&mut self.role, role, role.name());
+                        }
+                    });
+                ui.add(TextEdit::singleline(&mut self.target).hint_text(
+                    if cfg!(feature = "network") {
+                        "file path or tcp://host:port"
+                    } else {
+                        "file path"
+                    },
+                ));
+            });
+            if active {
+                if ui.button("Stop").clicked() {
+                    self.stop();
+                }
+            } else if ui
+                .add_enabled(self.role != Role::Off, egui::Button::new("Start"))
+                .clicked()
+            {
+                self.start();
+            }
+            match &self.status {
+                Some(Ok(s)) => {
+                    ui.label(s.as_str());
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, e.as_str());
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// Shows the shared transmission, where every participant can flip error bits.
+    #[cfg(feature = "network")]
+    pub fn show_collab(&mut self, ctx: &egui::Context, error: &mut [u8]) {
+        if !self.is_collaborative() {
+            return;
+        }
+        egui::Window::new("Shared Error").show(ctx, |ui| {
+            match self.user {
+                Some(user) => {
+                    ui.horizontal(|ui| {
+                        ui.label("You are");
+                        ui.colored_label(user_color(user), format!("user {user}"));
+                    });
+                }
+                None => {
+                    ui.label("Connecting...");
+                }
+            }
+            if self.is_leading() {
+                ui.checkbox(&mut self.allow_edits, "Allow followers to flip bits");
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                let faint = ui.visuals().faint_bg_color;
+                let owners = &self.owners;
+                let response = bit_grid(ui, error, |bit, set| match (set, owners.get(&bit)) {
+                    (true, Some(&user)) => user_color(user),
+                    (true, None) => Color32::GRAY,
+                    (false, _) => faint,
+                });
+                if let Some(bit) = response.clicked {
+                    self.toggle(bit, error);
+                }
+            });
+        });
+    }
+}
+
+#[cfg(feature = "network")]
+type LeadChannels = (Sender<String>, Receiver<(usize, usize)>);
+
+#[cfg(feature = "network")]
+fn spawn_tcp_lead(addr: &str) -> Result<LeadChannels, String> {
+    let listener = TcpListener::bind(addr).map_err(|e| e.to_string())?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let (tx, rx) = mpsc::channel::<String>();
+    let (toggle_tx, toggle_rx) = mpsc::channel::<(usize, usize)>();
+    thread::spawn(move || {
+        let mut followers: Vec<TcpStream> = Vec::new();
+        let mut latest: Option<String> = None;
+        let mut next_user = 1;
+        loop {
+            while let Ok((mut stream, _)) = listener.accept() {
+                let user = next_user;
+                next_user += 1;
+                let _ = stream.set_nonblocking(false);
+                let _ = stream.set_write_timeout(Some(Duration::from_secs(1)));
+
+                let welcome = match (Message::Welcome { user }).to_line() {
+                    Ok(line) => line,
+                    Err(_) => continue,
+                };
+                if stream.write_all(welcome.as_bytes()).is_err() {
+                    continue;
+                }
+                if let Some(line) = &latest {
+                    if stream.write_all(line.as_bytes()).is_err() {
+                        continue;
+                    }
+                }
+
+                if let Ok(reader) = stream.try_clone() {
+                    let toggle_tx = toggle_tx.clone();
+                    thread::spawn(move || {
+                        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                            if let Ok(Message::Toggle { bit }) = serde_json::from_str(&line) {
+                                if toggle_tx.send((user, bit)).is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                    });
+                }
+                followers.push(stream);
+            }
+            match rx.recv_timeout(Duration::from_millis(50)) {
+                Ok(line) => {
+                    followers.retain_mut(|s| s.write_all(line.as_bytes()).is_ok());
+                    latest = Some(line);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        for stream in followers {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    });
+    Ok((tx, toggle_rx))
+}
+
+#[cfg(feature = "network")]
+fn spawn_tcp_follow(addr: String) -> (Receiver<FollowEvent>, Sender<String>) {
+    let (in_tx, in_rx) = mpsc::channel();
+    let (out_tx, out_rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        let stream = match TcpStream::connect(&addr) {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = in_tx.send(FollowEvent::Error(e.to_string()));
+                return;
+            }
+        };
+        match (stream.try_clone(), stream.try_clone()) {
+            (Ok(handle), Ok(mut writer)) => {
+                let _ = in_tx.send(FollowEvent::Connected(handle));
+                thread::spawn(move || {
+                    for line in out_rx {
+                        if writer.write_all(line.as_bytes()).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                let _ = in_tx.send(FollowEvent::Error(e.to_string()));
+                return;
+            }
+        }
+        for line in BufReader::new(stream).lines() {
+            let event = match line
+                .map_err(|e| e.to_string())
+                .and_then(|line| serde_json::from_str(&line).map_err(|e| e.to_string()))
+            {
+                Ok(msg) => FollowEvent::Message(msg),
+                Err(e) => FollowEvent::Error(e),
+            };
+            if in_tx.send(event).is_err() {
+                return;
+            }
+        }
+        let _ = in_tx.send(FollowEvent::Error("Leader disconnected".to_string()));
+    });
+    (in_rx, out_tx)
+}
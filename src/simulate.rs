@@ -0,0 +1,332 @@
+use crate::{
+    codes::CodeInfo,
+    corrections::{self, Corrections},
+    decoders, export, manifest,
+};
+use egui::{
+    plot::{Legend, Line, Plot, PlotPoints, Points},
+    Color32, DragValue, Grid, ProgressBar,
+};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::Duration;
+
+/// How long each frame may spend on trials, so the window stays responsive.
+const FRAME_BUDGET: Duration = Duration::from_millis(10);
+
+/// Trials at one error probability.
+struct Point {
+    probability: f64,
+    trials: usize,
+    /// Trials that decoded to the whole message.
+    successes: usize,
+    /// Bits sent through the channel, how many it flipped, and how many of those were flipped
+    /// back by correcting each codeword on its own.
+    channel_bits: u64,
+    channel_flips: u64,
+    bits_corrected: u64,
+    /// Message bits sent, and how many came out of the decoder wrong.
+    data_bits: u64,
+    residual_errors: u64,
+    /// Codewords sent, and how many came through right.
+    codewords: u64,
+    codewords_decoded: u64,
+}
+
+impl Point {
+    fn new(probability: f64) -> Self {
+        Self {
+            probability,
+            trials: 0,
+            successes: 0,
+            channel_bits: 0,
+            channel_flips: 0,
+            bits_corrected: 0,
+            data_bits: 0,
+            residual_errors: 0,
+            codewords: 0,
+            codewords_decoded: 0,
+        }
+    }
+
+    /// Encodes `message`, sends it through the channel and decodes it. A stream that doesn't
+    /// decode as a whole is decoded codeword by codeword, the codewords that can't be corrected
+    /// giving zeros.
+    fn trial(&mut self, code: &Code, info: &CodeInfo, message: &[u8], seed: u64) {
+        let encoded = encode(message, code).unwrap();
+        let error = manifest::error_pattern(seed, encoded.len(), self.probability);
+        let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+            .map(|(b, e)| b ^ e)
+            .collect();
+        let decoded = decode(&received, code).ok();
+        if decoded.as_deref() == Some(message) {
+            self.successes += 1;
+        }
+        let decoded =
+            decoded.or_else(|| decoders::decode_blocks(code, info, &received).map(|(d, _)| d));
+        self.residual_errors += match decoded {
+            Some(decoded) => Iterator::zip(message.iter(), decoded.iter())
+                .map(|(a, b)| u64::from((a ^ b).count_ones()))
+                .sum(),
+            // Nothing came back, so every bit is as good as a guess.
+            None => message.len() as u64 * 4,
+        };
+        self.trials += 1;
+        self.channel_bits += encoded.len() as u64 * 8;
+        self.channel_flips += error.iter().map(|b| u64::from(b.count_ones())).sum::<u64>();
+        self.data_bits += message.len() as u64 * 8;
+        let corrections = Corrections::by_codeword(info, &received);
+        for t in corrections::tally(info, &error, &corrections) {
+            self.bits_corrected += t.corrected as u64;
+            self.codewords += 1;
+            self.codewords_decoded += u64::from(t.residual == 0);
+        }
+    }
+
+    fn channel_ber(&self) -> f64 {
+        self.channel_flips as f64 / self.channel_bits.max(1) as f64
+    }
+
+    fn residual_ber(&self) -> f64 {
+        self.residual_errors as f64 / self.data_bits.max(1) as f64
+    }
+
+    fn success_rate(&self) -> f64 {
+        self.successes as f64 / self.trials.max(1) as f64
+    }
+}
+
+/// A row for each error probability of `points`, with its totals across every trial.
+fn to_csv(points: &[Point]) -> String {
+    let mut csv = "probability,trials,channel_bits,bits_flipped,bits_corrected,residual_errors,\
+                   codewords,codewords_decoded,messages_decoded\n"
+        .to_string();
+    for p in points.iter().filter(|p| p.trials > 0) {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            p.probability,
+            p.trials,
+            p.channel_bits,
+            p.channel_flips,
+            p.bits_corrected,
+            p.residual_errors,
+            p.codewords,
+            p.codewords_decoded,
+            p.successes
+        ));
+    }
+    csv
+}
+
+struct Run {
+    /// The code the run is for.
+    code: &'static str,
+    rng: StdRng,
+    message: Vec<u8>,
+    trials_per_point: usize,
+    points: Vec<Point>,
+    /// Trials done across every point. They go round the points in turn, so that the curves
+    /// fill in together.
+    done: usize,
+    stopped: bool,
+}
+
+impl Run {
+    fn total(&self) -> usize {
+        self.trials_per_point * self.points.len()
+    }
+
+    fn running(&self) -> bool {
+        !self.stopped && self.done < self.total()
+    }
+}
+
+/// Runs many random trials at a range of error probabilities and plots how the code copes,
+/// against the bit error rate of the channel.
+pub struct Simulate {
+    pub open: bool,
+    trials_per_point: usize,
+    message_len: usize,
+    /// The error probabilities run at are spaced evenly in log scale from `10^min_exponent` to
+    /// `10^max_exponent`.
+    min_exponent: f64,
+    max_exponent: f64,
+    point_count: usize,
+    run: Option<Run>,
+    /// The outcome of the last export.
+    export_status: Option<Result<String, String>>,
+}
+
+impl Simulate {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            trials_per_point: 200,
+            message_len: 64,
+            min_exponent: -4.0,
+            max_exponent: -1.0,
+            point_count: 10,
+            run: None,
+            export_status: None,
+        }
+    }
+
+    /// Runs a frame's worth of trials while a run is going, and shows the window.
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code, info: &CodeInfo) {
+        // A run for another code no longer applies.
+        if matches!(&self.run, Some(run) if run.code != info.name) {
+            self.run = None;
+        }
+        if let Some(run) = self.run.as_mut().filter(|run| run.running()) {
+            let start = Instant::now();
+            while run.running() && start.elapsed() < FRAME_BUDGET {
+                run.rng.fill(&mut run.message[..]);
+                let seed = manifest::new_seed(&mut run.rng);
+                let i = run.done % run.points.len();
+                run.points[i].trial(code, info, &run.message, seed);
+                run.done += 1;
+            }
+            ctx.request_repaint();
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Simulate")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info));
+        self.open = open;
+    }
+
+    fn start(&mut self, info: &CodeInfo) {
+        let steps = self.point_count.max(2) - 1;
+        let points = (0..=steps)
+            .map(|i| {
+                let t = i as f64 / steps as f64;
+                let exponent = self.min_exponent + t * (self.max_exponent - self.min_exponent);
+                Point::new(10f64.powf(exponent))
+            })
+            .collect();
+        self.run = Some(Run {
+            code: info.name,
+            rng: StdRng::from_entropy(),
+            message: vec![0; self.message_len],
+            trials_per_point: self.trials_per_point,
+            points,
+            done: 0,
+            stopped: false,
+        });
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo) {
+        let running = matches!(&self.run, Some(run) if run.running());
+        ui.add_enabled_ui(!running, |ui| {
+            Grid::new("simulate_settings").show(ui, |ui| {
+                ui.label("Error probabilities");
+                ui.horizontal(|ui| {
+                    ui.label("1e");
+                    ui.add(
+                        DragValue::new(&mut self.min_exponent)
+                            .clamp_range(-8.0..=self.max_exponent)
+                            .speed(0.1),
+                    );
+                    ui.label("to 1e");
+                    ui.add(
+                        DragValue::new(&mut self.max_exponent)
+                            .clamp_range(self.min_exponent..=0.0)
+                            .speed(0.1),
+                    );
+                });
+                ui.end_row();
+                ui.label("Points");
+                ui.add(DragValue::new(&mut self.point_count).clamp_range(2..=40));
+                ui.end_row();
+                ui.label("Trials per point");
+                ui.add(DragValue::new(&mut self.trials_per_point).clamp_range(1..=100_000));
+                ui.end_row();
+                ui.label("Message length");
+                ui.add(
+                    DragValue::new(&mut self.message_len)
+                        .clamp_range(1..=4096)
+                        .suffix(" bytes"),
+                );
+                ui.end_row();
+            });
+        });
+        ui.horizontal(|ui| {
+            if running {
+                if ui.button("Stop").clicked() {
+                    // What has been done so far is kept.
+                    if let Some(run) = &mut self.run {
+                        run.stopped = true;
+                    }
+                }
+            } else if ui.button(format!("Simulate {}", info.name)).clicked() {
+                self.start(info);
+            }
+        });
+        let run = match &self.run {
+            Some(run) => run,
+            None => {
+                ui.label("Runs random messages through the channel at each error probability.");
+                return;
+            }
+        };
+        let progress = run.done as f32 / run.total().max(1) as f32;
+        ui.add(ProgressBar::new(progress).text(format!("{} of {} trials", run.done, run.total())));
+        ui.horizontal(|ui| {
+            if ui.button("Export CSV").clicked() {
+                let name = format!("simulation_{}.csv", run.code).to_lowercase();
+                self.export_status =
+                    match export::save_text(&name, "CSV", "csv", &to_csv(&run.points)) {
+                        Ok(Some(path)) => Some(Ok(format!("Saved {}", path.display()))),
+                        Ok(None) => self.export_status.take(),
+                        Err(e) => Some(Err(e)),
+                    };
+            }
+            match &self.export_status {
+                Some(Ok(msg)) => {
+                    ui.label(msg.as_str());
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, e.as_str());
+                }
+                None => {}
+            }
+        });
+
+        // Plotted as log10 of the bit error rates, which egui's plots can't do for themselves.
+        let log = |x: f64| (x > 0.0).then(|| x.log10());
+        let started = || run.points.iter().filter(|p| p.trials > 0);
+        let success: Vec<[f64; 2]> = started()
+            .filter_map(|p| Some([log(p.channel_ber())?, p.success_rate()]))
+            .collect();
+        let residual: Vec<[f64; 2]> = started()
+            .filter_map(|p| Some([log(p.channel_ber())?, log(p.residual_ber())?]))
+            .collect();
+        let uncoded: Vec<[f64; 2]> = success.iter().map(|&[x, _]| [x, x]).collect();
+
+        ui.label("Messages decoded whole");
+        Plot::new("simulate_success")
+            .height(160.0)
+            .include_y(0.0)
+            .include_y(1.0)
+            .show(ui, |plot| {
+                plot.line(Line::new(PlotPoints::new(success.clone())));
+                plot.points(Points::new(PlotPoints::new(success)).radius(3.0));
+            });
+        ui.small("x: log10 channel BER, y: share of messages decoded whole");
+        ui.label("Residual bit error rate");
+        Plot::new("simulate_residual")
+            .height(160.0)
+            .legend(Legend::default())
+            .show(ui, |plot| {
+                plot.line(Line::new(PlotPoints::new(residual.clone())).name(info.name));
+                plot.points(Points::new(PlotPoints::new(residual)).radius(3.0));
+                plot.line(Line::new(PlotPoints::new(uncoded)).name("Uncoded"));
+            });
+        ui.small(
+            "x: log10 channel BER, y: log10 residual BER. Points with no residual errors are left \
+             out.",
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use crate::{codes::CodeInfo, decoders, manifest, memory, platform};
+use egui::{
+    plot::{Line, Plot, PlotPoints},
+    Color32, DragValue, ProgressBar,
+};
+use hamming::{decode, encode, Code};
+use instant::Instant;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::time::Duration;
+
+/// How long each frame may spend on soak rounds, so the window stays responsive.
+const FRAME_BUDGET: Duration = Duration::from_millis(10);
+
+/// The size of each round's random message, in bytes.
+const MESSAGE_LEN: usize = 4096;
+
+/// How often memory and processor time are sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The lowest bit error rate the gauges show; anything lower reads as empty.
+const MIN_GAUGE_BER: f64 = 1e-9;
+
+/// The most samples kept: a day's worth. Older ones are thinned rather than dropped, so the plot
+/// still starts at the beginning of the run.
+const MAX_SAMPLES: usize = 8640;
+
+/// One reading of how the run is going.
+struct Sample {
+    /// Seconds since the run started.
+    at: f64,
+    rounds: u64,
+    memory: Option<u64>,
+    /// The share of one core used since the previous sample.
+    cpu: Option<f64>,
+}
+
+struct Run {
+    rng: StdRng,
+    /// Each round's message and received stream, reused so the run itself doesn't churn the
+    /// allocator it is watching.
+    message: Vec<u8>,
+    received: Vec<u8>,
+    started: Instant,
+    last_sample: Instant,
+    last_cpu: Option<Duration>,
+    rounds: u64,
+    bytes: u64,
+    /// Rounds the decoder rightly gave up on, having been hit by too many flips.
+    uncorrectable: u64,
+    /// Rounds whose decode failed without cause or didn't give back the message.
+    failures: u64,
+    first_failure: Option<String>,
+    /// Bits sent through the channel, and how many of them it flipped.
+    channel_bits: u64,
+    channel_flips: u64,
+    /// Message bits that came out of the decoder wrong. A round that doesn't decode as a whole
+    /// is decoded codeword by codeword, the codewords that can't be corrected giving zeros.
+    residual_errors: u64,
+}
+
+impl Run {
+    fn new() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            message: vec![0; MESSAGE_LEN],
+            received: Vec::new(),
+            started: Instant::now(),
+            last_sample: Instant::now(),
+            last_cpu: platform::cpu_time(),
+            rounds: 0,
+            bytes: 0,
+            uncorrectable: 0,
+            failures: 0,
+            first_failure: None,
+            channel_bits: 0,
+            channel_flips: 0,
+            residual_errors: 0,
+        }
+    }
+
+    /// Encodes a random message, damages it and decodes it again.
+    fn round(&mut self, code: &Code, info: &CodeInfo, probability: f64) {
+        self.rng.fill(&mut self.message[..]);
+        let seed = manifest::new_seed(&mut self.rng);
+        let received = &mut self.received;
+        let (channel_bits, channel_flips) = (&mut self.channel_bits, &mut self.channel_flips);
+        let result = encode(&self.message, code)
+            .map_err(|e| format!("encode: {e:?}"))
+            .and_then(|encoded| {
+                let error = manifest::error_pattern(seed, encoded.len(), probability);
+                *channel_bits += encoded.len() as u64 * 8;
+                *channel_flips += error.iter().map(|b| u64::from(b.count_ones())).sum::<u64>();
+                received.clear();
+                received.extend(encoded.iter().zip(&error).map(|(a, b)| a ^ b));
+                decode(received, code).map_err(|e| format!("decode: {e:?}"))
+            });
+        self.rounds += 1;
+        self.bytes += MESSAGE_LEN as u64;
+        let decoded = match &result {
+            Ok(decoded) => Some(decoded.clone()),
+            Err(_) => decoders::decode_blocks(code, info, &self.received).map(|(d, _)| d),
+        };
+        self.residual_errors += match decoded {
+            Some(decoded) => Iterator::zip(self.message.iter(), decoded.iter())
+                .map(|(a, b)| u64::from((a ^ b).count_ones()))
+                .sum(),
+            // Nothing came back, so every bit is as good as a guess.
+            None => MESSAGE_LEN as u64 * 4,
+        };
+        let failure = match result {
+            Ok(decoded) if decoded == self.message => None,
+            Ok(_) => Some(format!(
+                "round {}: decoded to the wrong message",
+                self.rounds
+            )),
+            // Expected once flips are dense enough to land two in one codeword.
+            Err(_) if probability > 0.0 => {
+                self.uncorrectable += 1;
+                None
+            }
+            Err(e) => Some(format!("round {}: {e}", self.rounds)),
+        };
+        if let Some(failure) = failure {
+            self.failures += 1;
+            self.first_failure.get_or_insert(failure);
+        }
+    }
+
+    fn sample(&mut self) -> Sample {
+        let now = Instant::now();
+        let cpu_time = platform::cpu_time();
+        let cpu = match (self.last_cpu, cpu_time) {
+            (Some(before), Some(after)) => {
+                let wall = now.duration_since(self.last_sample).as_secs_f64();
+                Some((after.saturating_sub(before)).as_secs_f64() / wall.max(f64::EPSILON))
+            }
+            _ => None,
+        };
+        self.last_sample = now;
+        self.last_cpu = cpu_time;
+        Sample {
+            at: now.duration_since(self.started).as_secs_f64(),
+            rounds: self.rounds,
+            memory: platform::resident_memory(),
+            cpu,
+        }
+    }
+}
+
+/// Runs the encode, damage and decode pipeline for as long as it is left going, sampling memory
+/// and processor use, to catch leaks and slowdowns that only show up over hours.
+pub struct Soak {
+    pub open: bool,
+    probability: f64,
+    run: Option<Run>,
+    samples: Vec<Sample>,
+}
+
+impl Soak {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            probability: 0.0,
+            run: None,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Runs a frame's worth of rounds while a run is going, whether or not the window is open,
+    /// and shows the window.
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code, info: &CodeInfo) {
+        if let Some(run) = &mut self.run {
+            let start = Instant::now();
+            while start.elapsed() < FRAME_BUDGET {
+                run.round(code, info, self.probability);
+            }
+            if run.last_sample.elapsed() >= SAMPLE_INTERVAL {
+                self.samples.push(run.sample());
+                if self.samples.len() > MAX_SAMPLES {
+                    // Keep every other sample, always including the first.
+                    let mut i = 0;
+                    self.samples.retain(|_| {
+                        i += 1;
+                        i % 2 == 1
+                    });
+                }
+            }
+            ctx.request_repaint();
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Soak Test")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Error probability:");
+            ui.add_enabled(
+                self.run.is_none(),
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=0.01)
+                    .speed(0.0001)
+                    .max_decimals(4),
+            );
+            if self.run.is_some() {
+                if ui.button("Stop").clicked() {
+                    self.run = None;
+                }
+            } else if ui.button("Start").clicked() {
+                self.samples.clear();
+                let mut run = Run::new();
+                self.samples.push(run.sample());
+                self.run = Some(run);
+            }
+        });
+        ui.small(format!(
+            "Each round encodes a random {MESSAGE_LEN}-byte message, flips bits with the chance \
+             above, and decodes it. Memory and processor use are sampled every {} seconds.",
+            SAMPLE_INTERVAL.as_secs()
+        ));
+        ui.separator();
+
+        if let Some(run) = &self.run {
+            let elapsed = run.started.elapsed().as_secs_f64();
+            egui::Grid::new("soak_stats").show(ui, |ui| {
+                ui.label("Running for");
+                ui.label(format!("{:.0} s", elapsed));
+                ui.end_row();
+                ui.label("Rounds");
+                ui.label(format!(
+                    "{} ({:.1} MB/s)",
+                    run.rounds,
+                    run.bytes as f64 / elapsed.max(f64::EPSILON) / 1e6
+                ));
+                ui.end_row();
+                ui.label("Uncorrectable");
+                ui.label(run.uncorrectable.to_string());
+                ui.end_row();
+                ui.label("Failures");
+                if run.failures == 0 {
+                    ui.label("0");
+                } else {
+                    ui.colored_label(Color32::RED, run.failures.to_string());
+                }
+                ui.end_row();
+                if let Some(last) = self.samples.last() {
+                    ui.label("Memory");
+                    ui.label(last.memory.map_or("unknown".to_string(), |m| {
+                        format!("{:.1} MiB", m as f64 / 1048576.0)
+                    }));
+                    ui.end_row();
+                    ui.label("Processor");
+                    ui.label(last.cpu.map_or("unknown".to_string(), |c| {
+                        format!("{:.0}% of a core", c * 100.0)
+                    }));
+                    ui.end_row();
+                }
+            });
+            if let Some(failure) = &run.first_failure {
+                ui.colored_label(Color32::RED, format!("First failure: {failure}"));
+            }
+            ui.separator();
+            ber_gauges(ui, run);
+        }
+
+        if let Some(stats) = memory::stats() {
+            egui::Grid::new("soak_allocator").show(ui, |ui| {
+                ui.label("Allocated");
+                ui.label(format!(
+                    "{:.1} MiB (peak {:.1} MiB)",
+                    stats.current as f64 / 1048576.0,
+                    stats.peak as f64 / 1048576.0
+                ));
+                ui.end_row();
+                ui.label("Allocations");
+                ui.label(stats.allocations.to_string());
+                ui.end_row();
+            });
+        }
+
+        let memory: Vec<[f64; 2]> = self
+            .samples
+            .iter()
+            .filter_map(|s| Some([s.at, s.memory? as f64 / 1048576.0]))
+            .collect();
+        if memory.len() >= 2 {
+            let (first, last) = (&self.samples[0], &self.samples[self.samples.len() - 1]);
+            if let (Some(a), Some(b)) = (first.memory, last.memory) {
+                let rounds = (last.rounds - first.rounds).max(1);
+                ui.label(format!(
+                    "Memory has grown {:.1} MiB since the start, {:.2} bytes a round.",
+                    (b as f64 - a as f64) / 1048576.0,
+                    (b as f64 - a as f64) / rounds as f64
+                ));
+            }
+            Plot::new("soak_memory")
+                .height(150.0)
+                .include_y(0.0)
+                .show(ui, |plot| {
+                    plot.line(Line::new(PlotPoints::new(memory)).name("MiB"))
+                });
+        } else if self.run.is_some() {
+            ui.label("Waiting for samples...");
+        }
+    }
+}
+
+/// A bit error rate as a gauge on a log scale, from `MIN_GAUGE_BER` up to one.
+fn ber_gauge(ui: &mut egui::Ui, label: &str, errors: u64, bits: u64) {
+    let ber = errors as f64 / bits.max(1) as f64;
+    let fill = (ber.max(MIN_GAUGE_BER).log10() / MIN_GAUGE_BER.log10()).clamp(0.0, 1.0);
+    ui.label(label);
+    ui.add(
+        ProgressBar::new(1.0 - fill as f32)
+            .desired_width(200.0)
+            .text(format!("{ber:.2e}")),
+    );
+    ui.end_row();
+}
+
+/// The bit error rate of the channel against what is left after decoding, and the ratio between
+/// them, which is how much the code buys.
+fn ber_gauges(ui: &mut egui::Ui, run: &Run) {
+    let data_bits = run.rounds * MESSAGE_LEN as u64 * 8;
+    egui::Grid::new("soak_ber").show(ui, |ui| {
+        ber_gauge(ui, "Raw BER", run.channel_flips, run.channel_bits);
+        ber_gauge(ui, "Residual BER", run.residual_errors, data_bits);
+        ui.label("Improvement");
+        if run.channel_flips == 0 {
+            ui.label("no flips yet");
+        } else if run.residual_errors == 0 {
+            ui.label("no residual errors yet");
+        } else {
+            let raw = run.channel_flips as f64 / run.channel_bits as f64;
+            let residual = run.residual_errors as f64 / data_bits as f64;
+            ui.strong(format!("{:.1}x", raw / residual));
+        }
+        ui.end_row();
+    });
+}
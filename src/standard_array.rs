@@ -0,0 +1,211 @@
+use crate::{
+    analysis::{self, StandardArray},
+    codes::{self, CodeInfo},
+    export,
+};
+use egui::{Color32, ComboBox, Grid, RichText, TextEdit};
+
+/// Codes small enough for their standard array to be shown.
+const CODES: [CodeInfo; 2] = [codes::H7_4, codes::EH8_4];
+
+fn format_mask(mask: u32, n: usize) -> String {
+    analysis::from_mask(mask, n)
+        .into_iter()
+        .map(|b| if b { '1' } else { '0' })
+        .collect()
+}
+
+/// How a coset's syndrome is written: with the overall parity appended for extended codes.
+fn format_syndrome(info: &CodeInfo, leader: u32) -> String {
+    let check = analysis::check(info, &analysis::from_mask(leader, info.n));
+    match check.overall_even {
+        Some(even) => format!("{} / {}", check.syndrome, u8::from(!even)),
+        None => check.syndrome.to_string(),
+    }
+}
+
+fn to_csv(info: &CodeInfo, array: &StandardArray) -> String {
+    let mut s = String::from("syndrome");
+    for &c in &array.codewords {
+        s.push(',');
+        s.push_str(&format_mask(c, info.n));
+    }
+    s.push('\n');
+    for (row, &leader) in array.leaders.iter().enumerate() {
+        s.push_str(&format_syndrome(info, leader));
+        for col in 0..array.codewords.len() {
+            s.push(',');
+            s.push_str(&format_mask(array.cell(row, col), info.n));
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn to_latex(info: &CodeInfo, array: &StandardArray) -> String {
+    let cols = array.codewords.len();
+    let mut s = format!(
+        "% Standard array of the {} code; the first column holds the coset leaders.\n",
+        info.name
+    );
+    s.push_str(&format!("\\begin{{tabular}}{{c|{}}}\n", "c".repeat(cols)));
+    s.push_str("Syndrome");
+    for &c in &array.codewords {
+        s.push_str(&format!(" & \\texttt{{{}}}", format_mask(c, info.n)));
+    }
+    s.push_str(" \\\\\n\\hline\n");
+    for (row, &leader) in array.leaders.iter().enumerate() {
+        s.push_str(&format_syndrome(info, leader));
+        for col in 0..cols {
+            s.push_str(&format!(
+                " & \\texttt{{{}}}",
+                format_mask(array.cell(row, col), info.n)
+            ));
+        }
+        s.push_str(" \\\\\n");
+    }
+    s.push_str("\\end{tabular}\n");
+    s
+}
+
+/// The standard array of a small code, with a received word located in it.
+pub struct StandardArrayView {
+    pub open: bool,
+    code: CodeInfo,
+    array: Option<StandardArray>,
+    word: String,
+    export_status: Option<Result<String, String>>,
+}
+
+impl StandardArrayView {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            code: CODES[0],
+            array: None,
+            word: String::new(),
+            export_status: None,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Standard Array")
+            .open(&mut open)
+            .default_width(600.0)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ComboBox::from_label("Code")
+                .selected_text(self.code.name)
+                .show_ui(ui, |ui| {
+                    for code in CODES {
+                        if ui
+                            .selectable_value(&mut self.code, code, code.name)
+                            .changed()
+                        {
+                            self.array = None;
+                        }
+                    }
+                });
+            ui.label("Received:");
+            ui.add(
+                TextEdit::singleline(&mut self.word)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(format!("{} bits", self.code.n)),
+            );
+        });
+
+        let info = self.code;
+        let array = self
+            .array
+            .get_or_insert_with(|| analysis::standard_array(&info).unwrap());
+
+        let word: Vec<bool> = self.word.trim().chars().map(|c| c == '1').collect();
+        let located = (word.len() == info.n
+            && self.word.trim().chars().all(|c| c == '0' || c == '1'))
+        .then(|| array.locate(analysis::to_mask(&word)))
+        .flatten();
+        match located {
+            Some((row, col)) => {
+                ui.label(format!(
+                    "Row {row}: the decoder assumes error pattern {} and decodes to codeword {}.",
+                    format_mask(array.leaders[row], info.n),
+                    format_mask(array.codewords[col], info.n),
+                ));
+            }
+            None => {
+                ui.label("Enter a received word to locate it in the array.");
+            }
+        }
+        ui.label("Rows are cosets, headed by their leader; columns are codewords.");
+
+        ui.horizontal(|ui| {
+            let name = format!("standard_array_{}", info.name);
+            let saved = if ui.button("Export CSV").clicked() {
+                Some(export::save_text(
+                    &format!("{name}.csv"),
+                    "CSV",
+                    "csv",
+                    &to_csv(&info, array),
+                ))
+            } else if ui.button("Export LaTeX").clicked() {
+                Some(export::save_text(
+                    &format!("{name}.tex"),
+                    "LaTeX",
+                    "tex",
+                    &to_latex(&info, array),
+                ))
+            } else {
+                None
+            };
+            match saved {
+                Some(Ok(Some(path))) => {
+                    self.export_status = Some(Ok(format!("Saved {}", path.display())))
+                }
+                Some(Ok(None)) => {}
+                Some(Err(e)) => self.export_status = Some(Err(e)),
+                None => {}
+            }
+            match &self.export_status {
+                Some(Ok(msg)) => {
+                    ui.label(msg.as_str());
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, e.as_str());
+                }
+                None => {}
+            }
+        });
+        ui.separator();
+
+        let highlight = Color32::from_rgb(230, 160, 40);
+        egui::ScrollArea::both().show(ui, |ui| {
+            Grid::new("standard_array").striped(true).show(ui, |ui| {
+                ui.strong("Syndrome");
+                for &c in &array.codewords {
+                    ui.strong(RichText::new(format_mask(c, info.n)).monospace());
+                }
+                ui.end_row();
+                for (row, &leader) in array.leaders.iter().enumerate() {
+                    ui.label(format_syndrome(&info, leader));
+                    for col in 0..array.codewords.len() {
+                        let mut text =
+                            RichText::new(format_mask(array.cell(row, col), info.n)).monospace();
+                        if col == 0 {
+                            text = text.strong();
+                        }
+                        if located == Some((row, col)) {
+                            text = text.background_color(highlight).color(Color32::BLACK);
+                        }
+                        ui.label(text);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{env, fs, path::PathBuf};
+
+/// The per-user directory where persistent app data is kept.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn data_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(base.join("hamming-gui"))
+}
+
+/// The browser's local storage, which stands in for the data directory on the web.
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// The key `name` is kept under in local storage.
+#[cfg(target_arch = "wasm32")]
+fn key(name: &str) -> String {
+    format!("hamming-gui/{name}")
+}
+
+/// Loads `name` from the data directory, or `None` if it is missing or unreadable.
+pub fn load<T: DeserializeOwned>(name: &str) -> Option<T> {
+    #[cfg(target_arch = "wasm32")]
+    let s = local_storage()?.get_item(&key(name)).ok()??;
+    #[cfg(not(target_arch = "wasm32"))]
+    let s = fs::read_to_string(data_dir()?.join(name)).ok()?;
+    serde_json::from_str(&s).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result<(), String> {
+    let dir = data_dir().ok_or_else(|| "No data directory available".to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let s = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(dir.join(name), s).map_err(|e| e.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result<(), String> {
+    let storage = local_storage().ok_or_else(|| "No local storage available".to_string())?;
+    let s = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    storage
+        .set_item(&key(name), &s)
+        .map_err(|e| format!("{e:?}"))
+}
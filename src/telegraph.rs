@@ -0,0 +1,164 @@
+/// The ITA2 (Baudot-Murray) letters and figures cases, indexed by code. Zero marks codes with
+/// no character here: blank, the two shifts, and in figures the who-are-you and unassigned codes.
+const LETTERS: &[u8; 32] = b"\0E\nA SIU\rDRJNFCKTZLWHYPQOBG\0MXV\0";
+const FIGURES: &[u8; 32] = b"\x003\n- '87\r\x004\x07,\x00:(5+)2\x006019?\x00\x00./=\x00";
+
+/// Shifts to the figures case.
+const FIGS: u8 = 27;
+/// Shifts to the letters case.
+const LTRS: u8 = 31;
+
+/// `codes` of `width` bits each, most significant bit first, run together. The last byte is
+/// filled out with zeros.
+fn pack(codes: &[u8], width: usize) -> Vec<u8> {
+    let mut bytes = vec![0; (codes.len() * width).div_ceil(8)];
+    for (j, &code) in codes.iter().enumerate() {
+        for i in 0..width {
+            if code >> (width - 1 - i) & 1 == 1 {
+                let bit = j * width + i;
+                bytes[bit / 8] |= 0x80 >> (bit % 8);
+            }
+        }
+    }
+    bytes
+}
+
+/// The `width`-bit codes run together in `bytes`, leaving off any bits too few for a code.
+fn unpack(bytes: &[u8], width: usize) -> Vec<u8> {
+    (0..bytes.len() * 8 / width)
+        .map(|j| {
+            (0..width).fold(0, |code, i| {
+                let bit = j * width + i;
+                code << 1 | (bytes[bit / 8] >> (7 - bit % 8) & 1)
+            })
+        })
+        .collect()
+}
+
+/// `text` in 7-bit ASCII, eight characters to every seven bytes.
+pub fn encode_ascii7(text: &str) -> Result<Vec<u8>, String> {
+    let codes = text
+        .chars()
+        .map(|c| {
+            u8::try_from(c)
+                .ok()
+                .filter(u8::is_ascii)
+                .ok_or_else(|| format!("{c:?} isn't a 7-bit ASCII character."))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    Ok(pack(&codes, 7))
+}
+
+/// The text of `encode_ascii7`. Seven bits of padding can't be told from a NUL character, so a
+/// NUL that ends exactly at the last bit is taken as padding.
+pub fn decode_ascii7(bytes: &[u8]) -> String {
+    let mut codes = unpack(bytes, 7);
+    if codes.len() * 7 == bytes.len() * 8 && codes.last() == Some(&0) {
+        codes.pop();
+    }
+    codes.into_iter().map(char::from).collect()
+}
+
+/// `text` in the five-bit ITA2 Baudot code, with shifts between letters and figures as needed.
+/// Baudot has no lowercase, so letters are sent as capitals. The receiver is taken to start in
+/// letters.
+pub fn encode_baudot(text: &str) -> Result<Vec<u8>, String> {
+    let find = |table: &[u8; 32], c: u8| table.iter().position(|&t| t != 0 && t == c);
+    let mut codes = Vec::with_capacity(text.len());
+    let mut figures = false;
+    for c in text.chars() {
+        let byte = u8::try_from(c.to_ascii_uppercase())
+            .ok()
+            .filter(|&b| b != 0)
+            .ok_or_else(|| format!("{c:?} has no Baudot code."))?;
+        let (here, there) = if figures {
+            (FIGURES, LETTERS)
+        } else {
+            (LETTERS, FIGURES)
+        };
+        if let Some(code) = find(here, byte) {
+            codes.push(code as u8);
+        } else if let Some(code) = find(there, byte) {
+            figures = !figures;
+            codes.push(if figures { FIGS } else { LTRS });
+            codes.push(code as u8);
+        } else {
+            return Err(format!("{c:?} has no Baudot code."));
+        }
+    }
+    Ok(pack(&codes, 5))
+}
+
+/// The text of `encode_baudot`. Blanks are skipped, and codes with no character in the case
+/// they arrive in come out as the replacement character. A flipped shift garbles everything up
+/// to the next one, as it did on the wire.
+pub fn decode_baudot(bytes: &[u8]) -> String {
+    let mut figures = false;
+    let mut text = String::new();
+    for code in unpack(bytes, 5) {
+        match code {
+            0 => {}
+            FIGS => figures = true,
+            LTRS => figures = false,
+            _ => {
+                let table = if figures { FIGURES } else { LETTERS };
+                text.push(match table[usize::from(code)] {
+                    0 => char::REPLACEMENT_CHARACTER,
+                    b => char::from(b),
+                });
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii7_round_trip() {
+        // Eight characters fill seven bytes exactly, so lengths either side of that are tried.
+        for len in 0..=17 {
+            let text: String = "Hello, world! ~\t\n".chars().take(len).collect();
+            let bytes = encode_ascii7(&text).unwrap();
+            assert_eq!(bytes.len(), (len * 7).div_ceil(8));
+            assert_eq!(decode_ascii7(&bytes), text);
+        }
+    }
+
+    #[test]
+    fn ascii7_rejects_wider_characters() {
+        assert!(encode_ascii7("café").is_err());
+        assert!(encode_ascii7("\u{80}").is_err());
+    }
+
+    #[test]
+    fn baudot_round_trip() {
+        let text = "RYRY 73 DE W1AW, 12:30.";
+        assert_eq!(decode_baudot(&encode_baudot(text).unwrap()), text);
+        assert_eq!(decode_baudot(&encode_baudot("qst").unwrap()), "QST");
+    }
+
+    #[test]
+    fn baudot_shifts_only_when_needed() {
+        // Letters, a shift to figures, two figures, a shift back, and a letter.
+        assert_eq!(
+            encode_baudot("A12B").unwrap().len(),
+            (6 * 5_usize).div_ceil(8)
+        );
+    }
+
+    #[test]
+    fn baudot_rejects_unknown_characters() {
+        assert!(encode_baudot("50%").is_err());
+        assert!(encode_baudot("@").is_err());
+        assert!(encode_baudot("\0").is_err());
+    }
+
+    #[test]
+    fn baudot_unassigned_code() {
+        // Code 9 is unassigned in figures.
+        assert_eq!(decode_baudot(&pack(&[FIGS, 9], 5)), "\u{fffd}");
+    }
+}
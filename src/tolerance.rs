@@ -0,0 +1,157 @@
+use crate::{analysis, codes::CodeInfo, manifest};
+use egui::{Color32, Grid};
+use hamming::{decode, Code};
+use instant::Instant;
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::Duration;
+
+/// The chance of the whole message decoding that the estimate is for.
+const CONFIDENCE: f64 = 0.95;
+
+/// How many times the message is sent through the channel to check the estimate.
+const TRIALS: usize = 1000;
+
+/// How long each frame may spend on trials, so the window stays responsive.
+const FRAME_BUDGET: Duration = Duration::from_millis(10);
+
+/// The chance that a codeword of `info` decodes right when each bit flips with chance `p`: that
+/// is, that at most one of its bits flips. Two or more flips are either detected or corrected to
+/// the wrong codeword, which always has different data.
+pub fn codeword_success(info: &CodeInfo, p: f64) -> f64 {
+    let n = info.n as i32;
+    (1.0 - p).powi(n) + f64::from(n) * p * (1.0 - p).powi(n - 1)
+}
+
+/// The largest `p` for which `codewords` codewords all decode right with chance `CONFIDENCE`,
+/// found by bisection: the chance only falls as `p` rises.
+fn tolerable_probability(info: &CodeInfo, codewords: usize) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..60 {
+        let mid = (low + high) / 2.0;
+        if codeword_success(info, mid).powf(codewords as f64) >= CONFIDENCE {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// The message sent through the channel again and again at the estimated probability.
+struct Verification {
+    probability: f64,
+    rng: StdRng,
+    trials: usize,
+    successes: usize,
+}
+
+/// Estimates the noisiest channel the message survives whole, for the current code and message
+/// length, and checks the estimate by simulation.
+pub struct Tolerance {
+    pub open: bool,
+    verification: Option<Verification>,
+}
+
+impl Tolerance {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            verification: None,
+        }
+    }
+
+    /// Runs a frame's worth of trials while a check is going, and shows the window.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        code: &Code,
+        info: &CodeInfo,
+        message: &[u8],
+        encoded: &[u8],
+    ) {
+        let codewords = analysis::codeword_count(info, encoded);
+        let probability = tolerable_probability(info, codewords);
+        // A check of a different estimate, for another code or message, no longer applies.
+        if matches!(&self.verification, Some(v) if v.probability != probability) {
+            self.verification = None;
+        }
+        if let Some(v) = self.verification.as_mut().filter(|v| v.trials < TRIALS) {
+            let start = Instant::now();
+            while v.trials < TRIALS && start.elapsed() < FRAME_BUDGET {
+                let seed = manifest::new_seed(&mut v.rng);
+                let error = manifest::error_pattern(seed, encoded.len(), v.probability);
+                let received: Vec<u8> = Iterator::zip(encoded.iter(), error.iter())
+                    .map(|(b, e)| b ^ e)
+                    .collect();
+                if decode(&received, code).ok().as_deref() == Some(message) {
+                    v.successes += 1;
+                }
+                v.trials += 1;
+            }
+            ctx.request_repaint();
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Tolerable Error Probability")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, info, codewords, probability));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, info: &CodeInfo, codewords: usize, probability: f64) {
+        if codewords == 0 {
+            ui.label("Type a message to estimate how much noise it can take.");
+            return;
+        }
+        ui.small(format!(
+            "A codeword decodes right when at most one of its {} bits flips. The whole message \
+             needs all {codewords} of its codewords to, and this is the largest error \
+             probability at which they all do {:.0}% of the time.",
+            info.n,
+            CONFIDENCE * 100.0
+        ));
+        Grid::new("tolerance").show(ui, |ui| {
+            ui.label("Code");
+            ui.label(info.name);
+            ui.end_row();
+            ui.label("Codewords");
+            ui.label(codewords.to_string());
+            ui.end_row();
+            ui.label("Tolerable probability");
+            ui.strong(format!("{probability:.3e}"));
+            ui.end_row();
+        });
+        ui.separator();
+
+        let running = matches!(&self.verification, Some(v) if v.trials < TRIALS);
+        if ui
+            .add_enabled(
+                !running,
+                egui::Button::new(format!("Simulate {TRIALS} messages")),
+            )
+            .clicked()
+        {
+            self.verification = Some(Verification {
+                probability,
+                rng: StdRng::from_entropy(),
+                trials: 0,
+                successes: 0,
+            });
+        }
+        if let Some(v) = &self.verification {
+            let share = v.successes as f64 / v.trials.max(1) as f64;
+            let text = format!(
+                "{} of {} decoded whole ({:.1}%)",
+                v.successes,
+                v.trials,
+                share * 100.0
+            );
+            // Only a finished run is held to the estimate, with some room for chance.
+            if v.trials == TRIALS && share < CONFIDENCE - 0.02 {
+                ui.colored_label(Color32::RED, text);
+            } else {
+                ui.label(text);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    analysis, channel,
+    codes::{self, CodeInfo},
+    manifest,
+};
+use egui::DragValue;
+use hamming::Code;
+use rand::thread_rng;
+
+/// `bits` packed into bytes, least significant bit first, with the last byte padded with zeros.
+fn pack(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = vec![0; bits.len().div_ceil(8)];
+    for (i, &bit) in bits.iter().enumerate() {
+        analysis::set_bit(&mut bytes, i, bit);
+    }
+    bytes
+}
+
+/// Bytes after going through the channel with unequal protection.
+pub struct Arrival {
+    pub bytes: Vec<u8>,
+    /// Bits flipped by the channel, in both parts.
+    pub flips: usize,
+}
+
+/// Unequal error protection: the most significant bits of each sample go through a strong code,
+/// and the rest through a weaker one or none at all. An error in a low bit barely shows, so
+/// protecting only the high bits keeps most of the benefit of the code for less of its overhead.
+pub struct UnequalProtection {
+    /// Whether to send with unequal protection as well.
+    pub enabled: bool,
+    /// The most significant bits of each sample sent through `strong`.
+    pub strong_bits: usize,
+    pub strong: &'static (CodeInfo, Code),
+    /// The code the other bits are sent through, or `None` to send them bare.
+    pub weak: Option<&'static (CodeInfo, Code)>,
+}
+
+impl UnequalProtection {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            strong_bits: 4,
+            strong: &codes::PIPELINE[0],
+            weak: None,
+        }
+    }
+
+    /// Whether bit `bit` of a stream of `sample_bits`-bit samples, stored least significant byte
+    /// first, goes through the strong code.
+    fn is_strong(&self, sample_bits: usize, bit: usize) -> bool {
+        bit % sample_bits >= sample_bits - self.strong_bits.min(sample_bits)
+    }
+
+    /// Bits sent for each bit of data, for samples of `sample_bits` bits.
+    pub fn overhead(&self, sample_bits: usize) -> f64 {
+        let strong = self.strong_bits.min(sample_bits) as f64;
+        let weak = sample_bits as f64 - strong;
+        let weak_rate = self.weak.map_or(1.0, |(info, _)| info.rate());
+        (strong / self.strong.0.rate() + weak / weak_rate) / sample_bits as f64
+    }
+
+    /// Sends `bytes`, made of `sample_bits`-bit samples, through a channel flipping each bit with
+    /// chance `probability`: the high bits of every sample gathered into one stream and encoded
+    /// with the strong code, and the low bits into another for the weak code.
+    pub fn send(&self, bytes: &[u8], sample_bits: usize, probability: f64) -> Arrival {
+        let bits = bytes.len() * 8;
+        let (strong, weak): (Vec<usize>, Vec<usize>) =
+            (0..bits).partition(|&b| self.is_strong(sample_bits, b));
+        let gather = |part: &[usize]| {
+            pack(
+                &part
+                    .iter()
+                    .map(|&b| analysis::get_bit(bytes, b))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        // Each part gets its own errors, rather than the same pattern twice.
+        let seed = manifest::new_seed(&mut thread_rng());
+        let (strong_in, strong_flips) =
+            channel::send(Some(self.strong), &gather(&strong), seed, probability);
+        let (weak_in, weak_flips) =
+            channel::send(self.weak, &gather(&weak), seed.wrapping_add(1), probability);
+        let mut arrived = vec![0; bytes.len()];
+        for (part, received) in [(&strong, &strong_in), (&weak, &weak_in)] {
+            for (i, &b) in part.iter().enumerate() {
+                analysis::set_bit(&mut arrived, b, analysis::get_bit(received, i));
+            }
+        }
+        Arrival {
+            bytes: arrived,
+            flips: strong_flips + weak_flips,
+        }
+    }
+
+    /// A short name for the scheme, such as "Top 4 in H7_4, rest bare".
+    pub fn name(&self) -> String {
+        let rest = self.weak.map_or("bare", |(info, _)| info.name);
+        format!(
+            "Top {} in {}, rest {rest}",
+            self.strong_bits, self.strong.0.name
+        )
+    }
+
+    /// The settings on one line, with a checkbox to send with them as well. `sample` names what
+    /// a sample is, such as "byte".
+    pub fn ui(&mut self, ui: &mut egui::Ui, sample: &str, sample_bits: usize) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.enabled, "Also send the top");
+            ui.add_enabled_ui(self.enabled, |ui| {
+                ui.add(DragValue::new(&mut self.strong_bits).clamp_range(1..=sample_bits));
+                ui.label(format!("bits of each {sample} through"));
+                egui::ComboBox::from_id_source(("unequal_strong", sample))
+                    .selected_text(self.strong.0.name)
+                    .show_ui(ui, |ui| {
+                        for pipeline in &codes::PIPELINE {
+                            let current = self.strong.0 == pipeline.0;
+                            if ui.selectable_label(current, pipeline.0.name).clicked() {
+                                self.strong = pipeline;
+                            }
+                        }
+                    });
+                ui.label("and the rest through");
+                egui::ComboBox::from_id_source(("unequal_weak", sample))
+                    .selected_text(self.weak.map_or("nothing", |(info, _)| info.name))
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_label(self.weak.is_none(), "nothing")
+                            .clicked()
+                        {
+                            self.weak = None;
+                        }
+                        for pipeline in &codes::PIPELINE {
+                            let current = self.weak.map(|(info, _)| *info) == Some(pipeline.0);
+                            if ui.selectable_label(current, pipeline.0.name).clicked() {
+                                self.weak = Some(pipeline);
+                            }
+                        }
+                    });
+            });
+        });
+        if self.enabled {
+            ui.small(format!(
+                "Unequal protection sends {:.2} bits for each bit of data.",
+                self.overhead(sample_bits)
+            ));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use egui::{Color32, TextEdit};
+use hamming::{encode, Code};
+use instant::Instant;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// How often the inbox is looked at.
+const SCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The most entries kept in the log.
+const MAX_LOG: usize = 500;
+
+/// A file's size and modification time, to tell whether it has changed.
+type Stamp = (u64, Option<SystemTime>);
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()))
+}
+
+struct Running {
+    inbox: PathBuf,
+    outbox: PathBuf,
+    started: Instant,
+    last_scan: Option<Instant>,
+    /// Files seen on the last scan, which are encoded once they stop changing.
+    seen: HashMap<PathBuf, Stamp>,
+    /// Files already encoded, as they were when encoded.
+    done: HashMap<PathBuf, Stamp>,
+}
+
+impl Running {
+    /// Looks for files that have finished arriving in the inbox, and encodes each into the
+    /// outbox. Returns what happened.
+    fn scan(&mut self, code: &Code) -> Vec<Result<String, String>> {
+        let mut events = Vec::new();
+        let entries = match fs::read_dir(&self.inbox) {
+            Ok(entries) => entries,
+            Err(e) => return vec![Err(format!("{}: {e}", self.inbox.display()))],
+        };
+        let mut seen = HashMap::new();
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let stamp = match stamp(&path) {
+                Some(stamp) if path.is_file() => stamp,
+                _ => continue,
+            };
+            // A file still being written changes between scans, so wait for it to settle.
+            let settled = self.seen.get(&path) == Some(&stamp);
+            if settled && self.done.get(&path) != Some(&stamp) {
+                events.push(self.encode(&path, code));
+                self.done.insert(path.clone(), stamp);
+            }
+            seen.insert(path, stamp);
+        }
+        self.done.retain(|path, _| seen.contains_key(path));
+        self.seen = seen;
+        events
+    }
+
+    fn encode(&self, path: &Path, code: &Code) -> Result<String, String> {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let data = fs::read(path).map_err(|e| format!("{name}: {e}"))?;
+        let encoded = encode(&data, code).map_err(|e| format!("{name}: {e:?}"))?;
+        let out = self.outbox.join(format!("{name}.bin"));
+        fs::write(&out, &encoded).map_err(|e| format!("{name}: {e}"))?;
+        Ok(format!(
+            "Encoded {name} ({} bytes) to {}",
+            data.len(),
+            out.display()
+        ))
+    }
+}
+
+/// Watches `inbox/` in a folder and encodes every file dropped there into `outbox/`, for
+/// fitting into a larger lab workflow.
+pub struct Watch {
+    pub open: bool,
+    dir: String,
+    running: Option<Running>,
+    /// Seconds since the watch started, and what happened.
+    log: Vec<(f32, Result<String, String>)>,
+}
+
+impl Watch {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            dir: String::new(),
+            running: None,
+            log: Vec::new(),
+        }
+    }
+
+    fn start(&mut self) {
+        let root = PathBuf::from(self.dir.trim());
+        let (inbox, outbox) = (root.join("inbox"), root.join("outbox"));
+        self.log.clear();
+        let created = fs::create_dir_all(&inbox).and_then(|()| fs::create_dir_all(&outbox));
+        if let Err(e) = created {
+            self.log
+                .push((0.0, Err(format!("{}: {e}", root.display()))));
+            return;
+        }
+        self.log
+            .push((0.0, Ok(format!("Watching {}", inbox.display()))));
+        self.running = Some(Running {
+            inbox,
+            outbox,
+            started: Instant::now(),
+            last_scan: None,
+            seen: HashMap::new(),
+            done: HashMap::new(),
+        });
+    }
+
+    /// Scans the inbox when it is due, whether or not the window is open, and shows the window.
+    pub fn show(&mut self, ctx: &egui::Context, code: &Code) {
+        if let Some(running) = &mut self.running {
+            if running
+                .last_scan
+                .is_none_or(|t| t.elapsed() >= SCAN_INTERVAL)
+            {
+                running.last_scan = Some(Instant::now());
+                let at = running.started.elapsed().as_secs_f32();
+                self.log
+                    .extend(running.scan(code).into_iter().map(|event| (at, event)));
+                let excess = self.log.len().saturating_sub(MAX_LOG);
+                self.log.drain(..excess);
+            }
+            ctx.request_repaint_after(SCAN_INTERVAL);
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Watch Folder")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add_enabled(
+                self.running.is_none(),
+                TextEdit::singleline(&mut self.dir).hint_text("Folder to watch"),
+            );
+            if self.running.is_some() {
+                if ui.button("Stop").clicked() {
+                    if let Some(running) = self.running.take() {
+                        let at = running.started.elapsed().as_secs_f32();
+                        self.log.push((at, Ok("Stopped".to_string())));
+                    }
+                }
+            } else if ui
+                .add_enabled(!self.dir.trim().is_empty(), egui::Button::new("Start"))
+                .clicked()
+            {
+                self.start();
+            }
+        });
+        ui.small("Files dropped into inbox/ are encoded into outbox/, with .bin appended.");
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (at, event) in &self.log {
+                    ui.horizontal(|ui| {
+                        ui.monospace(format!("{at:>7.1} s"));
+                        match event {
+                            Ok(msg) => ui.label(msg.as_str()),
+                            Err(e) => ui.colored_label(Color32::RED, e.as_str()),
+                        };
+                    });
+                }
+            });
+    }
+}
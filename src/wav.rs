@@ -0,0 +1,136 @@
+/// PCM audio as read from or written to a WAV file.
+pub struct Wav {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Interleaved samples, one per channel in turn.
+    pub samples: Vec<i16>,
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(i..i + 4)?.try_into().ok()?))
+}
+
+/// Reads an uncompressed WAV file of 8 or 16 bit samples. 8 bit samples are widened to 16.
+pub fn parse(bytes: &[u8]) -> Result<Wav, String> {
+    if bytes.get(0..4) != Some(b"RIFF") || bytes.get(8..12) != Some(b"WAVE") {
+        return Err("That isn't a WAV file.".to_string());
+    }
+    let truncated = || "The WAV file is cut short.".to_string();
+    let mut format = None;
+    let mut data = None;
+    let mut i = 12;
+    while i + 8 <= bytes.len() {
+        let size = u32_at(bytes, i + 4).ok_or_else(truncated)? as usize;
+        // The size comes from the file, and can run past the end of the address space on
+        // 32-bit targets.
+        let end = (i + 8).checked_add(size).ok_or_else(truncated)?;
+        let body = bytes.get(i + 8..end).ok_or_else(truncated)?;
+        match &bytes[i..i + 4] {
+            b"fmt " => {
+                let tag = u16_at(body, 0).ok_or_else(truncated)?;
+                let channels = u16_at(body, 2).ok_or_else(truncated)?;
+                let sample_rate = u32_at(body, 4).ok_or_else(truncated)?;
+                let bits = u16_at(body, 14).ok_or_else(truncated)?;
+                format = Some((tag, channels, sample_rate, bits));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        i = end + size % 2;
+    }
+    let (tag, channels, sample_rate, bits) =
+        format.ok_or_else(|| "The WAV file has no format chunk.".to_string())?;
+    let data = data.ok_or_else(|| "The WAV file has no data chunk.".to_string())?;
+    if tag != 1 {
+        return Err("Only uncompressed (PCM) WAV files can be read.".to_string());
+    }
+    if channels == 0 {
+        return Err("The WAV file has no channels.".to_string());
+    }
+    let samples = match bits {
+        8 => data.iter().map(|&b| (i16::from(b) - 128) << 8).collect(),
+        16 => data
+            .chunks_exact(2)
+            .map(|s| i16::from_le_bytes([s[0], s[1]]))
+            .collect(),
+        _ => {
+            return Err(format!(
+                "{bits} bit samples aren't supported, only 8 and 16."
+            ))
+        }
+    };
+    Ok(Wav {
+        sample_rate,
+        channels,
+        samples,
+    })
+}
+
+/// `wav` as a WAV file of 16 bit samples.
+pub fn write(wav: &Wav) -> Vec<u8> {
+    let data = to_bytes(&wav.samples);
+    let block_align = wav.channels * 2;
+    let mut bytes = Vec::with_capacity(44 + data.len());
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&wav.channels.to_le_bytes());
+    bytes.extend_from_slice(&wav.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(wav.sample_rate * u32::from(block_align)).to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&data);
+    bytes
+}
+
+/// The bits of `samples` as they are sent through the channel: each sample little-endian.
+pub fn to_bytes(samples: &[i16]) -> Vec<u8> {
+    samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+/// Samples back from the bytes of `to_bytes`. A trailing odd byte is dropped.
+pub fn from_bytes(bytes: &[u8]) -> Vec<i16> {
+    bytes
+        .chunks_exact(2)
+        .map(|s| i16::from_le_bytes([s[0], s[1]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let wav = Wav {
+            sample_rate: 8000,
+            channels: 2,
+            samples: vec![0, -1, i16::MAX, i16::MIN],
+        };
+        let read = parse(&write(&wav)).unwrap();
+        assert_eq!(read.sample_rate, 8000);
+        assert_eq!(read.channels, 2);
+        assert_eq!(read.samples, wav.samples);
+    }
+
+    #[test]
+    fn oversized_chunk() {
+        let mut bytes = write(&Wav {
+            sample_rate: 8000,
+            channels: 1,
+            samples: vec![0; 4],
+        });
+        // The size of the format chunk.
+        bytes[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse(&bytes).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{platform::APP_NAME, storage};
+use egui::Align2;
+
+/// Where it is remembered that the welcome screen has been seen.
+const FILE: &str = "welcome.json";
+
+/// A way into the app, offered on the welcome screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Start {
+    Demo,
+    DecodeFile,
+    Simulation,
+    Tutorial,
+}
+
+impl Start {
+    pub const ALL: [Start; 4] = [
+        Start::Demo,
+        Start::DecodeFile,
+        Start::Simulation,
+        Start::Tutorial,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Start::Demo => "Interactive demo",
+            Start::DecodeFile => "Decode a file",
+            Start::Simulation => "Run a simulation",
+            Start::Tutorial => "Tutorial",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Start::Demo => "Start with a sample message and a few flipped bits to explore.",
+            Start::DecodeFile => "Open a received stream and see which bits were corrected.",
+            Start::Simulation => {
+                "Push random messages through a noisy channel for as long as \
+                                  you like."
+            }
+            Start::Tutorial => "A step-by-step first look around.",
+        }
+    }
+}
+
+/// The screen shown on first run, so that new users have somewhere to begin rather than five
+/// empty panels.
+pub struct Welcome {
+    pub open: bool,
+}
+
+impl Welcome {
+    pub fn load() -> Self {
+        Self {
+            open: storage::load::<bool>(FILE) != Some(true),
+        }
+    }
+
+    /// Shows the screen if it is open. Returns the way in that was picked, if any.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Start> {
+        if !self.open {
+            return None;
+        }
+        let mut picked = None;
+        let mut dismissed = false;
+        egui::Window::new(format!("Welcome to {APP_NAME}"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("How would you like to start?");
+                ui.separator();
+                for start in Start::ALL {
+                    // The simulations are left out of some builds.
+                    if start == Start::Simulation && !cfg!(feature = "simulation") {
+                        continue;
+                    }
+                    if ui.button(start.name()).clicked() {
+                        picked = Some(start);
+                    }
+                    ui.small(start.description());
+                    ui.add_space(4.0);
+                }
+                ui.separator();
+                if ui.button("Skip").clicked() {
+                    dismissed = true;
+                }
+            });
+        if picked.is_some() || dismissed {
+            self.open = false;
+            // Failing to remember only means the screen is shown again next time.
+            let _ = storage::save(FILE, &true);
+        }
+        picked
+    }
+}
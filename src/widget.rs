@@ -0,0 +1,334 @@
+use crate::{
+    charset::Alphabet,
+    codes::{self, CodeInfo},
+    corrections::Corrections,
+    decoders::{Algorithm, Outcome},
+    format::InputFormat,
+    manifest,
+    panel_model::{self, PanelModel, Stages, View},
+    panels::{self, ByteDisplay, ParityOverlay},
+    pipeline::Pipeline,
+    session::Notes,
+};
+use egui::DragValue;
+use hamming::Code;
+use rand::thread_rng;
+
+/// The longest message kept, in bytes. Anything longer makes every frame crawl, so it is cut
+/// short.
+pub(crate) const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// The encode, error, and decode pipeline with its panels, for showing inside any egui app. It
+/// is the middle of the Hamming GUI app without the windows and menus around it.
+///
+/// ```ignore
+/// let mut demo = HammingDemoWidget::new();
+/// demo.set_text("Hello, world!");
+/// // Each frame:
+/// egui::CentralPanel::default().show(ctx, |ui| demo.ui(ui));
+/// ```
+pub struct HammingDemoWidget {
+    /// The message as typed, in `input_format`.
+    pub(crate) message_in: String,
+    pub(crate) input_format: InputFormat,
+    /// The bytes `message_in` last parsed to.
+    pub(crate) message: Vec<u8>,
+    /// Why `message_in` doesn't parse, if it doesn't.
+    pub(crate) parse_error: Option<String>,
+    /// The format and text `message` was parsed from.
+    parsed_from: Option<(InputFormat, String)>,
+    /// The length the message had before it was cut to `MAX_MESSAGE_BYTES`, and the length it
+    /// was cut to, until it is next edited.
+    pub(crate) truncated: Option<(usize, usize)>,
+    pub(crate) encoded: Vec<u8>,
+    /// The message `encoded` was encoded from.
+    pub(crate) encoded_from: Option<Vec<u8>>,
+    pub(crate) error: Vec<u8>,
+    pub(crate) with_error: Vec<u8>,
+    pub(crate) message_out: Option<String>,
+    /// The panels, in order.
+    pub(crate) panels: Vec<Box<dyn PanelModel>>,
+    pub(crate) code: &'static Code,
+    pub(crate) code_info: CodeInfo,
+    pub(crate) algorithm: Algorithm,
+    pub(crate) outcomes: Vec<Outcome>,
+    /// The error `with_error` and `outcomes` were worked out with, or `None` if the code has
+    /// changed since.
+    pub(crate) decoded_with: Option<Vec<u8>>,
+    pub(crate) pipeline: Pipeline,
+    pub(crate) corrections: Corrections,
+    /// The algorithm `corrections` and `message_out` were taken from, or `None` if `outcomes`
+    /// have changed since.
+    pub(crate) chosen_for: Option<Algorithm>,
+    pub(crate) alphabet: Alphabet,
+    pub(crate) parity_overlay: ParityOverlay,
+    pub(crate) hovered_bit: Option<usize>,
+    /// Codewords per block of the interleaver, 1 for none.
+    pub(crate) interleave_depth: usize,
+    /// The error pattern kept while the error is locked, including any bits past the end of a
+    /// message since made shorter, so that they come back when it grows again.
+    pub(crate) error_lock: Option<Vec<u8>>,
+    /// The error probability the widget's own Randomize Error button uses.
+    probability: f64,
+}
+
+impl Default for HammingDemoWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HammingDemoWidget {
+    pub fn new() -> Self {
+        let (code_info, code) = codes::pipeline_code(codes::EH16_11.name).unwrap();
+        Self {
+            message_in: String::new(),
+            input_format: InputFormat::Text,
+            message: Vec::new(),
+            parse_error: None,
+            parsed_from: None,
+            truncated: None,
+            encoded: Vec::new(),
+            encoded_from: None,
+            error: Vec::new(),
+            with_error: Vec::new(),
+            message_out: Some(String::new()),
+            panels: panel_model::all(),
+            code,
+            code_info: *code_info,
+            algorithm: Algorithm::Library,
+            outcomes: Vec::new(),
+            decoded_with: None,
+            pipeline: Pipeline::new(),
+            corrections: Corrections::default(),
+            chosen_for: None,
+            alphabet: Alphabet::Unrestricted,
+            parity_overlay: ParityOverlay::Off,
+            hovered_bit: None,
+            interleave_depth: 1,
+            error_lock: None,
+            probability: 0.02,
+        }
+    }
+
+    /// Makes `text` the message, typed as UTF-8 text.
+    pub fn set_text(&mut self, text: &str) {
+        self.message_in = text.to_string();
+        self.input_format = InputFormat::Text;
+    }
+
+    /// The message as decoded by the chosen decoder, if it could be.
+    pub fn decoded(&self) -> Option<&str> {
+        self.message_out.as_deref()
+    }
+
+    /// Switches to another code, encoding the message afresh. The error is cleared, since its
+    /// bits no longer line up with the same codewords. Returns whether the code changed.
+    pub(crate) fn set_code(&mut self, (info, code): &'static (CodeInfo, Code)) -> bool {
+        if self.code_info == *info {
+            return false;
+        }
+        self.code = code;
+        self.code_info = *info;
+        self.encoded_from = None;
+        self.decoded_with = None;
+        self.error.clear();
+        if let Some(locked) = &mut self.error_lock {
+            locked.clear();
+        }
+        self.hovered_bit = None;
+        true
+    }
+
+    /// Splits the widget into its panels and the stages they show, so that both can be borrowed
+    /// at once.
+    pub(crate) fn split_panels<'a>(
+        &'a mut self,
+        notes: &'a Notes,
+        byte_display: ByteDisplay,
+    ) -> (&'a mut Vec<Box<dyn PanelModel>>, Stages<'a>) {
+        let stages = Stages {
+            message_in: &mut self.message_in,
+            input_format: &mut self.input_format,
+            message: &self.message,
+            parse_error: self.parse_error.as_deref(),
+            truncated_from: self.truncated.map(|(from, _)| from),
+            alphabet: &mut self.alphabet,
+            encoded: &self.encoded,
+            interleave_depth: &mut self.interleave_depth,
+            error: &mut self.error,
+            with_error: &self.with_error,
+            message_out: &mut self.message_out,
+            algorithm: &mut self.algorithm,
+            code_info: &self.code_info,
+            outcomes: &self.outcomes,
+            corrections: &self.corrections,
+            byte_display,
+            parity_overlay: &mut self.parity_overlay,
+            hovered_bit: &mut self.hovered_bit,
+            notes,
+        };
+        (&mut self.panels, stages)
+    }
+
+    /// Runs the message through the encode, error, and decode stages.
+    pub fn update(&mut self) {
+        self.limit_message();
+        self.parse_message();
+
+        // Encoding and decoding happen on the pipeline's worker, and the panels show the last
+        // results until the next are in. Results for a code since switched from are dropped.
+        if let Some(results) = self.pipeline.poll() {
+            if results.info == self.code_info {
+                if let Some((message, encoded)) = results.encoded {
+                    self.encoded = encoded;
+                    self.encoded_from = Some(message);
+                }
+                self.with_error = results.with_error;
+                self.outcomes = results.outcomes;
+                self.decoded_with = Some(results.error);
+                self.chosen_for = None;
+            }
+        }
+        self.fit_error();
+
+        // This runs every frame, so a job is only sent when the message or the error has
+        // changed, which the code changing forces too. Every decoder is run, so that they can be
+        // compared.
+        if self.pipeline.pending_for().is_none() {
+            let message =
+                (self.encoded_from.as_ref() != Some(&self.message)).then(|| self.message.clone());
+            if message.is_some() || self.decoded_with.as_ref() != Some(&self.error) {
+                self.pipeline
+                    .send(self.code, self.code_info, message, self.error.clone());
+            }
+        }
+        if self.chosen_for != Some(self.algorithm) {
+            let outcome = self.outcomes.iter().find(|o| o.algorithm == self.algorithm);
+            if let Some(outcome) = outcome {
+                self.corrections =
+                    Corrections::new(self.code, &self.code_info, &self.with_error, outcome);
+            }
+            self.message_out = outcome
+                .and_then(|o| o.decoded.as_deref())
+                .and_then(|decoded| self.input_format.write(decoded));
+            self.chosen_for = Some(self.algorithm);
+        }
+    }
+
+    /// Cuts the error short or pads it with zeros to the length of the encoded stream. While the
+    /// error is locked, bits cut off are kept and come back as the stream grows again, until the
+    /// error is changed some other way.
+    fn fit_error(&mut self) {
+        let len = self.encoded.len();
+        if let Some(locked) = &mut self.error_lock {
+            let kept = &locked[..self.error.len().min(locked.len())];
+            if kept != self.error.as_slice() {
+                locked.clone_from(&self.error);
+            }
+            if self.error.len() != len {
+                self.error.clear();
+                self.error.extend(locked.iter().take(len));
+            }
+        }
+        self.error.resize_with(len, || 0);
+    }
+
+    /// Parses the message as typed into its bytes, when it or its format has changed. Text that
+    /// doesn't parse leaves the last bytes that did.
+    fn parse_message(&mut self) {
+        let (format, text) = (self.input_format, &self.message_in);
+        if matches!(&self.parsed_from, Some((f, t)) if *f == format && t == text) {
+            return;
+        }
+        // The decoded message is written in the same format.
+        if self.parsed_from.as_ref().map(|(f, _)| *f) != Some(format) {
+            self.chosen_for = None;
+        }
+        match format.parse(text) {
+            Ok(message) => {
+                self.message = message;
+                self.parse_error = None;
+            }
+            Err(e) => self.parse_error = Some(e),
+        }
+        self.parsed_from = Some((format, text.clone()));
+    }
+
+    /// Cuts an oversized message, such as a huge paste, down to `MAX_MESSAGE_BYTES`.
+    fn limit_message(&mut self) {
+        if self.message_in.len() > MAX_MESSAGE_BYTES {
+            let mut end = MAX_MESSAGE_BYTES;
+            while !self.message_in.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.truncated = Some((self.message_in.len(), end));
+            self.message_in.truncate(end);
+        } else if matches!(self.truncated, Some((_, kept)) if kept != self.message_in.len()) {
+            self.truncated = None;
+        }
+    }
+
+    /// Updates the pipeline and shows it: a row of controls for the code and the error, then
+    /// every panel top to bottom with arrows between them.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        self.update();
+        if self.pipeline.pending_for().is_some() {
+            // Nothing else repaints when the results come in.
+            ui.ctx().request_repaint();
+        }
+        ui.horizontal(|ui| {
+            let mut selected = None;
+            egui::ComboBox::from_label("Code")
+                .selected_text(self.code_info.name)
+                .show_ui(ui, |ui| {
+                    for pipeline in &codes::PIPELINE {
+                        let name = pipeline.0.name;
+                        let current = self.code_info == pipeline.0;
+                        if ui.selectable_label(current, name).clicked() {
+                            selected = Some(pipeline);
+                        }
+                    }
+                });
+            if let Some(pipeline) = selected {
+                self.set_code(pipeline);
+            }
+            ui.label("Error probability:");
+            ui.add(
+                DragValue::new(&mut self.probability)
+                    .clamp_range(0.0..=1.0)
+                    .speed(0.001)
+                    .max_decimals(4),
+            );
+            if ui.button("Randomize Error").clicked() {
+                let seed = manifest::new_seed(&mut thread_rng());
+                self.error = manifest::error_pattern(seed, self.error.len(), self.probability);
+            }
+            if ui.button("Clear Error").clicked() {
+                self.error.iter_mut().for_each(|b| *b = 0);
+            }
+        });
+        ui.separator();
+
+        let notes = Notes::default();
+        let (panels, mut stages) = self.split_panels(&notes, ByteDisplay::default());
+        egui::ScrollArea::vertical()
+            .id_source("hamming_demo_widget")
+            .show(ui, |ui| {
+                for (i, panel) in panels.iter_mut().enumerate() {
+                    if i > 0 {
+                        panels::arrow(ui);
+                    }
+                    ui.group(|ui| {
+                        panels::header(ui, panel.id(), || panel.copy_text(&stages));
+                        let view = View {
+                            highlights: None,
+                            scroll_to: None,
+                            read_only: false,
+                        };
+                        panel.ui(ui, &mut stages, view);
+                    });
+                }
+            });
+    }
+}